@@ -0,0 +1,381 @@
+#[cfg(feature = "std")]
+use solana_program::msg;
+use solana_program::program_error::ProgramError;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MplCoreWrapperError {
+    #[error("Asset data exceeds the maximum account size")]
+    AssetTooLarge,
+
+    #[error("Oracle adapter must specify at least one lifecycle check")]
+    EmptyOracleChecks,
+
+    #[error("Asset signer does not match the PDA derived from the asset")]
+    InvalidAssetSigner,
+
+    #[error("Log wrapper is required for ledger-state assets")]
+    MissingLogWrapper,
+
+    #[error("Plugin cannot be managed by the given authority")]
+    IncompatiblePluginAuthority,
+
+    #[error("Owner not provided and defaulting to the payer was not requested")]
+    MissingOwner,
+
+    #[error("Too many plugins")]
+    TooManyPlugins,
+
+    #[error("Required account must not be the default pubkey")]
+    InvalidAccountAddress,
+
+    #[error("Account does not match the authority in instruction data")]
+    AuthorityMismatch,
+
+    #[error("Batch size must be between one and the maximum batch size")]
+    InvalidBatchSize,
+
+    #[error("Paired optional accounts must be both present or both absent")]
+    InconsistentOptionalAccounts,
+
+    #[error("Signer seeds do not derive the expected program address")]
+    InvalidSignerSeeds,
+
+    #[error("Authority is not the owner or a transfer delegate of the asset")]
+    UnauthorizedTransferAuthority,
+
+    #[error("Attributes exceed the maximum count")]
+    TooManyAttributes,
+
+    #[error("Asset metadata is immutable")]
+    MetadataImmutable,
+
+    #[error("Asset does not match the address derived from the nonce")]
+    InvalidAssetAddress,
+
+    #[error("Permanent plugins cannot be removed")]
+    CannotRemovePermanentPlugin,
+
+    #[error("Plugin is not present on the asset")]
+    PluginNotFound,
+
+    #[error("New owner is owned by a program outside the allow-list")]
+    UnsafeTransferRecipient,
+
+    #[error("Lamport arithmetic overflowed")]
+    LamportOverflow,
+
+    #[error("Uri exceeds the maximum length")]
+    UriTooLong,
+
+    #[error("Only one of new uri and uri suffix may be set")]
+    ConflictingUriUpdate,
+
+    #[error("Authority is not in the approved set")]
+    AuthorityNotApproved,
+
+    #[error("CPIs in one instruction must target the same mpl_core account")]
+    MplCoreProgramMismatch,
+
+    #[error("Nested CPIs exceeded the maximum depth")]
+    CpiDepthExceeded,
+
+    #[error("App data adapters need a data authority")]
+    InvalidDataAuthority,
+
+    #[error("Instruction data is empty")]
+    EmptyInstructionData,
+
+    #[error("Account data does not start with the expected mpl_core key")]
+    InvalidAccountKey,
+
+    #[error("None of the authorized accounts signed the transaction")]
+    NoAuthorizedSigner,
+
+    #[error("Asset carries an AddBlocker plugin, so no plugin can be added")]
+    PluginAdditionsBlocked,
+
+    #[error("Account data is smaller than the expected minimum length")]
+    AccountDataTooSmall,
+
+    #[error("Name or uri contains ASCII control characters")]
+    InvalidMetadataCharacters,
+
+    #[error("Asset name or uri changed from the expected value")]
+    ConcurrentModification,
+
+    #[error("Minting into the collection needs its update authority or a delegate to sign")]
+    CollectionAuthorityRequired,
+
+    #[error("A program id was passed in a role account slot")]
+    ProgramAccountInWrongSlot,
+
+    #[error("External plugin adapter has no lifecycle checks to update")]
+    AdapterWithoutLifecycleChecks,
+
+    #[error("Asset is compressed (ledger state); decompress it before updating")]
+    AssetIsCompressed,
+
+    #[error("Plugin type has no default value")]
+    UnsupportedPluginDefault,
+
+    #[error("Instruction was already processed")]
+    AlreadyProcessed,
+
+    #[error("Asset is not rent exempt after creation")]
+    AssetNotRentExempt,
+
+    #[error("Transfer requires the asset owner's signature")]
+    OwnerSignatureRequired,
+
+    #[error("Update leaves the asset unchanged")]
+    NoEffectiveChange,
+
+    #[error("Authority is not a burn delegate of the asset")]
+    UnauthorizedBurnDelegate,
+
+    #[error("Account count does not match the instruction's account layout")]
+    AccountLayoutMismatch,
+
+    #[error("Account must not be writable")]
+    UnexpectedWritableAccount,
+
+    #[error("Plugins contain more than one plugin of the same type")]
+    DuplicatePluginType,
+
+    #[error("Collection account is required for an asset in a collection")]
+    CollectionAccountRequired,
+
+    #[error("Authority is not the freeze delegate of the asset")]
+    UnauthorizedFreezeDelegate,
+
+    #[error("Authority and new owner must be different accounts")]
+    DuplicateAccount,
+
+    #[error("Optional account is present but unused by the instruction data")]
+    UnexpectedAccount,
+
+    #[error("Signer roles that must differ share one key")]
+    DuplicateSigner,
+
+    #[error("Plugins are given both structured and as raw bytes")]
+    ConflictingPluginSources,
+
+    #[error("Collection has reached its maximum size")]
+    CollectionFull,
+
+    #[error("Asset already belongs to a collection")]
+    AssetAlreadyInCollection,
+
+    #[error("Asset does not belong to the given collection")]
+    AssetNotInCollection,
+}
+
+impl From<MplCoreWrapperError> for ProgramError {
+    fn from(e: MplCoreWrapperError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl TryFrom<u32> for MplCoreWrapperError {
+    type Error = ProgramError;
+
+    fn try_from(code: u32) -> Result<Self, Self::Error> {
+        match code {
+            0 => Ok(Self::AssetTooLarge),
+            1 => Ok(Self::EmptyOracleChecks),
+            2 => Ok(Self::InvalidAssetSigner),
+            3 => Ok(Self::MissingLogWrapper),
+            4 => Ok(Self::IncompatiblePluginAuthority),
+            5 => Ok(Self::MissingOwner),
+            6 => Ok(Self::TooManyPlugins),
+            7 => Ok(Self::InvalidAccountAddress),
+            8 => Ok(Self::AuthorityMismatch),
+            9 => Ok(Self::InvalidBatchSize),
+            10 => Ok(Self::InconsistentOptionalAccounts),
+            11 => Ok(Self::InvalidSignerSeeds),
+            12 => Ok(Self::UnauthorizedTransferAuthority),
+            13 => Ok(Self::TooManyAttributes),
+            14 => Ok(Self::MetadataImmutable),
+            15 => Ok(Self::InvalidAssetAddress),
+            16 => Ok(Self::CannotRemovePermanentPlugin),
+            17 => Ok(Self::PluginNotFound),
+            18 => Ok(Self::UnsafeTransferRecipient),
+            19 => Ok(Self::LamportOverflow),
+            20 => Ok(Self::UriTooLong),
+            21 => Ok(Self::ConflictingUriUpdate),
+            22 => Ok(Self::AuthorityNotApproved),
+            23 => Ok(Self::MplCoreProgramMismatch),
+            24 => Ok(Self::CpiDepthExceeded),
+            25 => Ok(Self::InvalidDataAuthority),
+            26 => Ok(Self::EmptyInstructionData),
+            27 => Ok(Self::InvalidAccountKey),
+            28 => Ok(Self::NoAuthorizedSigner),
+            29 => Ok(Self::PluginAdditionsBlocked),
+            30 => Ok(Self::AccountDataTooSmall),
+            31 => Ok(Self::InvalidMetadataCharacters),
+            32 => Ok(Self::ConcurrentModification),
+            33 => Ok(Self::CollectionAuthorityRequired),
+            34 => Ok(Self::ProgramAccountInWrongSlot),
+            35 => Ok(Self::AdapterWithoutLifecycleChecks),
+            36 => Ok(Self::AssetIsCompressed),
+            37 => Ok(Self::UnsupportedPluginDefault),
+            38 => Ok(Self::AlreadyProcessed),
+            39 => Ok(Self::AssetNotRentExempt),
+            40 => Ok(Self::OwnerSignatureRequired),
+            41 => Ok(Self::NoEffectiveChange),
+            42 => Ok(Self::UnauthorizedBurnDelegate),
+            43 => Ok(Self::AccountLayoutMismatch),
+            44 => Ok(Self::UnexpectedWritableAccount),
+            45 => Ok(Self::DuplicatePluginType),
+            46 => Ok(Self::CollectionAccountRequired),
+            47 => Ok(Self::UnauthorizedFreezeDelegate),
+            48 => Ok(Self::DuplicateAccount),
+            49 => Ok(Self::UnexpectedAccount),
+            50 => Ok(Self::DuplicateSigner),
+            51 => Ok(Self::ConflictingPluginSources),
+            52 => Ok(Self::CollectionFull),
+            53 => Ok(Self::AssetAlreadyInCollection),
+            54 => Ok(Self::AssetNotInCollection),
+            _ => Err(ProgramError::Custom(code)),
+        }
+    }
+}
+
+/// Recovers the crate error from a `Custom` code; any other error is handed back.
+impl TryFrom<ProgramError> for MplCoreWrapperError {
+    type Error = ProgramError;
+
+    fn try_from(error: ProgramError) -> Result<Self, Self::Error> {
+        match error {
+            ProgramError::Custom(code) => Self::try_from(code),
+            error => Err(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl MplCoreWrapperError {
+    /// Searchable `MplCoreWrapperError::<Variant>` name followed by the error message.
+    pub fn log_message(&self) -> String {
+        format!("MplCoreWrapperError::{:?}: {}", self, self)
+    }
+
+    pub fn log(&self) {
+        msg!("{}", self.log_message());
+    }
+}
+
+/// Logs the variant name of `Custom` errors raised by this program so they
+/// show up as searchable strings in transaction logs.
+#[cfg(feature = "std")]
+pub fn log_error(error: &ProgramError) {
+    if let Ok(error) = MplCoreWrapperError::try_from(error.clone()) {
+        error.log();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_code_roundtrip() {
+        for error in [
+            MplCoreWrapperError::AssetTooLarge,
+            MplCoreWrapperError::EmptyOracleChecks,
+            MplCoreWrapperError::InvalidAssetSigner,
+            MplCoreWrapperError::MissingLogWrapper,
+            MplCoreWrapperError::IncompatiblePluginAuthority,
+            MplCoreWrapperError::MissingOwner,
+            MplCoreWrapperError::TooManyPlugins,
+            MplCoreWrapperError::InvalidAccountAddress,
+            MplCoreWrapperError::AuthorityMismatch,
+            MplCoreWrapperError::InvalidBatchSize,
+            MplCoreWrapperError::InconsistentOptionalAccounts,
+            MplCoreWrapperError::InvalidSignerSeeds,
+            MplCoreWrapperError::UnauthorizedTransferAuthority,
+            MplCoreWrapperError::TooManyAttributes,
+            MplCoreWrapperError::MetadataImmutable,
+            MplCoreWrapperError::InvalidAssetAddress,
+            MplCoreWrapperError::CannotRemovePermanentPlugin,
+            MplCoreWrapperError::PluginNotFound,
+            MplCoreWrapperError::UnsafeTransferRecipient,
+            MplCoreWrapperError::LamportOverflow,
+            MplCoreWrapperError::UriTooLong,
+            MplCoreWrapperError::ConflictingUriUpdate,
+            MplCoreWrapperError::AuthorityNotApproved,
+            MplCoreWrapperError::MplCoreProgramMismatch,
+            MplCoreWrapperError::CpiDepthExceeded,
+            MplCoreWrapperError::InvalidDataAuthority,
+            MplCoreWrapperError::EmptyInstructionData,
+            MplCoreWrapperError::InvalidAccountKey,
+            MplCoreWrapperError::NoAuthorizedSigner,
+            MplCoreWrapperError::PluginAdditionsBlocked,
+            MplCoreWrapperError::AccountDataTooSmall,
+            MplCoreWrapperError::InvalidMetadataCharacters,
+            MplCoreWrapperError::ConcurrentModification,
+            MplCoreWrapperError::CollectionAuthorityRequired,
+            MplCoreWrapperError::ProgramAccountInWrongSlot,
+            MplCoreWrapperError::AdapterWithoutLifecycleChecks,
+            MplCoreWrapperError::AssetIsCompressed,
+            MplCoreWrapperError::UnsupportedPluginDefault,
+            MplCoreWrapperError::AlreadyProcessed,
+            MplCoreWrapperError::AssetNotRentExempt,
+            MplCoreWrapperError::OwnerSignatureRequired,
+            MplCoreWrapperError::NoEffectiveChange,
+            MplCoreWrapperError::UnauthorizedBurnDelegate,
+            MplCoreWrapperError::AccountLayoutMismatch,
+            MplCoreWrapperError::UnexpectedWritableAccount,
+            MplCoreWrapperError::DuplicatePluginType,
+            MplCoreWrapperError::CollectionAccountRequired,
+            MplCoreWrapperError::UnauthorizedFreezeDelegate,
+            MplCoreWrapperError::DuplicateAccount,
+            MplCoreWrapperError::UnexpectedAccount,
+            MplCoreWrapperError::DuplicateSigner,
+            MplCoreWrapperError::ConflictingPluginSources,
+            MplCoreWrapperError::CollectionFull,
+            MplCoreWrapperError::AssetAlreadyInCollection,
+            MplCoreWrapperError::AssetNotInCollection,
+        ] {
+            assert_eq!(MplCoreWrapperError::try_from(error as u32), Ok(error));
+            assert_eq!(
+                MplCoreWrapperError::try_from(ProgramError::from(error)),
+                Ok(error)
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_from_foreign_program_error() {
+        assert_eq!(
+            MplCoreWrapperError::try_from(ProgramError::InvalidAccountOwner),
+            Err(ProgramError::InvalidAccountOwner)
+        );
+        assert_eq!(
+            MplCoreWrapperError::try_from(ProgramError::Custom(u32::MAX)),
+            Err(ProgramError::Custom(u32::MAX))
+        );
+    }
+
+    #[test]
+    fn test_error_into_program_error() {
+        assert_eq!(
+            ProgramError::from(MplCoreWrapperError::AssetTooLarge),
+            ProgramError::Custom(0)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_error_log_message() {
+        assert_eq!(
+            MplCoreWrapperError::AssetTooLarge.log_message(),
+            "MplCoreWrapperError::AssetTooLarge: Asset data exceeds the maximum account size",
+        );
+        assert_eq!(
+            MplCoreWrapperError::MissingLogWrapper.log_message(),
+            "MplCoreWrapperError::MissingLogWrapper: Log wrapper is required for ledger-state assets",
+        );
+    }
+}
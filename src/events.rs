@@ -0,0 +1,86 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey};
+
+/// Marks a log line as a base64 encoded [`MplCoreWrapperEvent`].
+pub const EVENT_PREFIX: &str = "EVENT:";
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CreateNftEvent {
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+    pub collection: Option<Pubkey>,
+    pub name: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TransferNftEvent {
+    pub asset: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UpdateNftEvent {
+    pub asset: Pubkey,
+    pub new_name: Option<String>,
+    pub new_uri: Option<String>,
+}
+
+/// Borsh encodes the variant index first, so new events must be appended.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub enum MplCoreWrapperEvent {
+    CreateNft(CreateNftEvent),
+    TransferNft(TransferNftEvent),
+    UpdateNft(UpdateNftEvent),
+}
+
+impl MplCoreWrapperEvent {
+    /// Log line for the event: [`EVENT_PREFIX`] followed by base64 borsh.
+    pub fn encode(&self) -> Result<String, ProgramError> {
+        Ok(format!(
+            "{}{}",
+            EVENT_PREFIX,
+            STANDARD.encode(self.try_to_vec()?)
+        ))
+    }
+
+    pub fn decode(log: &str) -> Result<Self, ProgramError> {
+        let data = log
+            .strip_prefix(EVENT_PREFIX)
+            .and_then(|data| STANDARD.decode(data).ok())
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        Ok(Self::try_from_slice(&data)?)
+    }
+
+    pub fn emit(&self) -> ProgramResult {
+        msg!("{}", self.encode()?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_roundtrip() {
+        let event = MplCoreWrapperEvent::TransferNft(TransferNftEvent {
+            asset: Pubkey::new_unique(),
+            new_owner: Pubkey::new_unique(),
+        });
+
+        let log = event.encode().unwrap();
+        assert!(log.starts_with(EVENT_PREFIX));
+        assert_eq!(MplCoreWrapperEvent::decode(&log), Ok(event));
+    }
+
+    #[test]
+    fn test_event_decode_rejects_other_logs() {
+        assert_eq!(
+            MplCoreWrapperEvent::decode("Program log: hello"),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
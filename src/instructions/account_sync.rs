@@ -0,0 +1,131 @@
+//! Guards against an accounts struct drifting from the CPI it feeds.
+//!
+//! Each test builds the accounts struct from a slice of distinct keys, wires
+//! its fields into mpl_core's instruction builder the same way the handler
+//! wires its CPI builder, and checks every mpl_core account lands on the key
+//! clients put at the matching position of the slice.
+
+use mpl_core::{
+    instructions::{CreateV1Builder, TransferV1Builder, UpdateV2Builder},
+    types::UpdateAuthority,
+};
+use solana_program::{account_info::AccountInfo, instruction::Instruction, pubkey::Pubkey};
+use solana_sdk_ids::system_program;
+
+use crate::{
+    instructions::{CreateNftV1Accounts, TransferNftV1Accounts, UpdateNftV1Accounts},
+    utils::test_utils::*,
+};
+
+fn keys(instruction: &Instruction) -> Vec<Pubkey> {
+    instruction
+        .accounts
+        .iter()
+        .map(|meta| meta.pubkey)
+        .collect()
+}
+
+fn slice_keys(accounts: &[AccountInfo], positions: &[usize]) -> Vec<Pubkey> {
+    positions.iter().map(|&i| *accounts[i].key).collect()
+}
+
+#[test]
+fn test_create_nft_v1_accounts_in_sync() {
+    let accounts = vec![
+        new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), false, true, 1, 0, mpl_core::ID),
+        new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID),
+        new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+        new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+    ];
+    let parsed = CreateNftV1Accounts::try_from(accounts.as_slice()).unwrap();
+
+    let instruction = CreateV1Builder::new()
+        .asset(*parsed.asset.key)
+        .collection(parsed.collection.map(|a| *a.key))
+        .authority(parsed.authority.map(|a| *a.key))
+        .payer(*parsed.payer.key)
+        .owner(parsed.owner.map(|a| *a.key))
+        .update_authority(parsed.update_authority.map(|a| *a.key))
+        .system_program(*parsed.system_program.unwrap().key)
+        .log_wrapper(parsed.log_wrapper.map(|a| *a.key))
+        .name(String::new())
+        .uri(String::new())
+        .instruction();
+
+    assert_eq!(
+        keys(&instruction),
+        slice_keys(&accounts, &[0, 1, 2, 3, 4, 5, 6, 7])
+    );
+}
+
+#[test]
+fn test_update_nft_v1_accounts_in_sync() {
+    let accounts = vec![
+        new_test_account(Pubkey::new_unique(), false, true, 1, 0, mpl_core::ID),
+        new_test_account(Pubkey::new_unique(), false, true, 1, 0, mpl_core::ID),
+        new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+        new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID),
+        new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+        new_test_account(Pubkey::new_unique(), false, true, 1, 0, mpl_core::ID),
+        new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+    ];
+    let parsed = UpdateNftV1Accounts::try_from(accounts.as_slice()).unwrap();
+
+    let instruction = UpdateV2Builder::new()
+        .asset(*parsed.asset.key)
+        .collection(parsed.collection.map(|a| *a.key))
+        .authority(parsed.authority.map(|a| *a.key))
+        .payer(*parsed.payer.key)
+        .new_collection(parsed.new_collection.map(|a| *a.key))
+        .system_program(*parsed.system_program.unwrap().key)
+        .log_wrapper(parsed.log_wrapper.map(|a| *a.key))
+        .instruction();
+
+    // mpl_core takes the payer before the authority, and never sees the new
+    // collection authority.
+    assert_eq!(
+        keys(&instruction),
+        slice_keys(&accounts, &[0, 1, 3, 2, 7, 4, 5])
+    );
+}
+
+#[test]
+fn test_transfer_nft_v1_accounts_in_sync() {
+    let authority = Pubkey::new_unique();
+    let accounts = vec![
+        new_asset_account(authority, UpdateAuthority::None, &[]),
+        new_test_account(Pubkey::new_unique(), false, true, 1, 0, mpl_core::ID),
+        new_test_account(authority, true, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+        new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID),
+        new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+    ];
+    let parsed = TransferNftV1Accounts::try_from(accounts.as_slice()).unwrap();
+
+    let instruction = TransferV1Builder::new()
+        .asset(*parsed.asset.key)
+        .collection(parsed.collection.map(|a| *a.key))
+        .payer(*parsed.payer.key)
+        .authority(parsed.authority.map(|a| *a.key))
+        .new_owner(*parsed.new_owner.key)
+        .system_program(parsed.system_program.map(|a| *a.key))
+        .log_wrapper(parsed.log_wrapper.map(|a| *a.key))
+        .instruction();
+
+    // mpl_core takes the payer before the authority and new owner, and fills
+    // the absent optional system program with its own id.
+    assert_eq!(
+        keys(&instruction),
+        slice_keys(&accounts, &[0, 1, 4, 2, 3, 7, 6])
+    );
+}
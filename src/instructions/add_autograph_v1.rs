@@ -0,0 +1,279 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    fetch_asset_plugin,
+    instructions::{AddPluginV1CpiBuilder, UpdatePluginV1CpiBuilder},
+    types::{Autograph, AutographSignature, Plugin, PluginType},
+};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+#[cfg(feature = "asset-state")]
+use crate::utils::check_collection_account;
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    instructions::Instructions,
+    plugins::check_plugin_additions_allowed,
+    utils::{
+        invoke_cpi, AccountCheck, MplCoreAccount, NonDefaultKey, NonWritable, NotProgramAccount,
+        OptionalAccountCheck, ProcessInstruction, SignerAccount, SystemAccount, ToOptionalAccount,
+        WritableAccount,
+    },
+};
+
+#[derive(Debug)]
+pub struct AddAutographV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for AddAutographV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, payer, authority, system_program, log_wrapper, mpl_core] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(2, payer)?;
+        NotProgramAccount::check_at(2, payer)?;
+        NonDefaultKey::check_at(3, authority)?;
+        NotProgramAccount::check_at(3, authority)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        WritableAccount::check_at(2, payer)?;
+        SignerAccount::check_at(2, payer)?;
+        SignerAccount::check_at(3, authority)?;
+        SystemAccount::check_at(4, system_program)?;
+        NonWritable::check_optional_at(5, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(6, mpl_core)?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            payer,
+            authority,
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for AddAutographV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            account_meta(self.payer, true, true),
+            account_meta(self.authority, true, false),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct AddAutographV1InstructionData {
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub struct AddAutographV1<'a, 'info> {
+    pub accounts: AddAutographV1Accounts<'a, 'info>,
+    pub instruction_data: AddAutographV1InstructionData,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], AddAutographV1InstructionData)>
+    for AddAutographV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], AddAutographV1InstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AddAutographV1Accounts::try_from(accounts)?;
+        #[cfg(feature = "asset-state")]
+        check_collection_account(accounts.asset, accounts.collection)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a, 'info> AddAutographV1<'a, 'info> {
+    /// Appends the authority's signature to `existing`, or starts a new plugin.
+    pub fn autograph(&self, existing: Option<Autograph>) -> Autograph {
+        let mut autograph = existing.unwrap_or(Autograph { signatures: vec![] });
+
+        autograph.signatures.push(AutographSignature {
+            address: *self.accounts.authority.key,
+            message: self.instruction_data.message.clone(),
+        });
+
+        autograph
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for AddAutographV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        let existing = fetch_asset_plugin::<Autograph>(self.accounts.asset, PluginType::Autograph)
+            .ok()
+            .map(|(_, autograph, _)| autograph);
+
+        if existing.is_some() {
+            invoke_cpi("UpdatePluginV1", self.accounts.asset.key, || {
+                UpdatePluginV1CpiBuilder::new(self.accounts.mpl_core)
+                    .asset(self.accounts.asset)
+                    .collection(self.accounts.collection)
+                    .payer(self.accounts.payer)
+                    .authority(Some(self.accounts.authority))
+                    .system_program(self.accounts.system_program)
+                    .log_wrapper(self.accounts.log_wrapper)
+                    .plugin(Plugin::Autograph(self.autograph(existing)))
+                    .invoke()
+            })?;
+        } else {
+            check_plugin_additions_allowed(self.accounts.asset)?;
+
+            invoke_cpi("AddPluginV1", self.accounts.asset.key, || {
+                AddPluginV1CpiBuilder::new(self.accounts.mpl_core)
+                    .asset(self.accounts.asset)
+                    .collection(self.accounts.collection)
+                    .payer(self.accounts.payer)
+                    .authority(Some(self.accounts.authority))
+                    .system_program(self.accounts.system_program)
+                    .log_wrapper(self.accounts.log_wrapper)
+                    .plugin(Plugin::Autograph(self.autograph(None)))
+                    .invoke()
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn process_add_autograph_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::AddAutographV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    AddAutographV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::UpdateAuthority;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(authority_is_signer: bool) -> Vec<AccountInfo<'static>> {
+        let asset = new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]);
+        let collection =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let authority = new_test_account(
+            Pubkey::new_unique(),
+            authority_is_signer,
+            false,
+            1,
+            0,
+            system_program::ID,
+        );
+        let system_program =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let log_wrapper =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        vec![
+            asset,
+            collection,
+            payer,
+            authority,
+            system_program,
+            log_wrapper,
+            mpl_core,
+        ]
+    }
+
+    #[test]
+    fn test_add_autograph_forwards_message() {
+        let accounts = new_accounts(true);
+        let instruction = AddAutographV1::try_from((
+            accounts.as_slice(),
+            AddAutographV1InstructionData {
+                message: "gm".to_string(),
+            },
+        ))
+        .unwrap();
+
+        let existing = Autograph {
+            signatures: vec![AutographSignature {
+                address: Pubkey::new_unique(),
+                message: "first".to_string(),
+            }],
+        };
+
+        let autograph = instruction.autograph(Some(existing));
+        assert_eq!(autograph.signatures.len(), 2);
+        assert_eq!(
+            autograph.signatures[1],
+            AutographSignature {
+                address: *accounts[3].key,
+                message: "gm".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_add_autograph_authority_not_signer() {
+        let accounts = new_accounts(false);
+        let res = AddAutographV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[test]
+    fn test_add_autograph_account_not_enough_accounts() {
+        let accounts = vec![];
+        let res = AddAutographV1Accounts::try_from(accounts.as_slice());
+        assert!(
+            res.is_err(),
+            "expected failure because account is not enough, but got Ok: {:?}",
+            res
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_add_autograph_account_metas() {
+        let accounts = new_accounts(true);
+        let metas = AddAutographV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            AddAutographV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
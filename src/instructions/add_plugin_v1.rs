@@ -0,0 +1,406 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    instructions::AddPluginV1CpiBuilder,
+    types::{Plugin, PluginAuthority},
+};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError, rent::Rent,
+    sysvar::Sysvar,
+};
+
+#[cfg(feature = "asset-state")]
+use crate::utils::check_collection_account;
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    instructions::Instructions,
+    plugins::{check_plugin_additions_allowed, PluginValidation},
+    utils::{
+        fund_realloc, invoke_cpi, plugin_realloc_size, AccountCheck, MinDataLen, MplCoreAccount,
+        NonDefaultKey, NonWritable, NotProgramAccount, OptionalAccountCheck, ProcessInstruction,
+        SignerAccount, SystemAccount, ToOptionalAccount, WritableAccount, BASE_ASSET_MIN_LEN,
+        MAX_ASSET_DATA_LEN,
+    },
+};
+
+#[derive(Debug)]
+pub struct AddPluginV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub authority: Option<&'a AccountInfo<'info>>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for AddPluginV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, payer, authority, system_program, log_wrapper, mpl_core] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(2, payer)?;
+        NotProgramAccount::check_at(2, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        WritableAccount::check_at(2, payer)?;
+        SignerAccount::check_at(2, payer)?;
+        SignerAccount::check_optional_at(3, authority.to_optional())?;
+        SystemAccount::check_at(4, system_program)?;
+        NonWritable::check_optional_at(5, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(6, mpl_core)?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            payer,
+            authority: authority.to_optional(),
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for AddPluginV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            account_meta(self.payer, true, true),
+            optional_account_meta(self.authority, true, false),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct AddPluginV1InstructionData {
+    pub plugin: Plugin,
+    pub init_authority: Option<PluginAuthority>,
+    /// Tops the asset up from the payer for its post-add size before the CPI.
+    pub fund_realloc: bool,
+}
+
+impl PluginValidation for AddPluginV1InstructionData {
+    fn plugins(&self) -> Vec<(&Plugin, Option<&PluginAuthority>)> {
+        vec![(&self.plugin, self.init_authority.as_ref())]
+    }
+}
+
+#[derive(Debug)]
+pub struct AddPluginV1<'a, 'info> {
+    pub accounts: AddPluginV1Accounts<'a, 'info>,
+    pub instruction_data: AddPluginV1InstructionData,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], AddPluginV1InstructionData)>
+    for AddPluginV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], AddPluginV1InstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AddPluginV1Accounts::try_from(accounts)?;
+        #[cfg(feature = "asset-state")]
+        check_collection_account(accounts.asset, accounts.collection)?;
+        instruction_data.validate_plugins(1, MAX_ASSET_DATA_LEN)?;
+        check_plugin_additions_allowed(accounts.asset)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a, 'info> AddPluginV1<'a, 'info> {
+    /// Asset size after the add. Without an explicit authority mpl_core uses
+    /// the plugin's manager, which serializes to the same single byte as
+    /// `UpdateAuthority`.
+    pub fn realloc_size(&self) -> Result<usize, ProgramError> {
+        MinDataLen::check(self.accounts.asset, BASE_ASSET_MIN_LEN)?;
+        let authority = self
+            .instruction_data
+            .init_authority
+            .clone()
+            .unwrap_or(PluginAuthority::UpdateAuthority);
+
+        plugin_realloc_size(
+            &self.accounts.asset.try_borrow_data()?,
+            &self.instruction_data.plugin,
+            &authority,
+        )
+    }
+
+    /// Tops the asset up from the payer so it is rent exempt at
+    /// [`Self::realloc_size`] before mpl_core grows it.
+    pub fn fund_realloc(&self, rent: &Rent) -> ProgramResult {
+        fund_realloc(
+            self.accounts.asset,
+            self.accounts.payer,
+            self.accounts.system_program,
+            self.realloc_size()?,
+            rent,
+        )
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for AddPluginV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        if self.instruction_data.fund_realloc {
+            self.fund_realloc(&Rent::get()?)?;
+        }
+
+        let mut add_plugin_cpi = AddPluginV1CpiBuilder::new(self.accounts.mpl_core);
+
+        add_plugin_cpi
+            .asset(self.accounts.asset)
+            .collection(self.accounts.collection)
+            .payer(self.accounts.payer)
+            .authority(self.accounts.authority)
+            .system_program(self.accounts.system_program)
+            .log_wrapper(self.accounts.log_wrapper)
+            .plugin(self.instruction_data.plugin);
+
+        if let Some(init_authority) = self.instruction_data.init_authority {
+            add_plugin_cpi.init_authority(init_authority);
+        }
+
+        invoke_cpi("AddPluginV1", self.accounts.asset.key, || {
+            add_plugin_cpi.invoke()
+        })
+    }
+}
+
+pub fn process_add_plugin_v1(accounts: &[AccountInfo], instruction: Instructions) -> ProgramResult {
+    let Instructions::AddPluginV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    AddPluginV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::MplCoreWrapperError, utils::test_utils::*};
+    use mpl_core::types::{AddBlocker, FreezeDelegate, PermanentBurnDelegate, UpdateAuthority};
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+    use solana_system_interface::instruction as system_instruction;
+
+    fn new_accounts(asset: AccountInfo<'static>) -> Vec<AccountInfo<'static>> {
+        let collection =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let authority =
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        let system_program =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let log_wrapper =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        vec![
+            asset,
+            collection,
+            payer,
+            authority,
+            system_program,
+            log_wrapper,
+            mpl_core,
+        ]
+    }
+
+    #[test]
+    fn test_add_plugin_realloc_size_and_top_up() {
+        let plugin = Plugin::FreezeDelegate(FreezeDelegate { frozen: true });
+        let owner = Pubkey::new_unique();
+        let accounts = new_accounts(new_asset_account(owner, UpdateAuthority::None, &[]));
+        let current_len = accounts[0].data_len();
+
+        let instruction = AddPluginV1::try_from((
+            accounts.as_slice(),
+            AddPluginV1InstructionData {
+                plugin: plugin.clone(),
+                init_authority: None,
+                fund_realloc: true,
+            },
+        ))
+        .unwrap();
+
+        let expected = new_asset_account(
+            owner,
+            UpdateAuthority::None,
+            &[(plugin, PluginAuthority::Owner)],
+        );
+        let new_len = instruction.realloc_size().unwrap();
+        assert_eq!(new_len, expected.data_len());
+
+        let rent = Rent::default();
+        assert_eq!(
+            crate::utils::realloc_top_up(&rent, rent.minimum_balance(current_len), new_len),
+            rent.minimum_balance(new_len) - rent.minimum_balance(current_len)
+        );
+
+        let res = instruction.fund_realloc(&rent);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(
+            take_recorded_cpi_targets(),
+            vec![("FundRealloc", *accounts[0].key)]
+        );
+        assert_eq!(
+            take_recorded_instructions(),
+            vec![system_instruction::transfer(
+                accounts[2].key,
+                accounts[0].key,
+                crate::utils::realloc_top_up(&rent, accounts[0].lamports(), new_len)
+            )]
+        );
+    }
+
+    #[test]
+    fn test_add_plugin_without_funding() {
+        let accounts = new_accounts(new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::None,
+            &[],
+        ));
+
+        let instruction = AddPluginV1::try_from((
+            accounts.as_slice(),
+            AddPluginV1InstructionData {
+                plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                init_authority: Some(PluginAuthority::Owner),
+                fund_realloc: false,
+            },
+        ))
+        .unwrap();
+
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_add_plugin_incompatible_authority() {
+        let accounts = new_accounts(new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::None,
+            &[],
+        ));
+
+        let res = AddPluginV1::try_from((
+            accounts.as_slice(),
+            AddPluginV1InstructionData {
+                plugin: Plugin::PermanentBurnDelegate(PermanentBurnDelegate {}),
+                init_authority: Some(PluginAuthority::Owner),
+                fund_realloc: false,
+            },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::IncompatiblePluginAuthority.into()
+        );
+    }
+
+    #[test]
+    fn test_add_plugin_after_add_blocker() {
+        let accounts = new_accounts(new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::None,
+            &[(
+                Plugin::AddBlocker(AddBlocker {}),
+                PluginAuthority::UpdateAuthority,
+            )],
+        ));
+
+        let res = AddPluginV1::try_from((
+            accounts.as_slice(),
+            AddPluginV1InstructionData {
+                plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                init_authority: Some(PluginAuthority::Owner),
+                fund_realloc: false,
+            },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::PluginAdditionsBlocked.into()
+        );
+    }
+
+    #[cfg(feature = "asset-state")]
+    fn add_plugin_to_collection_asset(supply_collection: bool) -> ProgramResult {
+        let collection = new_collection_account(Pubkey::new_unique());
+        let mut accounts = new_accounts(new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::Collection(*collection.key),
+            &[],
+        ));
+        if supply_collection {
+            accounts[1] = collection;
+        }
+
+        AddPluginV1::try_from((
+            accounts.as_slice(),
+            AddPluginV1InstructionData {
+                plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                init_authority: None,
+                fund_realloc: false,
+            },
+        ))
+        .map(|_| ())
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_add_plugin_collection_asset_with_collection() {
+        let res = add_plugin_to_collection_asset(true);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_add_plugin_collection_asset_missing_collection() {
+        assert_eq!(
+            add_plugin_to_collection_asset(false),
+            Err(MplCoreWrapperError::CollectionAccountRequired.into())
+        );
+    }
+
+    #[test]
+    fn test_add_plugin_account_not_enough_accounts() {
+        let accounts = vec![];
+        let res = AddPluginV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_add_plugin_account_metas() {
+        let accounts = new_accounts(new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::None,
+            &[],
+        ));
+        let metas = AddPluginV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            AddPluginV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
@@ -0,0 +1,380 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    instructions::UpdateV2CpiBuilder,
+    types::{Key, UpdateAuthority},
+};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+#[cfg(feature = "ledger-state")]
+use crate::utils::is_ledger_state;
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::Instructions,
+    utils::{
+        assert_account_key, invoke_cpi, resolve_authority, AccountCheck, MinDataLen,
+        MplCoreAccount, NonDefaultKey, NonWritable, NotProgramAccount, OptionalAccountCheck,
+        ProcessInstruction, SignerAccount, SystemAccount, ToOptionalAccount, WritableAccount,
+        BASE_ASSET_MIN_LEN, BASE_COLLECTION_MIN_LEN,
+    },
+};
+
+#[derive(Debug)]
+pub struct AddToCollectionV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    /// Collection the asset joins.
+    pub collection: &'a AccountInfo<'info>,
+    /// Update authority of the asset.
+    pub authority: &'a AccountInfo<'info>,
+    /// Update authority of the collection.
+    pub collection_authority: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for AddToCollectionV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, authority, collection_authority, payer, system_program, log_wrapper, mpl_core] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(1, collection)?;
+        NotProgramAccount::check_at(1, collection)?;
+        NonDefaultKey::check_at(4, payer)?;
+        NotProgramAccount::check_at(4, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_at(1, collection)?;
+        SignerAccount::check_at(3, collection_authority)?;
+        WritableAccount::check_at(4, payer)?;
+        SignerAccount::check_at(4, payer)?;
+        SystemAccount::check_at(5, system_program)?;
+        NonWritable::check_optional_at(6, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(7, mpl_core)?;
+
+        // mpl_core only updates a compressed asset after it is decompressed.
+        #[cfg(feature = "reject-compressed")]
+        if is_ledger_state(asset) {
+            return Err(MplCoreWrapperError::AssetIsCompressed.into());
+        }
+
+        #[cfg(feature = "ledger-state")]
+        if is_ledger_state(asset) && log_wrapper.to_optional().is_none() {
+            return Err(MplCoreWrapperError::MissingLogWrapper.into());
+        }
+
+        Ok(Self {
+            asset,
+            collection,
+            authority,
+            collection_authority,
+            payer,
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for AddToCollectionV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            account_meta(self.collection, false, true),
+            account_meta(self.authority, true, false),
+            account_meta(self.collection_authority, true, false),
+            account_meta(self.payer, true, true),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+impl<'a, 'info> AddToCollectionV1Accounts<'a, 'info> {
+    /// Checks the asset has no collection yet and `authority` is its update
+    /// authority.
+    pub fn check_asset_authority(&self) -> ProgramResult {
+        MinDataLen::check(self.asset, BASE_ASSET_MIN_LEN)?;
+        assert_account_key(self.asset, Key::AssetV1)?;
+        let asset =
+            BaseAssetV1::try_from(self.asset).map_err(|_| ProgramError::InvalidAccountData)?;
+
+        match asset.update_authority {
+            UpdateAuthority::Collection(_) => {
+                Err(MplCoreWrapperError::AssetAlreadyInCollection.into())
+            }
+            UpdateAuthority::Address(address) if &address == self.authority.key => Ok(()),
+            _ => Err(MplCoreWrapperError::AuthorityMismatch.into()),
+        }
+    }
+
+    /// Checks `collection_authority` is the collection's update authority,
+    /// as mpl_core only sees the asset's authority in the CPI.
+    pub fn check_collection_authority(&self) -> ProgramResult {
+        MinDataLen::check(self.collection, BASE_COLLECTION_MIN_LEN)?;
+        assert_account_key(self.collection, Key::CollectionV1)?;
+        let collection = BaseCollectionV1::try_from(self.collection)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if self.collection_authority.key != &collection.update_authority {
+            return Err(MplCoreWrapperError::CollectionAuthorityRequired.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct AddToCollectionV1InstructionData {
+    /// When `true`, `authority` is the payer's delegated authority PDA of
+    /// this program, which the program signs for.
+    pub delegated_authority: bool,
+}
+
+#[derive(Debug)]
+pub struct AddToCollectionV1<'a, 'info> {
+    pub accounts: AddToCollectionV1Accounts<'a, 'info>,
+    pub instruction_data: AddToCollectionV1InstructionData,
+    /// Seeds, bump included, the program signs for `authority` with.
+    pub authority_seeds: Option<Vec<Vec<u8>>>,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], AddToCollectionV1InstructionData)>
+    for AddToCollectionV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], AddToCollectionV1InstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AddToCollectionV1Accounts::try_from(accounts)?;
+        let authority_seeds = resolve_authority(
+            Some(accounts.authority),
+            accounts.payer,
+            instruction_data.delegated_authority,
+        )?;
+        accounts.check_asset_authority()?;
+        accounts.check_collection_authority()?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            authority_seeds,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for AddToCollectionV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        let mut update_cpi = UpdateV2CpiBuilder::new(self.accounts.mpl_core);
+
+        update_cpi
+            .asset(self.accounts.asset)
+            .authority(Some(self.accounts.authority))
+            .payer(self.accounts.payer)
+            .new_collection(Some(self.accounts.collection))
+            .system_program(self.accounts.system_program)
+            .log_wrapper(self.accounts.log_wrapper)
+            .new_update_authority(UpdateAuthority::Collection(*self.accounts.collection.key));
+
+        invoke_cpi("UpdateV2", self.accounts.asset.key, || {
+            match self.authority_seeds {
+                Some(seeds) => {
+                    let seeds = seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
+                    update_cpi.invoke_signed(&[&seeds])
+                }
+                None => update_cpi.invoke(),
+            }
+        })?;
+
+        Ok(())
+    }
+}
+
+pub fn process_add_to_collection_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::AddToCollectionV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    AddToCollectionV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{derive_delegated_authority, test_utils::*};
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(authority: Pubkey, collection_authority: Pubkey) -> Vec<AccountInfo<'static>> {
+        vec![
+            new_asset_account(
+                Pubkey::new_unique(),
+                UpdateAuthority::Address(authority),
+                &[],
+            ),
+            new_collection_account(collection_authority),
+            new_test_account(authority, true, false, 1, 0, system_program::ID),
+            new_test_account(collection_authority, true, false, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+            absent_account(),
+            new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+        ]
+    }
+
+    fn new_data() -> AddToCollectionV1InstructionData {
+        AddToCollectionV1InstructionData {
+            delegated_authority: false,
+        }
+    }
+
+    #[test]
+    fn test_add_to_collection_success() {
+        let accounts = new_accounts(Pubkey::new_unique(), Pubkey::new_unique());
+
+        let instruction = AddToCollectionV1::try_from((accounts.as_slice(), new_data()));
+        assert!(
+            instruction.is_ok(),
+            "expected Ok, but got Err: {:?}",
+            instruction
+        );
+
+        let res = instruction.unwrap().process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_add_to_collection_authority_not_signer() {
+        let authority = Pubkey::new_unique();
+        let mut accounts = new_accounts(authority, Pubkey::new_unique());
+        accounts[2] = new_test_account(authority, false, false, 1, 0, system_program::ID);
+
+        let res = AddToCollectionV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[test]
+    fn test_add_to_collection_collection_authority_not_signer() {
+        let collection_authority = Pubkey::new_unique();
+        let mut accounts = new_accounts(Pubkey::new_unique(), collection_authority);
+        accounts[3] =
+            new_test_account(collection_authority, false, false, 1, 0, system_program::ID);
+
+        let res = AddToCollectionV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[test]
+    fn test_add_to_collection_wrong_asset_authority() {
+        let mut accounts = new_accounts(Pubkey::new_unique(), Pubkey::new_unique());
+        accounts[2] = new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+
+        let res = AddToCollectionV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AuthorityMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_add_to_collection_wrong_collection_authority() {
+        let mut accounts = new_accounts(Pubkey::new_unique(), Pubkey::new_unique());
+        accounts[1] = new_collection_account(Pubkey::new_unique());
+
+        let res = AddToCollectionV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::CollectionAuthorityRequired.into()
+        );
+    }
+
+    #[test]
+    fn test_add_to_collection_already_in_collection() {
+        let mut accounts = new_accounts(Pubkey::new_unique(), Pubkey::new_unique());
+        accounts[0] = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::Collection(Pubkey::new_unique()),
+            &[],
+        );
+
+        let res = AddToCollectionV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AssetAlreadyInCollection.into()
+        );
+    }
+
+    fn add_as_delegated_authority(
+        controller: Pubkey,
+        payer: AccountInfo<'static>,
+    ) -> ProgramResult {
+        let (pda, _) = derive_delegated_authority(&controller);
+        let mut accounts = new_accounts(pda, Pubkey::new_unique());
+        accounts[2] = new_test_account(pda, false, false, 1, 0, system_program::ID);
+        accounts[4] = payer;
+
+        AddToCollectionV1::try_from((
+            accounts.as_slice(),
+            AddToCollectionV1InstructionData {
+                delegated_authority: true,
+            },
+        ))?
+        .process()
+    }
+
+    #[test]
+    fn test_add_to_collection_signed_by_delegated_authority() {
+        let controller = Pubkey::new_unique();
+        let payer = new_test_account(controller, true, true, 1, 0, system_program::ID);
+
+        take_recorded_cpis();
+        let res = add_as_delegated_authority(controller, payer);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["UpdateV2"]);
+    }
+
+    #[test]
+    fn test_add_to_collection_delegated_authority_of_other_controller() {
+        let caller = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+
+        take_recorded_cpis();
+        let res = add_as_delegated_authority(Pubkey::new_unique(), caller);
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidSignerSeeds.into()
+        );
+        assert!(take_recorded_cpis().is_empty());
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_add_to_collection_account_metas() {
+        let accounts = new_accounts(Pubkey::new_unique(), Pubkey::new_unique());
+        let metas = AddToCollectionV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[2], |accounts| {
+            AddToCollectionV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
@@ -0,0 +1,366 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::instructions::BurnV1CpiBuilder;
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+};
+use solana_system_interface::instruction as system_instruction;
+
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    instructions::Instructions,
+    utils::{
+        invoke_cpi, lamports_received, resolve_authority, transfer_lamports, AccountCheck,
+        MplCoreAccount, NonDefaultKey, NonWritable, NotProgramAccount, OptionalAccountCheck,
+        ProcessInstruction, SignerAccount, SystemAccount, ToOptionalAccount, WritableAccount,
+    },
+};
+
+#[derive(Debug)]
+pub struct BurnAndRefundV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub authority: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub refund_recipient: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for BurnAndRefundV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, authority, payer, refund_recipient, system_program, log_wrapper, mpl_core] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(3, payer)?;
+        NotProgramAccount::check_at(3, payer)?;
+        NonDefaultKey::check_at(4, refund_recipient)?;
+        NotProgramAccount::check_at(4, refund_recipient)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        WritableAccount::check_optional_at(2, authority.to_optional())?;
+        WritableAccount::check_at(3, payer)?;
+        WritableAccount::check_at(4, refund_recipient)?;
+        SignerAccount::check_at(3, payer)?;
+        SystemAccount::check_at(5, system_program)?;
+        NonWritable::check_optional_at(6, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(7, mpl_core)?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            authority: authority.to_optional(),
+            payer,
+            refund_recipient,
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for BurnAndRefundV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            optional_account_meta(self.authority, true, true),
+            account_meta(self.payer, true, true),
+            account_meta(self.refund_recipient, false, true),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+impl<'a, 'info> BurnAndRefundV1Accounts<'a, 'info> {
+    /// Account mpl_core closes the asset into: the authority, or the payer
+    /// when no authority is given.
+    pub fn rent_receiver(&self) -> &'a AccountInfo<'info> {
+        self.authority.unwrap_or(self.payer)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct BurnAndRefundV1InstructionData {
+    /// When `true`, `authority` is the payer's delegated authority PDA of
+    /// this program, which the program signs the burn and refund for.
+    pub delegated_authority: bool,
+}
+
+#[derive(Debug)]
+pub struct BurnAndRefundV1<'a, 'info> {
+    pub accounts: BurnAndRefundV1Accounts<'a, 'info>,
+    pub instruction_data: BurnAndRefundV1InstructionData,
+    /// Seeds, bump included, the program signs for `authority` with.
+    pub authority_seeds: Option<Vec<Vec<u8>>>,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], BurnAndRefundV1InstructionData)>
+    for BurnAndRefundV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], BurnAndRefundV1InstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = BurnAndRefundV1Accounts::try_from(accounts)?;
+        let authority_seeds = resolve_authority(
+            accounts.authority,
+            accounts.payer,
+            instruction_data.delegated_authority,
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            authority_seeds,
+        })
+    }
+}
+
+impl<'a, 'info> BurnAndRefundV1<'a, 'info> {
+    /// Moves the reclaimed rent from the rent receiver to the refund recipient.
+    ///
+    /// A receiver owned by this program is debited directly; any other goes
+    /// through a system transfer.
+    pub fn refund(&self, amount: u64) -> ProgramResult {
+        let receiver = self.accounts.rent_receiver();
+        let recipient = self.accounts.refund_recipient;
+
+        if amount == 0 || receiver.key == recipient.key {
+            return Ok(());
+        }
+
+        if receiver.owner == &crate::ID {
+            return transfer_lamports(receiver, recipient, amount);
+        }
+
+        let transfer = system_instruction::transfer(receiver.key, recipient.key, amount);
+        let accounts = [
+            receiver.clone(),
+            recipient.clone(),
+            self.accounts.system_program.clone(),
+        ];
+
+        invoke_cpi("Refund", recipient.key, || match &self.authority_seeds {
+            Some(seeds) => {
+                let seeds = seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
+                invoke_signed(&transfer, &accounts, &[&seeds])
+            }
+            None => invoke(&transfer, &accounts),
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for BurnAndRefundV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        let mut burn_cpi = BurnV1CpiBuilder::new(self.accounts.mpl_core);
+
+        burn_cpi
+            .asset(self.accounts.asset)
+            .collection(self.accounts.collection)
+            .authority(self.accounts.authority)
+            .payer(self.accounts.payer)
+            .system_program(Some(self.accounts.system_program))
+            .log_wrapper(self.accounts.log_wrapper);
+
+        // mpl_core may leave tombstone lamports on the burnt asset, so the
+        // refund is what the burn actually paid the rent receiver.
+        let reclaimed = lamports_received(self.accounts.rent_receiver(), || {
+            invoke_cpi("BurnV1", self.accounts.asset.key, || {
+                match &self.authority_seeds {
+                    Some(seeds) => {
+                        let seeds = seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
+                        burn_cpi.invoke_signed(&[&seeds])
+                    }
+                    None => burn_cpi.invoke(),
+                }
+            })
+        })?;
+
+        self.refund(reclaimed)
+    }
+}
+
+pub fn process_burn_and_refund_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::BurnAndRefundV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    BurnAndRefundV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::MplCoreWrapperError,
+        utils::{derive_delegated_authority, test_utils::*},
+    };
+    use mpl_core::types::UpdateAuthority;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(
+        asset: AccountInfo<'static>,
+        authority: AccountInfo<'static>,
+    ) -> Vec<AccountInfo<'static>> {
+        let collection =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let refund_recipient =
+            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
+        let system_program =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let log_wrapper =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        vec![
+            asset,
+            collection,
+            authority,
+            payer,
+            refund_recipient,
+            system_program,
+            log_wrapper,
+            mpl_core,
+        ]
+    }
+
+    /// Accounts burning an asset held by `controller`'s delegated
+    /// authority, with `payer` signing.
+    fn delegated_accounts(
+        controller: Pubkey,
+        payer: Pubkey,
+        authority_lamports: u64,
+    ) -> Vec<AccountInfo<'static>> {
+        let (pda, _) = derive_delegated_authority(&controller);
+        let mut accounts = new_accounts(
+            new_asset_account(pda, UpdateAuthority::None, &[]),
+            new_test_account(pda, false, true, authority_lamports, 0, crate::ID),
+        );
+        accounts[3] = new_test_account(payer, true, true, 1, 0, system_program::ID);
+        accounts
+    }
+
+    fn delegated_data() -> BurnAndRefundV1InstructionData {
+        BurnAndRefundV1InstructionData {
+            delegated_authority: true,
+        }
+    }
+
+    #[test]
+    fn test_burn_and_refund_pays_recipient() {
+        let controller = Pubkey::new_unique();
+        let accounts = delegated_accounts(controller, controller, 1_000);
+
+        let instruction =
+            BurnAndRefundV1::try_from((accounts.as_slice(), delegated_data())).unwrap();
+
+        let res = instruction.refund(500);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(accounts[2].lamports(), 500);
+        assert_eq!(accounts[4].lamports(), 501);
+    }
+
+    #[test]
+    fn test_burn_and_refund_refunds_what_the_burn_paid() {
+        let controller = Pubkey::new_unique();
+        let accounts = delegated_accounts(controller, controller, 1_000);
+
+        let instruction =
+            BurnAndRefundV1::try_from((accounts.as_slice(), delegated_data())).unwrap();
+
+        // Off-chain the burn pays nothing out, so nothing is refunded however
+        // many lamports the asset holds.
+        take_recorded_cpis();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["BurnV1"]);
+        assert_eq!(accounts[2].lamports(), 1_000);
+        assert_eq!(accounts[4].lamports(), 1);
+    }
+
+    #[test]
+    fn test_burn_and_refund_receiver_underfunded() {
+        let controller = Pubkey::new_unique();
+        let accounts = delegated_accounts(controller, controller, 100);
+
+        let instruction =
+            BurnAndRefundV1::try_from((accounts.as_slice(), delegated_data())).unwrap();
+
+        assert_eq!(
+            instruction.refund(500),
+            Err(MplCoreWrapperError::LamportOverflow.into())
+        );
+    }
+
+    #[test]
+    fn test_burn_and_refund_delegated_authority_of_other_controller() {
+        let accounts = delegated_accounts(Pubkey::new_unique(), Pubkey::new_unique(), 1_000);
+
+        let res = BurnAndRefundV1::try_from((accounts.as_slice(), delegated_data()));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidSignerSeeds.into()
+        );
+    }
+
+    #[test]
+    fn test_burn_and_refund_recipient_not_writable() {
+        let authority =
+            new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let mut accounts = new_accounts(
+            new_asset_account(*authority.key, UpdateAuthority::None, &[]),
+            authority,
+        );
+        accounts[4] =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
+
+        let res = BurnAndRefundV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_burn_and_refund_not_enough_accounts() {
+        let accounts = vec![];
+        let res = BurnAndRefundV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_burn_and_refund_account_metas() {
+        let accounts = new_accounts(
+            new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]),
+            new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+        );
+        let metas = BurnAndRefundV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[2], |accounts| {
+            BurnAndRefundV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
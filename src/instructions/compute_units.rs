@@ -0,0 +1,170 @@
+use crate::instructions::{Instructions, MAX_REVOKED_PLUGINS};
+
+/// Rough compute unit costs, rounded up from what the mpl_core CPIs and this
+/// program's own checks take. Meant for setting a compute budget, not for
+/// exact accounting.
+pub const CREATE_COMPUTE_UNITS: u32 = 60_000;
+pub const UPDATE_COMPUTE_UNITS: u32 = 40_000;
+pub const TRANSFER_COMPUTE_UNITS: u32 = 30_000;
+pub const READ_COMPUTE_UNITS: u32 = 10_000;
+pub const EXECUTE_COMPUTE_UNITS: u32 = 60_000;
+/// Added per plugin or external plugin adapter an instruction writes.
+pub const PLUGIN_COMPUTE_UNITS: u32 = 15_000;
+
+fn with_plugins(base: u32, plugins: usize) -> u32 {
+    let plugins = u32::try_from(plugins).unwrap_or(u32::MAX);
+    base.saturating_add(PLUGIN_COMPUTE_UNITS.saturating_mul(plugins))
+}
+
+#[cfg(feature = "create")]
+fn len<T>(items: &Option<Vec<T>>) -> usize {
+    items.as_ref().map_or(0, Vec::len)
+}
+
+/// Conservative compute unit estimate for `ix`, scaled by the plugins it
+/// writes and the CPIs it makes.
+///
+/// Variants of disabled features carry no data, so they get the estimate of
+/// the same instruction without plugins.
+pub fn estimated_compute_units(ix: &Instructions) -> u32 {
+    match ix {
+        #[cfg(feature = "create")]
+        Instructions::CreateNftV1(data) => with_plugins(CREATE_COMPUTE_UNITS, len(&data.plugins)),
+        #[cfg(not(feature = "create"))]
+        Instructions::CreateNftV1 => CREATE_COMPUTE_UNITS,
+        #[cfg(feature = "update")]
+        Instructions::UpdateNftV1(_) => UPDATE_COMPUTE_UNITS,
+        #[cfg(not(feature = "update"))]
+        Instructions::UpdateNftV1 => UPDATE_COMPUTE_UNITS,
+        #[cfg(feature = "transfer")]
+        Instructions::TransferNftV1(_) => TRANSFER_COMPUTE_UNITS,
+        #[cfg(not(feature = "transfer"))]
+        Instructions::TransferNftV1 => TRANSFER_COMPUTE_UNITS,
+        #[cfg(feature = "create")]
+        Instructions::CreateNftV2(data) => with_plugins(
+            CREATE_COMPUTE_UNITS,
+            len(&data.plugins) + len(&data.external_plugin_adapters),
+        ),
+        #[cfg(not(feature = "create"))]
+        Instructions::CreateNftV2 => CREATE_COMPUTE_UNITS,
+        Instructions::ExecuteNftV1(_) => EXECUTE_COMPUTE_UNITS,
+        Instructions::GetAssetInfoV1 => READ_COMPUTE_UNITS,
+        Instructions::TransferCollectionAuthorityV1(_) => UPDATE_COMPUTE_UNITS,
+        #[cfg(feature = "transfer")]
+        Instructions::TransferNftBatchV1(data) => {
+            TRANSFER_COMPUTE_UNITS.saturating_mul(u32::from(data.count.max(1)))
+        }
+        #[cfg(not(feature = "transfer"))]
+        Instructions::TransferNftBatchV1 => TRANSFER_COMPUTE_UNITS,
+        Instructions::AddAutographV1(_) => with_plugins(UPDATE_COMPUTE_UNITS, 1),
+        #[cfg(feature = "create")]
+        Instructions::CreateCollectionV2(data) => with_plugins(
+            CREATE_COMPUTE_UNITS,
+            len(&data.plugins) + len(&data.external_plugin_adapters),
+        ),
+        #[cfg(not(feature = "create"))]
+        Instructions::CreateCollectionV2 => CREATE_COMPUTE_UNITS,
+        Instructions::UpdateAttributesV1(_) => with_plugins(UPDATE_COMPUTE_UNITS, 1),
+        Instructions::RemovePluginV1(_) => with_plugins(UPDATE_COMPUTE_UNITS, 1),
+        Instructions::UpdateRoyaltyRulesetV1(_) => with_plugins(UPDATE_COMPUTE_UNITS, 1),
+        Instructions::AddPluginV1(_) => with_plugins(UPDATE_COMPUTE_UNITS, 1),
+        #[cfg(feature = "create")]
+        Instructions::CreateNftWithPluginV1(data) => {
+            with_plugins(CREATE_COMPUTE_UNITS, len(&data.create.plugins) + 1)
+        }
+        #[cfg(not(feature = "create"))]
+        Instructions::CreateNftWithPluginV1 => with_plugins(CREATE_COMPUTE_UNITS, 1),
+        Instructions::BurnAndRefundV1(_) => UPDATE_COMPUTE_UNITS,
+        #[cfg(feature = "transfer")]
+        Instructions::TransferWithProvenanceV1(_) => {
+            with_plugins(TRANSFER_COMPUTE_UNITS + UPDATE_COMPUTE_UNITS, 1)
+        }
+        #[cfg(not(feature = "transfer"))]
+        Instructions::TransferWithProvenanceV1 => {
+            with_plugins(TRANSFER_COMPUTE_UNITS + UPDATE_COMPUTE_UNITS, 1)
+        }
+        // The delegated plugins are only known on chain, so assume the most.
+        Instructions::RevokeAllPluginAuthoritiesV1 => {
+            with_plugins(UPDATE_COMPUTE_UNITS, MAX_REVOKED_PLUGINS)
+        }
+        Instructions::UpdateExternalPluginAdapterV1(_) => with_plugins(UPDATE_COMPUTE_UNITS, 1),
+        Instructions::UpdatePluginsBatchV1(data) => {
+            with_plugins(UPDATE_COMPUTE_UNITS, data.plugins.len())
+        }
+        Instructions::SetPermanentBurnDelegateV1(_) => with_plugins(UPDATE_COMPUTE_UNITS, 1),
+        Instructions::DelegateBurnV1 => UPDATE_COMPUTE_UNITS,
+        #[cfg(feature = "transfer")]
+        Instructions::TransferAndFreezeV1(_) => {
+            with_plugins(TRANSFER_COMPUTE_UNITS + UPDATE_COMPUTE_UNITS, 1)
+        }
+        #[cfg(not(feature = "transfer"))]
+        Instructions::TransferAndFreezeV1 => {
+            with_plugins(TRANSFER_COMPUTE_UNITS + UPDATE_COMPUTE_UNITS, 1)
+        }
+        #[cfg(feature = "transfer")]
+        Instructions::ThawAndTransferV1(_) => {
+            with_plugins(TRANSFER_COMPUTE_UNITS + UPDATE_COMPUTE_UNITS, 1)
+        }
+        #[cfg(not(feature = "transfer"))]
+        Instructions::ThawAndTransferV1 => {
+            with_plugins(TRANSFER_COMPUTE_UNITS + UPDATE_COMPUTE_UNITS, 1)
+        }
+        #[cfg(feature = "update")]
+        Instructions::UpdateNftBatchV1(data) => {
+            let count = u32::try_from(data.updates.len().max(1)).unwrap_or(u32::MAX);
+            UPDATE_COMPUTE_UNITS.saturating_mul(count)
+        }
+        #[cfg(not(feature = "update"))]
+        Instructions::UpdateNftBatchV1 => UPDATE_COMPUTE_UNITS,
+        #[cfg(feature = "update")]
+        Instructions::AddToCollectionV1(_) => UPDATE_COMPUTE_UNITS,
+        #[cfg(not(feature = "update"))]
+        Instructions::AddToCollectionV1 => UPDATE_COMPUTE_UNITS,
+        #[cfg(feature = "update")]
+        Instructions::RemoveFromCollectionV1(_) => UPDATE_COMPUTE_UNITS,
+        #[cfg(not(feature = "update"))]
+        Instructions::RemoveFromCollectionV1 => UPDATE_COMPUTE_UNITS,
+    }
+}
+
+#[cfg(all(test, feature = "create", feature = "transfer"))]
+mod tests {
+    use super::*;
+    use crate::instructions::*;
+    use mpl_core::types::{FreezeDelegate, Plugin, PluginAuthorityPair};
+
+    #[test]
+    fn test_create_with_plugins_estimates_above_transfer() {
+        let create = |plugins: Option<Vec<PluginAuthorityPair>>| {
+            Instructions::CreateNftV1(CreateNftV1InstructionData {
+                data_state: None,
+                name: String::new(),
+                uri: String::new(),
+                plugins,
+                default_owner_to_payer: false,
+                nonce: None,
+                owner_is_update_authority: None,
+                update_authority_controller: None,
+                report_size: false,
+                processed_flag_id: None,
+                program_controlled: false,
+                raw_plugins: None,
+                prefund_lamports: None,
+            })
+        };
+        let plugin = PluginAuthorityPair {
+            plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+            authority: None,
+        };
+        let transfer = Instructions::TransferNftV1(TransferNftV1InstructionData {
+            delegated_authority: false,
+            recipient_allow_list: None,
+            require_owner_signature: None,
+        });
+
+        let bare = estimated_compute_units(&create(None));
+        let with_plugins = estimated_compute_units(&create(Some(vec![plugin.clone(), plugin])));
+        assert!(with_plugins > bare);
+        assert!(bare > estimated_compute_units(&transfer));
+    }
+}
@@ -0,0 +1,381 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    instructions::CreateCollectionV2CpiBuilder,
+    types::{
+        ExternalPluginAdapterInitInfo, ExternalPluginAdapterSchema, Key, LinkedAppDataInitInfo,
+        PluginAuthority, PluginAuthorityPair,
+    },
+};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError, rent::Rent,
+};
+
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::Instructions,
+    instructions::MAX_PLUGINS,
+    plugins::check_plugin_authority,
+    utils::{
+        check_metadata, checked_add_size, checked_asset_size, invoke_cpi, rent_exempt_lamports,
+        AccountCheck, MplCoreAccount, NonDefaultKey, NotProgramAccount, ProcessInstruction,
+        SignerAccount, SystemAccount, ToOptionalAccount, WritableAccount,
+    },
+};
+
+#[derive(Debug)]
+pub struct CreateCollectionV2Accounts<'a, 'info> {
+    pub collection: &'a AccountInfo<'info>,
+    pub update_authority: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for CreateCollectionV2Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [collection, update_authority, payer, system_program, mpl_core] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, collection)?;
+        NotProgramAccount::check_at(0, collection)?;
+        NonDefaultKey::check_at(2, payer)?;
+        NotProgramAccount::check_at(2, payer)?;
+        WritableAccount::check_at(0, collection)?;
+        SignerAccount::check_at(0, collection)?;
+        WritableAccount::check_at(2, payer)?;
+        SignerAccount::check_at(2, payer)?;
+        SystemAccount::check_at(3, system_program)?;
+        MplCoreAccount::check_at(4, mpl_core)?;
+
+        Ok(Self {
+            collection,
+            update_authority: update_authority.to_optional(),
+            payer,
+            system_program,
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for CreateCollectionV2Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.collection, true, true),
+            optional_account_meta(self.update_authority, false, false),
+            account_meta(self.payer, true, true),
+            account_meta(self.system_program, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct CreateCollectionV2InstructionData {
+    pub name: String,
+    pub uri: String,
+    pub plugins: Option<Vec<PluginAuthorityPair>>,
+    pub external_plugin_adapters: Option<Vec<ExternalPluginAdapterInitInfo>>,
+}
+
+impl CreateCollectionV2InstructionData {
+    /// Appends a `LinkedAppData` external plugin adapter, which gives every
+    /// asset in the collection its own app data written by `data_authority`.
+    pub fn with_linked_app_data(
+        &mut self,
+        schema: ExternalPluginAdapterSchema,
+        data_authority: PluginAuthority,
+    ) -> Result<&mut Self, ProgramError> {
+        if data_authority == PluginAuthority::None {
+            return Err(MplCoreWrapperError::InvalidDataAuthority.into());
+        }
+
+        self.external_plugin_adapters
+            .get_or_insert_with(Vec::new)
+            .push(ExternalPluginAdapterInitInfo::LinkedAppData(
+                LinkedAppDataInitInfo {
+                    data_authority,
+                    init_plugin_authority: None,
+                    schema: Some(schema),
+                },
+            ));
+
+        Ok(self)
+    }
+
+    /// Serialized size of the name, uri, plugins and adapters that end up in
+    /// the collection account.
+    pub fn collection_data_len(&self) -> Result<usize, ProgramError> {
+        let mut len = checked_asset_size(
+            self.name.len(),
+            self.uri.len(),
+            self.plugins.as_deref().unwrap_or_default(),
+        )?;
+
+        for adapter in self.external_plugin_adapters.iter().flatten() {
+            len = checked_add_size(len, adapter.try_to_vec()?.len())?;
+        }
+
+        Ok(len)
+    }
+
+    /// Estimated lamports the payer funds for the new collection account,
+    /// sized from the collection's base rather than an asset's.
+    pub fn rent_exempt_lamports(&self, rent: &Rent) -> Result<u64, ProgramError> {
+        rent_exempt_lamports(rent, Key::CollectionV1, self.collection_data_len()?)
+    }
+}
+
+#[derive(Debug)]
+pub struct CreateCollectionV2<'a, 'info> {
+    pub accounts: CreateCollectionV2Accounts<'a, 'info>,
+    pub instruction_data: CreateCollectionV2InstructionData,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], CreateCollectionV2InstructionData)>
+    for CreateCollectionV2<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], CreateCollectionV2InstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = CreateCollectionV2Accounts::try_from(accounts)?;
+
+        if instruction_data.plugins.as_ref().map_or(0, Vec::len) > MAX_PLUGINS {
+            return Err(MplCoreWrapperError::TooManyPlugins.into());
+        }
+        check_metadata(&instruction_data.name, &instruction_data.uri)?;
+
+        for pair in instruction_data.plugins.iter().flatten() {
+            check_plugin_authority(&pair.plugin, pair.authority.as_ref())?;
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for CreateCollectionV2<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        invoke_cpi("CreateCollectionV2", self.accounts.collection.key, || {
+            CreateCollectionV2CpiBuilder::new(self.accounts.mpl_core)
+                .collection(self.accounts.collection)
+                .update_authority(self.accounts.update_authority)
+                .payer(self.accounts.payer)
+                .system_program(self.accounts.system_program)
+                .name(self.instruction_data.name)
+                .uri(self.instruction_data.uri)
+                .plugins(self.instruction_data.plugins.unwrap_or_default())
+                .external_plugin_adapters(
+                    self.instruction_data
+                        .external_plugin_adapters
+                        .unwrap_or_default(),
+                )
+                .invoke()
+        })?;
+
+        Ok(())
+    }
+}
+
+pub fn process_create_collection_v2(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::CreateCollectionV2(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    CreateCollectionV2::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::AppDataInitInfo;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(collection_is_signer: bool) -> Vec<AccountInfo<'static>> {
+        let collection = new_test_account(
+            Pubkey::new_unique(),
+            collection_is_signer,
+            true,
+            1,
+            0,
+            system_program::ID,
+        );
+        let update_authority =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
+        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let system_program =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        vec![
+            collection,
+            update_authority,
+            payer,
+            system_program,
+            mpl_core,
+        ]
+    }
+
+    #[test]
+    fn test_create_collection_account_success() {
+        let accounts = new_accounts(true);
+        let res = CreateCollectionV2Accounts::try_from(accounts.as_slice());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_create_collection_account_collection_not_signer() {
+        let accounts = new_accounts(false);
+        let res = CreateCollectionV2Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[test]
+    fn test_create_collection_account_wrong_mpl_core() {
+        let mut accounts = new_accounts(true);
+        accounts[4] = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            1,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        let res = CreateCollectionV2Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountOwner);
+    }
+
+    #[test]
+    fn test_create_collection_forwards_adapters() {
+        let accounts = new_accounts(true);
+        let adapter = ExternalPluginAdapterInitInfo::AppData(AppDataInitInfo {
+            data_authority: PluginAuthority::UpdateAuthority,
+            init_plugin_authority: None,
+            schema: Some(ExternalPluginAdapterSchema::Json),
+        });
+
+        let instruction = CreateCollectionV2::try_from((
+            accounts.as_slice(),
+            CreateCollectionV2InstructionData {
+                name: "Collection".to_string(),
+                uri: "https://example.com/collection.json".to_string(),
+                plugins: None,
+                external_plugin_adapters: Some(vec![adapter.clone()]),
+            },
+        ))
+        .unwrap();
+
+        assert_eq!(
+            instruction.instruction_data.external_plugin_adapters,
+            Some(vec![adapter])
+        );
+
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    fn new_instruction_data() -> CreateCollectionV2InstructionData {
+        CreateCollectionV2InstructionData {
+            name: "Collection".to_string(),
+            uri: "https://example.com/collection.json".to_string(),
+            plugins: None,
+            external_plugin_adapters: None,
+        }
+    }
+
+    #[test]
+    fn test_create_collection_with_linked_app_data() {
+        let accounts = new_accounts(true);
+        let mut data = new_instruction_data();
+        let res = data
+            .with_linked_app_data(
+                ExternalPluginAdapterSchema::Binary,
+                PluginAuthority::UpdateAuthority,
+            )
+            .map(|_| ());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+
+        let instruction = CreateCollectionV2::try_from((accounts.as_slice(), data)).unwrap();
+        let adapters = instruction
+            .instruction_data
+            .external_plugin_adapters
+            .unwrap();
+        assert_eq!(adapters.len(), 1);
+        assert!(matches!(
+            &adapters[0],
+            ExternalPluginAdapterInitInfo::LinkedAppData(LinkedAppDataInitInfo {
+                data_authority: PluginAuthority::UpdateAuthority,
+                schema: Some(ExternalPluginAdapterSchema::Binary),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_create_collection_with_linked_app_data_no_authority() {
+        let mut data = new_instruction_data();
+        let res = data
+            .with_linked_app_data(ExternalPluginAdapterSchema::Json, PluginAuthority::None)
+            .map(|_| ());
+
+        assert_eq!(res, Err(MplCoreWrapperError::InvalidDataAuthority.into()));
+        assert!(data.external_plugin_adapters.is_none());
+    }
+
+    #[test]
+    fn test_create_collection_rent_uses_collection_base() {
+        let rent = Rent::default();
+        let data = new_instruction_data();
+        let data_len = data.collection_data_len().unwrap();
+
+        assert_eq!(
+            data.rent_exempt_lamports(&rent),
+            Ok(rent.minimum_balance(crate::utils::base_len(Key::CollectionV1) + data_len))
+        );
+        assert_ne!(
+            data.rent_exempt_lamports(&rent),
+            crate::utils::rent_exempt_lamports(&rent, Key::AssetV1, data_len)
+        );
+    }
+
+    #[test]
+    fn test_create_collection_account_not_enough_accounts() {
+        let accounts = vec![];
+        let res = CreateCollectionV2Accounts::try_from(accounts.as_slice());
+        assert!(
+            res.is_err(),
+            "expected failure because account is not enough, but got Ok: {:?}",
+            res
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_create_collection_account_metas() {
+        let accounts = new_accounts(true);
+        let metas = CreateCollectionV2Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            CreateCollectionV2Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
@@ -1,17 +1,70 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use mpl_core::{
     instructions::CreateV1CpiBuilder,
-    types::{DataState, PluginAuthorityPair},
+    types::{DataState, Key, Plugin, PluginAuthority, PluginAuthorityPair},
 };
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+#[cfg(feature = "std")]
+use solana_program::msg;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program::{invoke, set_return_data},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
 };
+use solana_system_interface::instruction as system_instruction;
 
+#[cfg(feature = "collection-size")]
+use mpl_core::{
+    accounts::BaseCollectionV1,
+    fetch_collection_plugin,
+    types::{MasterEdition, PluginType},
+};
+
+#[cfg(all(feature = "rent-check", not(test)))]
+use crate::utils::check_rent_exempt;
+#[cfg(feature = "asset-state")]
+use crate::utils::is_collection_authority;
+#[cfg(feature = "strict-accounts")]
+use crate::utils::reject_unused_account;
+#[cfg(feature = "client")]
 use crate::utils::{
-    AccountCheck, MplCoreAccount, OptionalAccountCheck, ProcessInstruction, SignerAccount,
-    SystemAccount, ToOptionalAccount, WritableAccount,
+    account_meta, optional_account_meta, optional_system_program_meta, ToAccountMetas,
+};
+#[cfg(feature = "collection-size")]
+use crate::utils::{assert_account_key, MinDataLen, BASE_COLLECTION_MIN_LEN};
+use crate::{
+    error::MplCoreWrapperError,
+    events::{CreateNftEvent, MplCoreWrapperEvent},
+    instructions::Instructions,
+    plugins::PluginValidation,
+    utils::{
+        check_metadata, check_processed_flag, checked_asset_size, derive_asset_address,
+        derive_delegated_authority, invoke_cpi, optional_system_program, rent_exempt_lamports,
+        set_processed_flag, AccountCheck, LogMiddleware, Middleware, MplCoreAccount, NonDefaultKey,
+        NonWritable, NotProgramAccount, OptionalAccountCheck, ProcessInstruction, SignerAccount,
+        SystemAccount, ToOptionalAccount, WritableAccount, ASSET_SEED, MAX_ASSET_DATA_LEN,
+    },
 };
 
+/// Data state of an asset whose instruction leaves it unset. Ledger state
+/// needs a log wrapper, so plain account state is the only safe default.
+pub const DEFAULT_DATA_STATE: DataState = DataState::AccountState;
+
+/// Resolves an omitted `data_state` to [`DEFAULT_DATA_STATE`].
+pub fn data_state_or_default(data_state: Option<DataState>) -> DataState {
+    data_state.unwrap_or(DEFAULT_DATA_STATE)
+}
+
+/// Upper bound on plugins accepted at create.
+pub const MAX_PLUGINS: usize = 32;
+
+/// `payer` and `authority` may be the same account; each check only looks at
+/// its own slot, so aliasing them is supported.
 #[derive(Debug)]
 pub struct CreateNftV1Accounts<'a, 'info> {
     pub asset: &'a AccountInfo<'info>,
@@ -20,30 +73,40 @@ pub struct CreateNftV1Accounts<'a, 'info> {
     pub payer: &'a AccountInfo<'info>,
     pub owner: Option<&'a AccountInfo<'info>>,
     pub update_authority: Option<&'a AccountInfo<'info>>,
-    pub system_program: &'a AccountInfo<'info>,
+    /// Absent when the asset needs no rent moved, in which case the slot
+    /// holds mpl_core and is passed on as such.
+    pub system_program: Option<&'a AccountInfo<'info>>,
     pub log_wrapper: Option<&'a AccountInfo<'info>>,
     pub mpl_core: &'a AccountInfo<'info>,
+    /// Flag PDA set on the first call when the instruction opts in to replay
+    /// protection. Trailing, so clients that don't opt in can leave it off.
+    pub processed_flag: Option<&'a AccountInfo<'info>>,
 }
 
 impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for CreateNftV1Accounts<'a, 'info> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
-        let [asset, collection, authority, payer, owner, update_authority, system_program, log_wrapper, mpl_core] =
+        let [asset, collection, authority, payer, owner, update_authority, system_program, log_wrapper, mpl_core, processed_flag @ ..] =
             accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        WritableAccount::check(asset)?;
-        WritableAccount::check(collection)?;
-        SignerAccount::check_optional(authority.to_optional())?;
-        WritableAccount::check(payer)?;
-        SignerAccount::check(payer)?;
-        SignerAccount::check_optional(owner.to_optional())?;
-        SignerAccount::check_optional(update_authority.to_optional())?;
-        SystemAccount::check(system_program)?;
-        MplCoreAccount::check(mpl_core)?;
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(3, payer)?;
+        NotProgramAccount::check_at(3, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        SignerAccount::check_optional_at(2, authority.to_optional())?;
+        WritableAccount::check_at(3, payer)?;
+        SignerAccount::check_at(3, payer)?;
+        SignerAccount::check_optional_at(4, owner.to_optional())?;
+        WritableAccount::check_optional_at(9, processed_flag.to_optional())?;
+        SystemAccount::check_optional_at(6, optional_system_program(system_program))?;
+        NonWritable::check_optional_at(7, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(8, mpl_core)?;
 
         Ok(Self {
             asset,
@@ -52,25 +115,310 @@ impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for CreateNftV1Accounts<'a, 'i
             payer,
             owner: owner.to_optional(),
             update_authority: update_authority.to_optional(),
-            system_program,
+            system_program: optional_system_program(system_program),
             log_wrapper: log_wrapper.to_optional(),
             mpl_core,
+            processed_flag: processed_flag.to_optional(),
         })
     }
 }
 
+#[cfg(feature = "client")]
+impl ToAccountMetas for CreateNftV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, true, true),
+            optional_account_meta(self.collection, false, true),
+            optional_account_meta(self.authority, true, false),
+            account_meta(self.payer, true, true),
+            optional_account_meta(self.owner, true, false),
+            // A delegated authority PDA is only recorded, never a signer.
+            optional_account_meta(
+                self.update_authority,
+                self.update_authority
+                    .is_some_and(|account| account.is_signer),
+                false,
+            ),
+            optional_system_program_meta(self.system_program),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+            optional_account_meta(self.processed_flag, false, true),
+        ]
+    }
+}
+
+impl<'a, 'info> CreateNftV1Accounts<'a, 'info> {
+    /// Checks the asset is the PDA for `nonce` and returns its bump.
+    pub fn check_asset_address(&self, nonce: u64) -> Result<u8, ProgramError> {
+        let (expected, bump) = derive_asset_address(self.payer.key, nonce);
+
+        if self.asset.key != &expected {
+            return Err(MplCoreWrapperError::InvalidAssetAddress.into());
+        }
+
+        Ok(bump)
+    }
+
+    /// Checks the update authority signed or, when `controller` is given, is
+    /// the delegated authority PDA of that controller.
+    pub fn check_update_authority(&self, controller: Option<&Pubkey>) -> ProgramResult {
+        let Some(controller) = controller else {
+            return SignerAccount::check_optional(self.update_authority);
+        };
+
+        let Some(update_authority) = self.update_authority else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (expected, _) = derive_delegated_authority(controller);
+
+        if update_authority.key != &expected {
+            return Err(MplCoreWrapperError::AuthorityMismatch.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks the processed flag is the PDA for `id` and not yet set,
+    /// returning its bump. Setting it needs the system program.
+    pub fn check_processed_flag(&self, id: u64) -> Result<u8, ProgramError> {
+        let (Some(processed_flag), Some(_)) = (self.processed_flag, self.system_program) else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        check_processed_flag(processed_flag, self.payer.key, id)
+    }
+
+    /// Checks the update authority is the payer's delegated authority PDA and
+    /// the system program is there to fund the asset.
+    pub fn check_program_authority(&self) -> ProgramResult {
+        let (Some(update_authority), Some(_)) = (self.update_authority, self.system_program) else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (expected, _) = derive_delegated_authority(self.payer.key);
+
+        if update_authority.key != &expected {
+            return Err(MplCoreWrapperError::AuthorityMismatch.into());
+        }
+
+        Ok(())
+    }
+
+    /// Size of the asset account as allocated, read after the create CPI.
+    pub fn asset_size(&self) -> u64 {
+        self.asset.data_len() as u64
+    }
+
+    /// mpl_core makes the payer the owner when no owner is passed, so clients
+    /// have to opt in to that explicitly.
+    pub fn check_owner(&self, default_owner_to_payer: bool) -> ProgramResult {
+        if self.owner.is_some() {
+            return Ok(());
+        }
+
+        if !default_owner_to_payer {
+            return Err(MplCoreWrapperError::MissingOwner.into());
+        }
+
+        #[cfg(feature = "std")]
+        msg!("Owner not provided, defaulting to payer {}", self.payer.key);
+
+        Ok(())
+    }
+
+    /// Moves `amount` lamports from the payer into the created asset on top
+    /// of its rent.
+    pub fn prefund_asset(&self, amount: u64) -> ProgramResult {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let Some(system_program) = self.system_program else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        self.asset
+            .lamports()
+            .checked_add(amount)
+            .ok_or(MplCoreWrapperError::LamportOverflow)?;
+
+        invoke_cpi("PrefundAsset", self.asset.key, || {
+            invoke(
+                &system_instruction::transfer(self.payer.key, self.asset.key, amount),
+                &[
+                    self.payer.clone(),
+                    self.asset.clone(),
+                    system_program.clone(),
+                ],
+            )
+        })
+    }
+}
+
+#[cfg(feature = "asset-state")]
+impl<'a, 'info> CreateNftV1Accounts<'a, 'info> {
+    /// Checks the authority (the payer when none is given) signed and is the
+    /// collection's update authority or an update delegate of it, which
+    /// mpl_core requires to mint into the collection.
+    pub fn check_collection_authority(&self) -> ProgramResult {
+        let Some(collection) = self.collection else {
+            return Ok(());
+        };
+        let authority = self.authority.unwrap_or(self.payer);
+
+        if !authority.is_signer || !is_collection_authority(collection, authority.key)? {
+            return Err(MplCoreWrapperError::CollectionAuthorityRequired.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "collection-size")]
+impl<'a, 'info> CreateNftV1Accounts<'a, 'info> {
+    /// A collection's MasterEdition `max_supply` caps its size, so a full
+    /// collection fails here instead of after the create CPI starts.
+    pub fn check_collection_capacity(&self) -> ProgramResult {
+        let Some(collection) = self.collection else {
+            return Ok(());
+        };
+
+        MinDataLen::check(collection, BASE_COLLECTION_MIN_LEN)?;
+        assert_account_key(collection, Key::CollectionV1)?;
+        let current_size = BaseCollectionV1::try_from(collection)
+            .map_err(|_| ProgramError::InvalidAccountData)?
+            .current_size;
+        let max_size =
+            fetch_collection_plugin::<MasterEdition>(collection, PluginType::MasterEdition)
+                .ok()
+                .and_then(|(_, edition, _)| edition.max_supply);
+
+        if max_size.is_some_and(|max_size| current_size >= max_size) {
+            return Err(MplCoreWrapperError::CollectionFull.into());
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub struct CreateNftV1InstructionData {
     pub data_state: Option<DataState>,
     pub name: String,
     pub uri: String,
     pub plugins: Option<Vec<PluginAuthorityPair>>,
+    pub default_owner_to_payer: bool,
+    /// Creates the asset at the PDA derived from the payer and this nonce.
+    pub nonce: Option<u64>,
+    /// Uses the `owner` account as the update authority too, so self-custody
+    /// mints don't pass the same account twice.
+    pub owner_is_update_authority: Option<bool>,
+    /// Signer whose delegated authority PDA of this program is passed as
+    /// `update_authority`, so only instructions it signs can update the asset.
+    pub update_authority_controller: Option<Pubkey>,
+    /// Sets the created asset's account size as a little-endian `u64` return
+    /// value, for clients that account for allocated space.
+    pub report_size: bool,
+    /// Sets the processed flag PDA for this payer and id, rejecting any later
+    /// call with the same id.
+    pub processed_flag_id: Option<u64>,
+    /// Creates the asset at its nonce PDA, funded by the payer up front, with
+    /// the payer's delegated authority PDA as update authority.
+    pub program_controlled: bool,
+    /// Borsh-serialized `Vec<PluginAuthorityPair>`, for clients that can't
+    /// build the plugin enums themselves; conflicts with `plugins`.
+    pub raw_plugins: Option<Vec<u8>>,
+    /// Lamports the payer moves into the asset after the create on top of
+    /// rent, e.g. for `ExecuteNftV1` to disburse later.
+    pub prefund_lamports: Option<u64>,
+}
+
+impl CreateNftV1InstructionData {
+    /// Decodes `raw_plugins` into `plugins`, so later checks only see the
+    /// latter.
+    pub fn resolve_raw_plugins(self) -> Result<Self, ProgramError> {
+        let Some(raw_plugins) = self.raw_plugins else {
+            return Ok(self);
+        };
+
+        if self.plugins.is_some() {
+            return Err(MplCoreWrapperError::ConflictingPluginSources.into());
+        }
+
+        let plugins = Vec::<PluginAuthorityPair>::try_from_slice(&raw_plugins)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        Ok(Self {
+            plugins: Some(plugins),
+            raw_plugins: None,
+            ..self
+        })
+    }
+
+    /// Serialized size of the name, uri and plugins that end up in the asset account.
+    pub fn asset_data_len(&self) -> Result<usize, ProgramError> {
+        checked_asset_size(
+            self.name.len(),
+            self.uri.len(),
+            self.plugins.as_deref().unwrap_or_default(),
+        )
+    }
+
+    /// Rejects options that conflict with a program-controlled asset, which
+    /// needs a nonce and takes the payer's delegated authority as update authority.
+    pub fn check_program_controlled(&self) -> ProgramResult {
+        if !self.program_controlled {
+            return Ok(());
+        }
+
+        if self.nonce.is_none() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if self.update_authority_controller.is_some()
+            || self.owner_is_update_authority == Some(true)
+        {
+            return Err(MplCoreWrapperError::AuthorityMismatch.into());
+        }
+
+        Ok(())
+    }
+
+    /// Estimated lamports the payer funds for the new asset account.
+    pub fn rent_exempt_lamports(&self, rent: &Rent) -> Result<u64, ProgramError> {
+        rent_exempt_lamports(rent, Key::AssetV1, self.asset_data_len()?)
+    }
+
+    /// Lamports the asset holds once created and prefunded.
+    pub fn prefunded_lamports(&self, rent: &Rent) -> Result<u64, ProgramError> {
+        self.rent_exempt_lamports(rent)?
+            .checked_add(self.prefund_lamports.unwrap_or_default())
+            .ok_or_else(|| MplCoreWrapperError::LamportOverflow.into())
+    }
+}
+
+impl PluginValidation for CreateNftV1InstructionData {
+    fn plugins(&self) -> Vec<(&Plugin, Option<&PluginAuthority>)> {
+        self.plugins
+            .iter()
+            .flatten()
+            .map(|pair| (&pair.plugin, pair.authority.as_ref()))
+            .collect()
+    }
+
+    fn data_len(&self) -> Result<usize, ProgramError> {
+        self.asset_data_len()
+    }
 }
 
 #[derive(Debug)]
 pub struct CreateNftV1<'a, 'info> {
     pub accounts: CreateNftV1Accounts<'a, 'info>,
     pub instruction_data: CreateNftV1InstructionData,
+    /// Bump of the asset PDA when created from a nonce.
+    pub asset_bump: Option<u8>,
+    /// Bump of the processed flag PDA when replay protection is requested.
+    pub processed_flag_bump: Option<u8>,
 }
 
 impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], CreateNftV1InstructionData)>
@@ -82,91 +430,263 @@ impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], CreateNftV1InstructionData)>
         (accounts, instruction_data): (&'a [AccountInfo<'info>], CreateNftV1InstructionData),
     ) -> Result<Self, Self::Error> {
         let accounts = CreateNftV1Accounts::try_from(accounts)?;
+        let instruction_data = instruction_data.resolve_raw_plugins()?;
+        accounts.check_owner(instruction_data.default_owner_to_payer)?;
+        if instruction_data.owner_is_update_authority == Some(true) {
+            if accounts.owner.is_none() {
+                return Err(MplCoreWrapperError::MissingOwner.into());
+            }
+            if instruction_data.update_authority_controller.is_some() {
+                return Err(MplCoreWrapperError::AuthorityMismatch.into());
+            }
+        }
+        instruction_data.check_program_controlled()?;
+        if instruction_data.program_controlled {
+            accounts.check_program_authority()?;
+        } else {
+            accounts
+                .check_update_authority(instruction_data.update_authority_controller.as_ref())?;
+        }
+        #[cfg(feature = "asset-state")]
+        accounts.check_collection_authority()?;
+        #[cfg(feature = "collection-size")]
+        accounts.check_collection_capacity()?;
+        instruction_data.validate_plugins(MAX_PLUGINS, MAX_ASSET_DATA_LEN)?;
+        check_metadata(&instruction_data.name, &instruction_data.uri)?;
+
+        let asset_bump = instruction_data
+            .nonce
+            .map(|nonce| accounts.check_asset_address(nonce))
+            .transpose()?;
+        #[cfg(feature = "strict-accounts")]
+        reject_unused_account(
+            accounts.processed_flag,
+            instruction_data.processed_flag_id.is_some(),
+        )?;
+        let processed_flag_bump = instruction_data
+            .processed_flag_id
+            .map(|id| accounts.check_processed_flag(id))
+            .transpose()?;
 
         Ok(Self {
             accounts,
             instruction_data,
+            asset_bump,
+            processed_flag_bump,
+        })
+    }
+}
+
+impl<'a, 'info> CreateNftV1<'a, 'info> {
+    /// Account wired into the CPI's `update_authority`.
+    pub fn update_authority(&self) -> Option<&'a AccountInfo<'info>> {
+        if self.instruction_data.owner_is_update_authority == Some(true) {
+            return self.accounts.owner;
+        }
+
+        self.accounts.update_authority
+    }
+}
+
+impl<'a, 'info> CreateNftV1<'a, 'info> {
+    /// Sets the processed flag when the instruction opted in to replay
+    /// protection.
+    pub fn mark_processed(&self, rent: &Rent) -> ProgramResult {
+        let (Some(id), Some(bump), Some(processed_flag), Some(system_program)) = (
+            self.instruction_data.processed_flag_id,
+            self.processed_flag_bump,
+            self.accounts.processed_flag,
+            self.accounts.system_program,
+        ) else {
+            return Ok(());
+        };
+
+        set_processed_flag(
+            processed_flag,
+            self.accounts.payer,
+            system_program,
+            id,
+            bump,
+            rent,
+        )
+    }
+
+    /// Lamports the payer moves into a program-controlled asset so it is rent
+    /// exempt before the create CPI.
+    pub fn funding_amount(&self, rent: &Rent) -> Result<u64, ProgramError> {
+        Ok(self
+            .instruction_data
+            .rent_exempt_lamports(rent)?
+            .saturating_sub(self.accounts.asset.lamports()))
+    }
+
+    /// Funds a program-controlled asset from the payer.
+    pub fn fund_asset(&self, rent: &Rent) -> ProgramResult {
+        let Some(system_program) = self.accounts.system_program else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let amount = self.funding_amount(rent)?;
+
+        if amount == 0 {
+            return Ok(());
+        }
+
+        invoke_cpi("FundAsset", self.accounts.asset.key, || {
+            invoke(
+                &system_instruction::transfer(
+                    self.accounts.payer.key,
+                    self.accounts.asset.key,
+                    amount,
+                ),
+                &[
+                    self.accounts.payer.clone(),
+                    self.accounts.asset.clone(),
+                    system_program.clone(),
+                ],
+            )
+        })
+    }
+
+    /// Seeds, bumps included, of every PDA this program signs the create for.
+    pub fn signer_seeds(&self) -> Vec<Vec<Vec<u8>>> {
+        let mut signers = Vec::new();
+
+        if let (Some(nonce), Some(bump)) = (self.instruction_data.nonce, self.asset_bump) {
+            signers.push(vec![
+                ASSET_SEED.to_vec(),
+                self.accounts.payer.key.to_bytes().to_vec(),
+                nonce.to_le_bytes().to_vec(),
+                vec![bump],
+            ]);
+        }
+
+        signers
+    }
+
+    pub fn event(&self) -> MplCoreWrapperEvent {
+        MplCoreWrapperEvent::CreateNft(CreateNftEvent {
+            asset: *self.accounts.asset.key,
+            owner: *self.accounts.owner.unwrap_or(self.accounts.payer).key,
+            collection: self.accounts.collection.map(|collection| *collection.key),
+            name: self.instruction_data.name.clone(),
         })
     }
 }
 
 impl<'a, 'info> ProcessInstruction for CreateNftV1<'a, 'info> {
     fn process(self) -> ProgramResult {
-        CreateV1CpiBuilder::new(self.accounts.mpl_core)
+        let event = self.event();
+        let report_size = self.instruction_data.report_size;
+        let prefund_lamports = self.instruction_data.prefund_lamports.unwrap_or_default();
+
+        if self.processed_flag_bump.is_some() {
+            self.mark_processed(&Rent::get()?)?;
+        }
+
+        if self.instruction_data.program_controlled {
+            self.fund_asset(&Rent::get()?)?;
+        }
+
+        let signers = self.signer_seeds();
+
+        let mut create_cpi = CreateV1CpiBuilder::new(self.accounts.mpl_core);
+
+        create_cpi
             .asset(self.accounts.asset)
             .collection(self.accounts.collection)
             .authority(self.accounts.authority)
             .payer(self.accounts.payer)
             .owner(self.accounts.owner)
-            .update_authority(self.accounts.update_authority)
-            .system_program(self.accounts.system_program)
-            .data_state(
-                self.instruction_data
-                    .data_state
-                    .unwrap_or(DataState::AccountState),
+            .update_authority(self.update_authority())
+            .system_program(
+                self.accounts
+                    .system_program
+                    .unwrap_or(self.accounts.mpl_core),
             )
+            .data_state(data_state_or_default(self.instruction_data.data_state))
             .log_wrapper(self.accounts.log_wrapper)
             .name(self.instruction_data.name)
             .uri(self.instruction_data.uri)
-            .plugins(self.instruction_data.plugins.unwrap_or_default())
-            .invoke()?;
+            .plugins(self.instruction_data.plugins.unwrap_or_default());
 
-        Ok(())
+        invoke_cpi("CreateV1", self.accounts.asset.key, || {
+            if signers.is_empty() {
+                return create_cpi.invoke();
+            }
+
+            let signers = signers
+                .iter()
+                .map(|seeds| seeds.iter().map(Vec::as_slice).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let signers = signers.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            create_cpi.invoke_signed(&signers)
+        })?;
+
+        self.accounts.prefund_asset(prefund_lamports)?;
+
+        // Off-chain `invoke` is a no-op, so tests never see a created asset
+        // to check.
+        #[cfg(all(feature = "rent-check", not(test)))]
+        check_rent_exempt(self.accounts.asset, &Rent::get()?)?;
+
+        if report_size {
+            set_return_data(&self.accounts.asset_size().to_le_bytes());
+        }
+
+        event.emit()
     }
 }
 
+pub fn process_create_nft_v1(accounts: &[AccountInfo], instruction: Instructions) -> ProgramResult {
+    let Instructions::CreateNftV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    Middleware::new(
+        LogMiddleware {
+            name: "CreateNftV1",
+        },
+        CreateNftV1::try_from((accounts, instruction_data))?,
+    )
+    .process()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::test_utils::*;
-    use solana_program::pubkey::Pubkey;
+    use crate::instructions::{AddPluginV1InstructionData, UpdatePluginsBatchV1InstructionData};
+    use crate::utils::{derive_processed_flag, test_utils::*};
+    use mpl_core::instructions::CreateV1InstructionArgs;
+    use mpl_core::types::{
+        Attribute, Attributes, FreezeDelegate, PermanentBurnDelegate, Plugin, Royalties, RuleSet,
+    };
+    use solana_program::{instruction::Instruction, pubkey::Pubkey};
     use solana_sdk_ids::system_program;
 
     #[test]
     fn test_create_nft_account_success() {
-        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let collection =
-            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
-        let owner = new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let update_authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let system_program =
-            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
-        let log_wrapper =
-            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
-        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
-
-        let accounts = vec![
-            asset,
-            collection,
-            authority,
-            payer,
-            owner,
-            update_authority,
-            system_program,
-            log_wrapper,
-            mpl_core,
-        ];
+        let accounts = create_nft_accounts(Pubkey::new_unique());
 
         let res = CreateNftV1Accounts::try_from(accounts.as_slice());
         assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
     }
 
+    #[test]
+    fn test_create_nft_account_program_as_asset() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[0] = new_test_account(mpl_core::ID, false, true, 1, 0, mpl_core::ID);
+
+        let res = CreateNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::ProgramAccountInWrongSlot.into()
+        );
+    }
+
     #[test]
     fn test_create_nft_account_wrong_system_program() {
-        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let collection =
-            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
-        let owner = new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let update_authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let bad_system_program = new_test_account(
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[6] = new_test_account(
             Pubkey::new_unique(),
             false,
             false,
@@ -174,46 +694,57 @@ mod tests {
             0,
             Pubkey::new_unique(),
         );
-        let log_wrapper =
-            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
-        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
 
-        let accounts = vec![
-            asset,
-            collection,
-            authority,
-            payer,
-            owner,
-            update_authority,
-            bad_system_program,
-            log_wrapper,
-            mpl_core,
-        ];
+        let res = CreateNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountOwner);
+    }
+
+    #[test]
+    fn test_create_nft_account_present_system_program() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
 
         let res = CreateNftV1Accounts::try_from(accounts.as_slice());
-        assert!(
-            res.is_err(),
-            "expected failure because system_program was wrong, but got Ok: {:?}",
-            res,
+        assert_eq!(
+            res.unwrap().system_program.map(|account| *account.key),
+            Some(system_program::ID)
+        );
+    }
+
+    #[test]
+    fn test_create_nft_account_absent_system_program() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[6] = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        let res = CreateNftV1Accounts::try_from(accounts.as_slice());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert!(res.unwrap().system_program.is_none());
+    }
+
+    #[test]
+    fn test_create_nft_account_logs_failing_index() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[6] = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            1,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        take_recorded_account_errors();
+        let res = CreateNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountOwner);
+        assert_eq!(
+            take_recorded_account_errors(),
+            vec!["account[6] must be owned by the system program"]
         );
     }
 
     #[test]
     fn test_create_nft_account_wrong_mpl_core() {
-        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let collection =
-            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
-        let owner = new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let update_authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let system_program =
-            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
-        let log_wrapper =
-            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
-        let bad_mpl_core = new_test_account(
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[8] = new_test_account(
             Pubkey::new_unique(),
             false,
             false,
@@ -222,26 +753,881 @@ mod tests {
             Pubkey::new_unique(),
         );
 
-        let accounts = vec![
-            asset,
-            collection,
-            authority,
-            payer,
-            owner,
-            update_authority,
-            system_program,
-            log_wrapper,
-            bad_mpl_core,
-        ];
+        let res = CreateNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountOwner);
+    }
+
+    #[test]
+    fn test_create_nft_account_absent_collection_read_only() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[1] = new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
 
         let res = CreateNftV1Accounts::try_from(accounts.as_slice());
-        assert!(
-            res.is_err(),
-            "expected failure because mpl_core was wrong, but got Ok: {:?}",
-            res
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert!(res.unwrap().collection.is_none());
+    }
+
+    #[test]
+    fn test_create_nft_account_present_collection_read_only() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[1] = new_test_account(Pubkey::new_unique(), false, false, 1, 0, mpl_core::ID);
+
+        let res = CreateNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_create_nft_payer_as_authority() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[1] = new_collection_account(*accounts[3].key);
+        accounts[2] = accounts[3].clone();
+
+        let data = CreateNftV1InstructionData {
+            data_state: None,
+            name: "Asset".to_string(),
+            uri: "https://example.com/asset.json".to_string(),
+            plugins: None,
+            default_owner_to_payer: false,
+            nonce: None,
+            owner_is_update_authority: None,
+            update_authority_controller: None,
+            report_size: false,
+            processed_flag_id: None,
+            program_controlled: false,
+            raw_plugins: None,
+            prefund_lamports: None,
+        };
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), data));
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+
+        let accounts = res.unwrap().accounts;
+        assert_eq!(accounts.authority.unwrap().key, accounts.payer.key);
+    }
+
+    #[test]
+    fn test_create_nft_data_state_default() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let instruction =
+            CreateNftV1::try_from((accounts.as_slice(), new_attributes_data(0))).unwrap();
+
+        take_recorded_instructions();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+
+        let create = recorded_create_v1();
+        let args = CreateV1InstructionArgs::try_from_slice(&create.data[1..]).unwrap();
+        assert_eq!(args.data_state, DataState::AccountState);
+    }
+
+    #[test]
+    fn test_create_nft_explicit_owner() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let accounts = CreateNftV1Accounts::try_from(accounts.as_slice()).unwrap();
+        assert!(accounts.check_owner(false).is_ok());
+    }
+
+    #[test]
+    fn test_create_nft_owner_defaults_to_payer() {
+        let accounts = create_nft_accounts(system_program::ID);
+        let accounts = CreateNftV1Accounts::try_from(accounts.as_slice()).unwrap();
+        assert!(accounts.check_owner(true).is_ok());
+    }
+
+    #[test]
+    fn test_create_nft_missing_owner_without_opt_in() {
+        let accounts = create_nft_accounts(system_program::ID);
+        let accounts = CreateNftV1Accounts::try_from(accounts.as_slice()).unwrap();
+        assert_eq!(
+            accounts.check_owner(false),
+            Err(MplCoreWrapperError::MissingOwner.into()),
         );
     }
 
+    #[test]
+    fn test_create_nft_asset_size_within_limit() {
+        let data = CreateNftV1InstructionData {
+            data_state: None,
+            name: "Asset".to_string(),
+            uri: "https://example.com/asset.json".to_string(),
+            plugins: Some(vec![
+                PluginAuthorityPair {
+                    plugin: Plugin::Attributes(Attributes {
+                        attribute_list: vec![Attribute {
+                            key: "rarity".to_string(),
+                            value: "common".to_string(),
+                        }],
+                    }),
+                    authority: None,
+                },
+                PluginAuthorityPair {
+                    plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                    authority: None,
+                },
+            ]),
+            default_owner_to_payer: false,
+            nonce: None,
+            owner_is_update_authority: None,
+            update_authority_controller: None,
+            report_size: false,
+            processed_flag_id: None,
+            program_controlled: false,
+            raw_plugins: None,
+            prefund_lamports: None,
+        };
+
+        let res = data.check_asset_size(MAX_ASSET_DATA_LEN);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_create_nft_asset_size_too_large() {
+        let data = CreateNftV1InstructionData {
+            data_state: None,
+            name: "Asset".to_string(),
+            uri: "https://example.com/asset.json".to_string(),
+            plugins: Some(vec![PluginAuthorityPair {
+                plugin: Plugin::Attributes(Attributes {
+                    attribute_list: vec![Attribute {
+                        key: "blob".to_string(),
+                        value: "x".repeat(MAX_ASSET_DATA_LEN),
+                    }],
+                }),
+                authority: None,
+            }]),
+            default_owner_to_payer: false,
+            nonce: None,
+            owner_is_update_authority: None,
+            update_authority_controller: None,
+            report_size: false,
+            processed_flag_id: None,
+            program_controlled: false,
+            raw_plugins: None,
+            prefund_lamports: None,
+        };
+
+        assert_eq!(
+            data.check_asset_size(MAX_ASSET_DATA_LEN),
+            Err(MplCoreWrapperError::AssetTooLarge.into()),
+        );
+    }
+
+    fn new_attributes_data(plugin_count: usize) -> CreateNftV1InstructionData {
+        CreateNftV1InstructionData {
+            data_state: None,
+            name: "Asset".to_string(),
+            uri: "https://example.com/asset.json".to_string(),
+            plugins: Some(vec![
+                PluginAuthorityPair {
+                    plugin: Plugin::Attributes(Attributes {
+                        attribute_list: vec![],
+                    }),
+                    authority: None,
+                };
+                plugin_count
+            ]),
+            default_owner_to_payer: false,
+            nonce: None,
+            owner_is_update_authority: None,
+            update_authority_controller: None,
+            report_size: false,
+            processed_flag_id: None,
+            program_controlled: false,
+            raw_plugins: None,
+            prefund_lamports: None,
+        }
+    }
+
+    #[test]
+    fn test_create_nft_plugin_count_at_max() {
+        let data = new_attributes_data(MAX_PLUGINS);
+        assert!(data.check_plugin_count(MAX_PLUGINS).is_ok());
+    }
+
+    #[test]
+    fn test_create_nft_plugin_count_over_max() {
+        let data = new_attributes_data(MAX_PLUGINS + 1);
+        assert_eq!(
+            data.check_plugin_count(MAX_PLUGINS),
+            Err(MplCoreWrapperError::TooManyPlugins.into()),
+        );
+    }
+
+    fn new_plugins_data(plugins: Vec<Plugin>) -> CreateNftV1InstructionData {
+        CreateNftV1InstructionData {
+            plugins: Some(
+                plugins
+                    .into_iter()
+                    .map(|plugin| PluginAuthorityPair {
+                        plugin,
+                        authority: None,
+                    })
+                    .collect(),
+            ),
+            ..new_attributes_data(0)
+        }
+    }
+
+    fn new_royalties() -> Plugin {
+        Plugin::Royalties(Royalties {
+            basis_points: 500,
+            creators: vec![],
+            rule_set: RuleSet::None,
+        })
+    }
+
+    #[test]
+    fn test_create_nft_unique_plugin_types() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let data = new_plugins_data(vec![
+            new_royalties(),
+            Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+        ]);
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), data));
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_create_nft_duplicate_plugin_types() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let data = new_plugins_data(vec![new_royalties(), new_royalties()]);
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), data));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::DuplicatePluginType.into()
+        );
+    }
+
+    #[test]
+    fn test_plugin_validation_shared_across_instructions() {
+        let freeze = Plugin::FreezeDelegate(FreezeDelegate { frozen: false });
+        let create = new_plugins_data(vec![freeze.clone(), freeze.clone()]);
+        let update = UpdatePluginsBatchV1InstructionData {
+            plugins: vec![freeze.clone(), freeze],
+        };
+        assert_eq!(
+            create.validate_plugins(MAX_PLUGINS, MAX_ASSET_DATA_LEN),
+            Err(MplCoreWrapperError::DuplicatePluginType.into())
+        );
+        assert_eq!(
+            update.validate_plugins(MAX_PLUGINS, MAX_ASSET_DATA_LEN),
+            Err(MplCoreWrapperError::DuplicatePluginType.into())
+        );
+
+        let burn = Plugin::PermanentBurnDelegate(PermanentBurnDelegate {});
+        let create = CreateNftV1InstructionData {
+            plugins: Some(vec![PluginAuthorityPair {
+                plugin: burn.clone(),
+                authority: Some(PluginAuthority::Owner),
+            }]),
+            ..new_attributes_data(0)
+        };
+        let add = AddPluginV1InstructionData {
+            plugin: burn,
+            init_authority: Some(PluginAuthority::Owner),
+            fund_realloc: false,
+        };
+        assert_eq!(
+            create.validate_plugins(MAX_PLUGINS, MAX_ASSET_DATA_LEN),
+            Err(MplCoreWrapperError::IncompatiblePluginAuthority.into())
+        );
+        assert_eq!(
+            add.validate_plugins(1, MAX_ASSET_DATA_LEN),
+            Err(MplCoreWrapperError::IncompatiblePluginAuthority.into())
+        );
+
+        assert_eq!(
+            new_attributes_data(2).check_plugin_count(1),
+            Err(MplCoreWrapperError::TooManyPlugins.into())
+        );
+        assert_eq!(
+            add.check_plugin_count(0),
+            Err(MplCoreWrapperError::TooManyPlugins.into())
+        );
+    }
+
+    #[test]
+    fn test_create_nft_raw_plugins() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let plugins = new_plugins_data(vec![
+            new_royalties(),
+            Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+        ])
+        .plugins;
+        let data = CreateNftV1InstructionData {
+            plugins: None,
+            raw_plugins: Some(plugins.as_ref().unwrap().try_to_vec().unwrap()),
+            ..new_attributes_data(0)
+        };
+
+        let instruction = CreateNftV1::try_from((accounts.as_slice(), data)).unwrap();
+        assert_eq!(instruction.instruction_data.plugins, plugins);
+        assert!(instruction.instruction_data.raw_plugins.is_none());
+    }
+
+    #[test]
+    fn test_create_nft_raw_and_structured_plugins() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let data = CreateNftV1InstructionData {
+            raw_plugins: Some(Vec::<PluginAuthorityPair>::new().try_to_vec().unwrap()),
+            ..new_attributes_data(1)
+        };
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), data));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::ConflictingPluginSources.into()
+        );
+    }
+
+    #[test]
+    fn test_create_nft_asset_from_nonce() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        let (asset, bump) = derive_asset_address(accounts[3].key, 42);
+        accounts[0] = new_test_account(asset, false, true, 1, 0, system_program::ID);
+
+        let instruction = CreateNftV1::try_from((
+            accounts.as_slice(),
+            CreateNftV1InstructionData {
+                nonce: Some(42),
+                ..new_attributes_data(0)
+            },
+        ))
+        .unwrap();
+        assert_eq!(instruction.asset_bump, Some(bump));
+
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_create_nft_asset_from_wrong_nonce() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        let (asset, _) = derive_asset_address(accounts[3].key, 42);
+        accounts[0] = new_test_account(asset, false, true, 1, 0, system_program::ID);
+
+        let res = CreateNftV1::try_from((
+            accounts.as_slice(),
+            CreateNftV1InstructionData {
+                nonce: Some(43),
+                ..new_attributes_data(0)
+            },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidAssetAddress.into()
+        );
+    }
+
+    fn update_authority_data(controller: Option<Pubkey>) -> CreateNftV1InstructionData {
+        CreateNftV1InstructionData {
+            update_authority_controller: controller,
+            ..new_attributes_data(0)
+        }
+    }
+
+    /// The CreateV1 instruction among the CPIs recorded since the last call.
+    fn recorded_create_v1() -> Instruction {
+        take_recorded_instructions()
+            .into_iter()
+            .find(|instruction| instruction.program_id == mpl_core::ID)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_create_nft_update_authority_pda() {
+        let controller = Pubkey::new_unique();
+        let (pda, _) = derive_delegated_authority(&controller);
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[5] = new_test_account(pda, false, false, 1, 0, system_program::ID);
+
+        let instruction =
+            CreateNftV1::try_from((accounts.as_slice(), update_authority_data(Some(controller))))
+                .unwrap();
+        assert_eq!(instruction.update_authority().unwrap().key, &pda);
+        assert!(instruction.signer_seeds().is_empty());
+
+        take_recorded_instructions();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(recorded_create_v1().accounts[5].pubkey, pda);
+    }
+
+    #[test]
+    fn test_create_nft_update_authority_pda_mismatch() {
+        let (pda, _) = derive_delegated_authority(&Pubkey::new_unique());
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[5] = new_test_account(pda, false, false, 1, 0, system_program::ID);
+
+        let res = CreateNftV1::try_from((
+            accounts.as_slice(),
+            update_authority_data(Some(Pubkey::new_unique())),
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AuthorityMismatch.into()
+        );
+    }
+
+    #[cfg(feature = "update")]
+    #[test]
+    fn test_create_nft_update_authority_pda_other_caller() {
+        use crate::instructions::{UpdateNftV1, UpdateNftV1InstructionData};
+        use mpl_core::types::UpdateAuthority;
+
+        let (pda, _) = derive_delegated_authority(&Pubkey::new_unique());
+        let update = |authority: AccountInfo<'static>, delegated_authority: bool| {
+            let mut accounts = update_nft_accounts(pda);
+            accounts[0] =
+                new_asset_account(Pubkey::new_unique(), UpdateAuthority::Address(pda), &[]);
+            accounts[2] = authority;
+
+            UpdateNftV1::try_from((
+                accounts.as_slice(),
+                UpdateNftV1InstructionData {
+                    new_name: Some("Renamed".to_string()),
+                    new_uri: None,
+                    uri_suffix: None,
+                    delegated_authority,
+                    approved_authorities: None,
+                    new_collection: None,
+                    expected_name: None,
+                    expected_uri: None,
+                },
+            ))
+            .map(|_| ())
+        };
+
+        assert_eq!(
+            update(
+                new_test_account(pda, false, false, 1, 0, system_program::ID),
+                true
+            ),
+            Err(MplCoreWrapperError::InvalidSignerSeeds.into())
+        );
+        assert_eq!(
+            update(
+                new_test_account(pda, false, false, 1, 0, system_program::ID),
+                false
+            ),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+    }
+
+    fn program_controlled_accounts(nonce: u64) -> Vec<AccountInfo<'static>> {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        let (asset, _) = derive_asset_address(accounts[3].key, nonce);
+        let (authority, _) = derive_delegated_authority(accounts[3].key);
+        accounts[0] = new_test_account(asset, false, true, 0, 0, system_program::ID);
+        accounts[5] = new_test_account(authority, false, false, 1, 0, system_program::ID);
+        accounts
+    }
+
+    fn program_controlled_data(nonce: Option<u64>) -> CreateNftV1InstructionData {
+        CreateNftV1InstructionData {
+            nonce,
+            program_controlled: true,
+            raw_plugins: None,
+            prefund_lamports: None,
+            ..new_attributes_data(0)
+        }
+    }
+
+    #[test]
+    fn test_create_nft_program_controlled() {
+        let accounts = program_controlled_accounts(42);
+        let (_, asset_bump) = derive_asset_address(accounts[3].key, 42);
+        let (authority, _) = derive_delegated_authority(accounts[3].key);
+
+        let instruction =
+            CreateNftV1::try_from((accounts.as_slice(), program_controlled_data(Some(42))))
+                .unwrap();
+        assert_eq!(instruction.asset_bump, Some(asset_bump));
+        assert_eq!(instruction.update_authority().unwrap().key, &authority);
+
+        let signers = instruction.signer_seeds();
+        assert_eq!(signers.len(), 1);
+        let seeds = signers[0].iter().map(Vec::as_slice).collect::<Vec<_>>();
+        assert_eq!(
+            &Pubkey::create_program_address(&seeds, &crate::ID).unwrap(),
+            accounts[0].key
+        );
+    }
+
+    #[cfg(feature = "transfer")]
+    #[test]
+    fn test_create_nft_program_controlled_other_caller() {
+        use crate::instructions::{TransferNftV1, TransferNftV1InstructionData};
+        use mpl_core::types::UpdateAuthority;
+
+        let accounts = program_controlled_accounts(42);
+        let authority = *accounts[5].key;
+        let instruction =
+            CreateNftV1::try_from((accounts.as_slice(), program_controlled_data(Some(42))))
+                .unwrap();
+        assert_eq!(instruction.update_authority().unwrap().key, &authority);
+
+        let mut transfer_accounts = transfer_nft_accounts(
+            new_asset_account(authority, UpdateAuthority::Address(authority), &[]),
+            new_test_account(authority, false, false, 1, 0, system_program::ID),
+        );
+        transfer_accounts[4] =
+            new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+
+        let res = TransferNftV1::try_from((
+            transfer_accounts.as_slice(),
+            TransferNftV1InstructionData {
+                delegated_authority: true,
+                ..TransferNftV1InstructionData::default()
+            },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidSignerSeeds.into()
+        );
+    }
+
+    #[test]
+    fn test_create_nft_program_controlled_funding() {
+        let accounts = program_controlled_accounts(42);
+        let instruction =
+            CreateNftV1::try_from((accounts.as_slice(), program_controlled_data(Some(42))))
+                .unwrap();
+        let rent = Rent::default();
+
+        assert_eq!(
+            instruction.funding_amount(&rent),
+            instruction.instruction_data.rent_exempt_lamports(&rent)
+        );
+
+        take_recorded_cpis();
+        let res = instruction.fund_asset(&rent);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["FundAsset"]);
+    }
+
+    #[test]
+    fn test_create_nft_prefund() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let data = CreateNftV1InstructionData {
+            prefund_lamports: Some(5_000),
+            ..new_attributes_data(0)
+        };
+        let rent = Rent::default();
+
+        assert_eq!(
+            data.prefunded_lamports(&rent),
+            Ok(data.rent_exempt_lamports(&rent).unwrap() + 5_000)
+        );
+
+        let instruction = CreateNftV1::try_from((accounts.as_slice(), data)).unwrap();
+        take_recorded_cpis();
+        let res = instruction.accounts.prefund_asset(5_000);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["PrefundAsset"]);
+    }
+
+    #[test]
+    fn test_create_nft_prefund_overflow() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let data = CreateNftV1InstructionData {
+            prefund_lamports: Some(u64::MAX),
+            ..new_attributes_data(0)
+        };
+
+        assert_eq!(
+            data.prefunded_lamports(&Rent::default()),
+            Err(MplCoreWrapperError::LamportOverflow.into())
+        );
+
+        let instruction = CreateNftV1::try_from((accounts.as_slice(), data)).unwrap();
+        let res = instruction.accounts.prefund_asset(u64::MAX);
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::LamportOverflow.into()
+        );
+    }
+
+    #[test]
+    fn test_create_nft_program_controlled_wrong_authority() {
+        let mut accounts = program_controlled_accounts(42);
+        accounts[5] = new_test_account(Pubkey::new_unique(), false, false, 1, 0, crate::ID);
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), program_controlled_data(Some(42))));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AuthorityMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_create_nft_program_controlled_without_nonce() {
+        let accounts = program_controlled_accounts(42);
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), program_controlled_data(None)));
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidInstructionData);
+    }
+
+    #[test]
+    fn test_create_nft_update_authority_pda_missing_account() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[5] = new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+
+        let res = CreateNftV1::try_from((
+            accounts.as_slice(),
+            update_authority_data(Some(Pubkey::new_unique())),
+        ));
+        assert_eq!(res.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[test]
+    fn test_create_nft_unsigned_update_authority_without_controller() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[5] =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), update_authority_data(None)));
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    fn owner_data(owner_is_update_authority: Option<bool>) -> CreateNftV1InstructionData {
+        CreateNftV1InstructionData {
+            default_owner_to_payer: true,
+            owner_is_update_authority,
+            ..new_attributes_data(0)
+        }
+    }
+
+    #[test]
+    fn test_create_nft_owner_is_update_authority() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[5] = new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+
+        let instruction =
+            CreateNftV1::try_from((accounts.as_slice(), owner_data(Some(true)))).unwrap();
+        assert_eq!(instruction.update_authority().unwrap().key, accounts[4].key);
+    }
+
+    #[test]
+    fn test_create_nft_owner_is_not_update_authority() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+
+        for flag in [None, Some(false)] {
+            let instruction =
+                CreateNftV1::try_from((accounts.as_slice(), owner_data(flag))).unwrap();
+            assert_eq!(instruction.update_authority().unwrap().key, accounts[5].key);
+            assert_eq!(instruction.accounts.owner.unwrap().key, accounts[4].key);
+        }
+    }
+
+    #[test]
+    fn test_create_nft_owner_is_update_authority_without_owner() {
+        let accounts = create_nft_accounts(system_program::ID);
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), owner_data(Some(true))));
+        assert_eq!(res.unwrap_err(), MplCoreWrapperError::MissingOwner.into());
+    }
+
+    #[test]
+    fn test_create_nft_event() {
+        use crate::events::{CreateNftEvent, MplCoreWrapperEvent};
+
+        let owner = Pubkey::new_unique();
+        let accounts = create_nft_accounts(owner);
+        let instruction =
+            CreateNftV1::try_from((accounts.as_slice(), new_attributes_data(0))).unwrap();
+
+        let log = instruction.event().encode().unwrap();
+        assert_eq!(
+            MplCoreWrapperEvent::decode(&log),
+            Ok(MplCoreWrapperEvent::CreateNft(CreateNftEvent {
+                asset: *accounts[0].key,
+                owner,
+                collection: Some(*accounts[1].key),
+                name: "Asset".to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_create_nft_report_size() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[0] =
+            new_test_account(Pubkey::new_unique(), true, true, 1, 187, system_program::ID);
+        let instruction = CreateNftV1::try_from((
+            accounts.as_slice(),
+            CreateNftV1InstructionData {
+                report_size: true,
+                ..new_attributes_data(0)
+            },
+        ))
+        .unwrap();
+
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+
+        // The return value is read from the asset once the create CPI ran.
+        let reported = CreateNftV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .asset_size();
+        assert_eq!(reported, accounts[0].data_len() as u64);
+        assert_eq!(reported, 187);
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_create_nft_collection_authority() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let res = CreateNftV1::try_from((accounts.as_slice(), new_attributes_data(0)));
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[cfg(feature = "collection-size")]
+    fn create_in_collection(current_size: u32, max_supply: u32) -> ProgramResult {
+        use mpl_core::types::PluginAuthority;
+
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[1] = new_sized_collection_account(
+            *accounts[2].key,
+            current_size,
+            &[(
+                Plugin::MasterEdition(MasterEdition {
+                    max_supply: Some(max_supply),
+                    name: None,
+                    uri: None,
+                }),
+                PluginAuthority::UpdateAuthority,
+            )],
+        );
+
+        CreateNftV1::try_from((accounts.as_slice(), new_attributes_data(0))).map(|_| ())
+    }
+
+    #[cfg(feature = "collection-size")]
+    #[test]
+    fn test_create_nft_collection_below_capacity() {
+        assert_eq!(create_in_collection(9, 10), Ok(()));
+    }
+
+    #[cfg(feature = "collection-size")]
+    #[test]
+    fn test_create_nft_collection_full() {
+        assert_eq!(
+            create_in_collection(10, 10),
+            Err(MplCoreWrapperError::CollectionFull.into())
+        );
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_create_nft_collection_authority_missing() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[2] = absent_account();
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), new_attributes_data(0)));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::CollectionAuthorityRequired.into()
+        );
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_create_nft_collection_authority_wrong() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts[2] = new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), new_attributes_data(0)));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::CollectionAuthorityRequired.into()
+        );
+    }
+
+    #[test]
+    fn test_create_nft_with_log_middleware() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let instruction =
+            CreateNftV1::try_from((accounts.as_slice(), new_attributes_data(0))).unwrap();
+
+        let res = Middleware::new(
+            LogMiddleware {
+                name: "CreateNftV1",
+            },
+            instruction,
+        )
+        .process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    fn new_processed_flag_accounts(id: u64, flag_owner: Pubkey) -> Vec<AccountInfo<'static>> {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        let (flag, _) = derive_processed_flag(accounts[3].key, id);
+        accounts[9] = new_test_account(flag, false, true, 0, 0, flag_owner);
+        accounts
+    }
+
+    #[test]
+    fn test_create_nft_processed_flag_omitted() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts.pop();
+
+        let res = CreateNftV1Accounts::try_from(accounts.as_slice());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert!(res.unwrap().processed_flag.is_none());
+    }
+
+    #[test]
+    fn test_create_nft_processed_flag_first_call() {
+        let accounts = new_processed_flag_accounts(7, system_program::ID);
+        let data = CreateNftV1InstructionData {
+            processed_flag_id: Some(7),
+            ..new_attributes_data(0)
+        };
+
+        let instruction = CreateNftV1::try_from((accounts.as_slice(), data)).unwrap();
+        let (_, bump) = derive_processed_flag(accounts[3].key, 7);
+        assert_eq!(instruction.processed_flag_bump, Some(bump));
+
+        take_recorded_cpis();
+        let res = instruction.mark_processed(&Rent::default());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["CreateProcessedFlag"]);
+    }
+
+    #[test]
+    fn test_create_nft_processed_flag_replay() {
+        let accounts = new_processed_flag_accounts(7, crate::ID);
+        let data = CreateNftV1InstructionData {
+            processed_flag_id: Some(7),
+            ..new_attributes_data(0)
+        };
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), data));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AlreadyProcessed.into()
+        );
+    }
+
+    #[cfg(feature = "strict-accounts")]
+    #[test]
+    fn test_create_nft_unused_processed_flag() {
+        let accounts = new_processed_flag_accounts(7, system_program::ID);
+
+        let res = CreateNftV1::try_from((accounts.as_slice(), new_attributes_data(0)));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::UnexpectedAccount.into()
+        );
+
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let res = CreateNftV1::try_from((accounts.as_slice(), new_attributes_data(0)));
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
     #[test]
     fn test_create_nft_account_not_enough_accounts() {
         let accounts = vec![];
@@ -252,4 +1638,17 @@ mod tests {
             res
         );
     }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_create_nft_account_metas() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let metas = CreateNftV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[0, 5], |accounts| {
+            CreateNftV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
 }
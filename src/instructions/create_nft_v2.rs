@@ -0,0 +1,272 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    instructions::CreateV2CpiBuilder,
+    types::{
+        AppDataInitInfo, DataState, ExternalCheckResult, ExternalPluginAdapterInitInfo,
+        ExternalPluginAdapterSchema, HookableLifecycleEvent, OracleInitInfo, Plugin,
+        PluginAuthority, PluginAuthorityPair, ValidationResultsOffset,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::{data_state_or_default, CreateNftV1Accounts, Instructions, MAX_PLUGINS},
+    plugins::PluginValidation,
+    utils::{
+        check_metadata, checked_add_size, checked_asset_size, invoke_cpi, ProcessInstruction,
+        MAX_ASSET_DATA_LEN,
+    },
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct CreateNftV2InstructionData {
+    pub data_state: Option<DataState>,
+    pub name: String,
+    pub uri: String,
+    pub plugins: Option<Vec<PluginAuthorityPair>>,
+    pub external_plugin_adapters: Option<Vec<ExternalPluginAdapterInitInfo>>,
+    pub default_owner_to_payer: bool,
+}
+
+impl CreateNftV2InstructionData {
+    /// Appends an `AppData` external plugin adapter with the given schema and data authority.
+    pub fn with_app_data(
+        &mut self,
+        schema: ExternalPluginAdapterSchema,
+        data_authority: PluginAuthority,
+    ) -> &mut Self {
+        self.external_plugin_adapters
+            .get_or_insert_with(Vec::new)
+            .push(ExternalPluginAdapterInitInfo::AppData(AppDataInitInfo {
+                data_authority,
+                init_plugin_authority: None,
+                schema: Some(schema),
+            }));
+
+        self
+    }
+
+    /// Appends an `Oracle` external plugin adapter that gates the given lifecycle events.
+    pub fn with_oracle(
+        &mut self,
+        base_address: Pubkey,
+        lifecycle_checks: Vec<(HookableLifecycleEvent, ExternalCheckResult)>,
+        results_offset: ValidationResultsOffset,
+    ) -> Result<&mut Self, ProgramError> {
+        if lifecycle_checks.is_empty() {
+            return Err(MplCoreWrapperError::EmptyOracleChecks.into());
+        }
+
+        self.external_plugin_adapters
+            .get_or_insert_with(Vec::new)
+            .push(ExternalPluginAdapterInitInfo::Oracle(OracleInitInfo {
+                base_address,
+                init_plugin_authority: None,
+                lifecycle_checks,
+                base_address_config: None,
+                results_offset: Some(results_offset),
+            }));
+
+        Ok(self)
+    }
+
+    /// Serialized size of the name, uri, plugins and adapters that end up in the asset account.
+    pub fn asset_data_len(&self) -> Result<usize, ProgramError> {
+        let mut len = checked_asset_size(
+            self.name.len(),
+            self.uri.len(),
+            self.plugins.as_deref().unwrap_or_default(),
+        )?;
+
+        for adapter in self.external_plugin_adapters.iter().flatten() {
+            len = checked_add_size(len, adapter.try_to_vec()?.len())?;
+        }
+
+        Ok(len)
+    }
+}
+
+impl PluginValidation for CreateNftV2InstructionData {
+    fn plugins(&self) -> Vec<(&Plugin, Option<&PluginAuthority>)> {
+        self.plugins
+            .iter()
+            .flatten()
+            .map(|pair| (&pair.plugin, pair.authority.as_ref()))
+            .collect()
+    }
+
+    fn data_len(&self) -> Result<usize, ProgramError> {
+        self.asset_data_len()
+    }
+}
+
+#[derive(Debug)]
+pub struct CreateNftV2<'a, 'info> {
+    pub accounts: CreateNftV1Accounts<'a, 'info>,
+    pub instruction_data: CreateNftV2InstructionData,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], CreateNftV2InstructionData)>
+    for CreateNftV2<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], CreateNftV2InstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = CreateNftV1Accounts::try_from(accounts)?;
+        accounts.check_owner(instruction_data.default_owner_to_payer)?;
+        accounts.check_update_authority(None)?;
+        #[cfg(feature = "asset-state")]
+        accounts.check_collection_authority()?;
+        instruction_data.validate_plugins(MAX_PLUGINS, MAX_ASSET_DATA_LEN)?;
+        check_metadata(&instruction_data.name, &instruction_data.uri)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for CreateNftV2<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        invoke_cpi("CreateV2", self.accounts.asset.key, || {
+            CreateV2CpiBuilder::new(self.accounts.mpl_core)
+                .asset(self.accounts.asset)
+                .collection(self.accounts.collection)
+                .authority(self.accounts.authority)
+                .payer(self.accounts.payer)
+                .owner(self.accounts.owner)
+                .update_authority(self.accounts.update_authority)
+                .system_program(
+                    self.accounts
+                        .system_program
+                        .unwrap_or(self.accounts.mpl_core),
+                )
+                .data_state(data_state_or_default(self.instruction_data.data_state))
+                .log_wrapper(self.accounts.log_wrapper)
+                .name(self.instruction_data.name)
+                .uri(self.instruction_data.uri)
+                .plugins(self.instruction_data.plugins.unwrap_or_default())
+                .external_plugin_adapters(
+                    self.instruction_data
+                        .external_plugin_adapters
+                        .unwrap_or_default(),
+                )
+                .invoke()
+        })?;
+
+        Ok(())
+    }
+}
+
+pub fn process_create_nft_v2(accounts: &[AccountInfo], instruction: Instructions) -> ProgramResult {
+    let Instructions::CreateNftV2(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    CreateNftV2::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::FreezeDelegate;
+
+    fn new_instruction_data() -> CreateNftV2InstructionData {
+        CreateNftV2InstructionData {
+            data_state: None,
+            name: "Asset".to_string(),
+            uri: "https://example.com/asset.json".to_string(),
+            plugins: None,
+            external_plugin_adapters: None,
+            default_owner_to_payer: false,
+        }
+    }
+
+    #[test]
+    fn test_create_nft_v2_with_app_data() {
+        let mut data = new_instruction_data();
+        data.with_app_data(
+            ExternalPluginAdapterSchema::Json,
+            PluginAuthority::UpdateAuthority,
+        );
+
+        let adapters = data.external_plugin_adapters.unwrap();
+        assert_eq!(adapters.len(), 1);
+        assert!(matches!(
+            &adapters[0],
+            ExternalPluginAdapterInitInfo::AppData(AppDataInitInfo {
+                data_authority: PluginAuthority::UpdateAuthority,
+                schema: Some(ExternalPluginAdapterSchema::Json),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_create_nft_v2_with_oracle() {
+        let base_address = Pubkey::new_unique();
+        let mut data = new_instruction_data();
+        let res = data.with_oracle(
+            base_address,
+            vec![(
+                HookableLifecycleEvent::Transfer,
+                ExternalCheckResult { flags: 4 },
+            )],
+            ValidationResultsOffset::Anchor,
+        );
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+
+        let adapters = data.external_plugin_adapters.unwrap();
+        assert_eq!(adapters.len(), 1);
+        assert!(matches!(
+            &adapters[0],
+            ExternalPluginAdapterInitInfo::Oracle(OracleInitInfo {
+                base_address: address,
+                results_offset: Some(ValidationResultsOffset::Anchor),
+                ..
+            }) if *address == base_address
+        ));
+    }
+
+    #[test]
+    fn test_create_nft_v2_duplicate_plugin_types() {
+        let freeze = PluginAuthorityPair {
+            plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+            authority: None,
+        };
+        let data = CreateNftV2InstructionData {
+            plugins: Some(vec![freeze.clone(), freeze]),
+            ..new_instruction_data()
+        };
+
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let res = CreateNftV2::try_from((accounts.as_slice(), data));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::DuplicatePluginType.into()
+        );
+    }
+
+    #[test]
+    fn test_create_nft_v2_with_oracle_empty_checks() {
+        let mut data = new_instruction_data();
+        let res = data
+            .with_oracle(
+                Pubkey::new_unique(),
+                vec![],
+                ValidationResultsOffset::NoOffset,
+            )
+            .map(|_| ());
+
+        assert_eq!(res, Err(MplCoreWrapperError::EmptyOracleChecks.into()));
+        assert!(data.external_plugin_adapters.is_none());
+    }
+}
@@ -0,0 +1,260 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    instructions::AddPluginV1CpiBuilder,
+    types::{Plugin, PluginAuthority},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::{
+    instructions::{CreateNftV1, CreateNftV1InstructionData, Instructions, MAX_PLUGINS},
+    plugins::PluginValidation,
+    utils::{
+        check_cpi_programs, checked_add_size, invoke_cpi, ProcessInstruction, MAX_ASSET_DATA_LEN,
+    },
+};
+
+/// Accounts are those of `CreateNftV1`, which already cover AddPluginV1.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct CreateNftWithPluginV1InstructionData {
+    pub create: CreateNftV1InstructionData,
+    pub plugin: Plugin,
+    pub init_authority: Option<PluginAuthority>,
+}
+
+/// The asset ends up with the created plugins and the added one, so they
+/// are validated together.
+impl PluginValidation for CreateNftWithPluginV1InstructionData {
+    fn plugins(&self) -> Vec<(&Plugin, Option<&PluginAuthority>)> {
+        let mut plugins = self.create.plugins();
+        plugins.push((&self.plugin, self.init_authority.as_ref()));
+        plugins
+    }
+
+    fn data_len(&self) -> Result<usize, ProgramError> {
+        let len = checked_add_size(
+            self.create.asset_data_len()?,
+            self.plugin.try_to_vec()?.len(),
+        )?;
+        checked_add_size(len, self.init_authority.try_to_vec()?.len())
+    }
+}
+
+#[derive(Debug)]
+pub struct CreateNftWithPluginV1<'a, 'info> {
+    pub create: CreateNftV1<'a, 'info>,
+    pub plugin: Plugin,
+    pub init_authority: Option<PluginAuthority>,
+}
+
+impl<'a, 'info>
+    TryFrom<(
+        &'a [AccountInfo<'info>],
+        CreateNftWithPluginV1InstructionData,
+    )> for CreateNftWithPluginV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (
+            &'a [AccountInfo<'info>],
+            CreateNftWithPluginV1InstructionData,
+        ),
+    ) -> Result<Self, Self::Error> {
+        instruction_data.validate_plugins(MAX_PLUGINS, MAX_ASSET_DATA_LEN)?;
+        let create = CreateNftV1::try_from((accounts, instruction_data.create))?;
+        check_cpi_programs(
+            accounts,
+            create.accounts.mpl_core,
+            create.accounts.log_wrapper,
+        )?;
+
+        Ok(Self {
+            create,
+            plugin: instruction_data.plugin,
+            init_authority: instruction_data.init_authority,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for CreateNftWithPluginV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        let accounts = &self.create.accounts;
+        let asset = accounts.asset;
+
+        let mut add_plugin_cpi = AddPluginV1CpiBuilder::new(accounts.mpl_core);
+
+        add_plugin_cpi
+            .asset(accounts.asset)
+            .collection(accounts.collection)
+            .payer(accounts.payer)
+            .authority(accounts.authority)
+            .system_program(accounts.system_program.unwrap_or(accounts.mpl_core))
+            .log_wrapper(accounts.log_wrapper)
+            .plugin(self.plugin);
+
+        if let Some(init_authority) = self.init_authority {
+            add_plugin_cpi.init_authority(init_authority);
+        }
+
+        self.create.process()?;
+        invoke_cpi("AddPluginV1", asset.key, || add_plugin_cpi.invoke())?;
+
+        Ok(())
+    }
+}
+
+pub fn process_create_nft_with_plugin_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::CreateNftWithPluginV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    CreateNftWithPluginV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::MplCoreWrapperError, utils::test_utils::*};
+    use mpl_core::types::{FreezeDelegate, PermanentFreezeDelegate, PluginAuthorityPair};
+    use solana_program::pubkey::Pubkey;
+
+    fn new_data(
+        plugin: Plugin,
+        init_authority: Option<PluginAuthority>,
+    ) -> CreateNftWithPluginV1InstructionData {
+        CreateNftWithPluginV1InstructionData {
+            create: CreateNftV1InstructionData {
+                data_state: None,
+                name: "Asset".to_string(),
+                uri: "https://example.com/asset.json".to_string(),
+                plugins: None,
+                default_owner_to_payer: false,
+                nonce: None,
+                owner_is_update_authority: None,
+                update_authority_controller: None,
+                report_size: false,
+                processed_flag_id: None,
+                program_controlled: false,
+                raw_plugins: None,
+                prefund_lamports: None,
+            },
+            plugin,
+            init_authority,
+        }
+    }
+
+    #[test]
+    fn test_create_nft_with_plugin_cpi_order() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let instruction = CreateNftWithPluginV1::try_from((
+            accounts.as_slice(),
+            new_data(
+                Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                Some(PluginAuthority::Owner),
+            ),
+        ))
+        .unwrap();
+
+        take_recorded_cpis();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["CreateV1", "AddPluginV1"]);
+    }
+
+    #[test]
+    fn test_create_nft_with_plugin_second_program() {
+        let mut accounts = create_nft_accounts(Pubkey::new_unique());
+        accounts.push(new_program_account(Pubkey::new_unique()));
+
+        let res = CreateNftWithPluginV1::try_from((
+            accounts.as_slice(),
+            new_data(
+                Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                Some(PluginAuthority::Owner),
+            ),
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::MplCoreProgramMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_create_nft_with_plugin_incompatible_authority() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let res = CreateNftWithPluginV1::try_from((
+            accounts.as_slice(),
+            new_data(
+                Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: false }),
+                Some(PluginAuthority::Owner),
+            ),
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::IncompatiblePluginAuthority.into()
+        );
+    }
+
+    #[test]
+    fn test_create_nft_with_plugin_duplicate_type() {
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let mut data = new_data(
+            Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+            Some(PluginAuthority::Owner),
+        );
+        data.create.plugins = Some(vec![PluginAuthorityPair {
+            plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+            authority: Some(PluginAuthority::Owner),
+        }]);
+
+        let res = CreateNftWithPluginV1::try_from((accounts.as_slice(), data));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::DuplicatePluginType.into()
+        );
+    }
+
+    #[test]
+    fn test_create_nft_with_plugin_too_many_plugins() {
+        use mpl_core::types::Attributes;
+
+        // MAX_PLUGINS distinct created plugins leave no room for the added one.
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        let mut data = new_data(
+            Plugin::Attributes(Attributes {
+                attribute_list: vec![],
+            }),
+            None,
+        );
+        data.create.plugins = Some(
+            (0..MAX_PLUGINS)
+                .map(|_| PluginAuthorityPair {
+                    plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                    authority: None,
+                })
+                .collect(),
+        );
+        assert_eq!(data.plugins().len(), MAX_PLUGINS + 1);
+
+        let res = CreateNftWithPluginV1::try_from((accounts.as_slice(), data));
+        assert_eq!(res.unwrap_err(), MplCoreWrapperError::TooManyPlugins.into());
+    }
+
+    #[test]
+    fn test_create_nft_with_plugin_not_enough_accounts() {
+        let accounts = vec![];
+        let res = CreateNftWithPluginV1::try_from((
+            accounts.as_slice(),
+            new_data(
+                Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                None,
+            ),
+        ));
+        assert_eq!(res.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+    }
+}
@@ -0,0 +1,281 @@
+use mpl_core::instructions::BurnV1CpiBuilder;
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+#[cfg(feature = "asset-state")]
+use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_asset_plugin,
+    types::{BurnDelegate, Key, PluginType},
+};
+
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+#[cfg(feature = "asset-state")]
+use crate::{
+    error::MplCoreWrapperError,
+    plugins::{is_plugin_authority, load_asset_collection},
+    utils::{assert_account_key, MinDataLen, BASE_ASSET_MIN_LEN},
+};
+use crate::{
+    instructions::Instructions,
+    utils::{
+        invoke_cpi, AccountCheck, MplCoreAccount, NonDefaultKey, NonWritable, NotProgramAccount,
+        OptionalAccountCheck, ProcessInstruction, SignerAccount, SystemAccount, ToOptionalAccount,
+        WritableAccount,
+    },
+};
+
+#[derive(Debug)]
+pub struct DelegateBurnV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    /// Holder of the asset's BurnDelegate plugin.
+    pub delegate: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: Option<&'a AccountInfo<'info>>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for DelegateBurnV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, delegate, payer, system_program, log_wrapper, mpl_core] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(3, payer)?;
+        NotProgramAccount::check_at(3, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        SignerAccount::check_at(2, delegate)?;
+        WritableAccount::check_at(3, payer)?;
+        SignerAccount::check_at(3, payer)?;
+        SystemAccount::check_optional_at(4, system_program.to_optional())?;
+        NonWritable::check_optional_at(5, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(6, mpl_core)?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            delegate,
+            payer,
+            system_program: system_program.to_optional(),
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for DelegateBurnV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            account_meta(self.delegate, true, false),
+            account_meta(self.payer, true, true),
+            optional_account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+#[cfg(feature = "asset-state")]
+impl<'a, 'info> DelegateBurnV1Accounts<'a, 'info> {
+    /// Checks that `delegate` holds the asset's BurnDelegate plugin, so an
+    /// unapproved key fails here instead of inside the mpl_core CPI.
+    pub fn check_burn_delegate(&self) -> ProgramResult {
+        MinDataLen::check(self.asset, BASE_ASSET_MIN_LEN)?;
+        assert_account_key(self.asset, Key::AssetV1)?;
+        let asset =
+            BaseAssetV1::try_from(self.asset).map_err(|_| ProgramError::InvalidAccountData)?;
+
+        let (plugin_authority, _, _) =
+            fetch_asset_plugin::<BurnDelegate>(self.asset, PluginType::BurnDelegate)
+                .map_err(|_| MplCoreWrapperError::UnauthorizedBurnDelegate)?;
+
+        let collection = load_asset_collection(&asset, self.collection)?;
+
+        if !is_plugin_authority(
+            &plugin_authority,
+            self.delegate.key,
+            &asset,
+            collection.as_ref(),
+        ) {
+            return Err(MplCoreWrapperError::UnauthorizedBurnDelegate.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct DelegateBurnV1<'a, 'info> {
+    pub accounts: DelegateBurnV1Accounts<'a, 'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for DelegateBurnV1<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts = DelegateBurnV1Accounts::try_from(accounts)?;
+        #[cfg(feature = "asset-state")]
+        accounts.check_burn_delegate()?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for DelegateBurnV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        invoke_cpi("BurnV1", self.accounts.asset.key, || {
+            BurnV1CpiBuilder::new(self.accounts.mpl_core)
+                .asset(self.accounts.asset)
+                .collection(self.accounts.collection)
+                .authority(Some(self.accounts.delegate))
+                .payer(self.accounts.payer)
+                .system_program(self.accounts.system_program)
+                .log_wrapper(self.accounts.log_wrapper)
+                .invoke()
+        })
+    }
+}
+
+pub fn process_delegate_burn_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::DelegateBurnV1 = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    DelegateBurnV1::try_from(accounts)?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::UpdateAuthority;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(
+        asset: AccountInfo<'static>,
+        delegate: AccountInfo<'static>,
+    ) -> Vec<AccountInfo<'static>> {
+        vec![
+            asset,
+            absent_account(),
+            delegate,
+            new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+            absent_account(),
+            new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+        ]
+    }
+
+    #[test]
+    fn test_delegate_burn_delegate_not_signer() {
+        let accounts = new_accounts(
+            new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]),
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID),
+        );
+
+        let res = DelegateBurnV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[test]
+    fn test_delegate_burn_not_enough_accounts() {
+        let accounts = vec![];
+        let res = DelegateBurnV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_delegate_burn_by_burn_delegate() {
+        use mpl_core::types::{Plugin, PluginAuthority};
+
+        let delegate = Pubkey::new_unique();
+        let accounts = new_accounts(
+            new_asset_account(
+                Pubkey::new_unique(),
+                UpdateAuthority::None,
+                &[(
+                    Plugin::BurnDelegate(BurnDelegate {}),
+                    PluginAuthority::Address { address: delegate },
+                )],
+            ),
+            new_test_account(delegate, true, false, 1, 0, system_program::ID),
+        );
+
+        let res = DelegateBurnV1::try_from(accounts.as_slice()).and_then(|ix| ix.process());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_delegate_burn_unauthorized_key() {
+        use mpl_core::types::{Plugin, PluginAuthority};
+
+        let asset = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::None,
+            &[(
+                Plugin::BurnDelegate(BurnDelegate {}),
+                PluginAuthority::Address {
+                    address: Pubkey::new_unique(),
+                },
+            )],
+        );
+        let accounts = new_accounts(
+            asset,
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
+
+        let res = DelegateBurnV1::try_from(accounts.as_slice());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::UnauthorizedBurnDelegate.into()
+        );
+
+        // Without a BurnDelegate plugin there is no one to delegate to.
+        let accounts = new_accounts(
+            new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
+        let res = DelegateBurnV1::try_from(accounts.as_slice());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::UnauthorizedBurnDelegate.into()
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_delegate_burn_account_metas() {
+        let accounts = new_accounts(
+            new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
+        let metas = DelegateBurnV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            DelegateBurnV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
@@ -0,0 +1,31 @@
+//! Borsh variant index of each [`Instructions`](super::Instructions) variant,
+//! i.e. the first byte of its instruction data. These never change once
+//! released; a new instruction takes the next free value.
+
+pub const CREATE_NFT_V1: u8 = 0;
+pub const UPDATE_NFT_V1: u8 = 1;
+pub const TRANSFER_NFT_V1: u8 = 2;
+pub const CREATE_NFT_V2: u8 = 3;
+pub const EXECUTE_NFT_V1: u8 = 4;
+pub const GET_ASSET_INFO_V1: u8 = 5;
+pub const TRANSFER_COLLECTION_AUTHORITY_V1: u8 = 6;
+pub const TRANSFER_NFT_BATCH_V1: u8 = 7;
+pub const ADD_AUTOGRAPH_V1: u8 = 8;
+pub const CREATE_COLLECTION_V2: u8 = 9;
+pub const UPDATE_ATTRIBUTES_V1: u8 = 10;
+pub const REMOVE_PLUGIN_V1: u8 = 11;
+pub const UPDATE_ROYALTY_RULESET_V1: u8 = 12;
+pub const ADD_PLUGIN_V1: u8 = 13;
+pub const CREATE_NFT_WITH_PLUGIN_V1: u8 = 14;
+pub const BURN_AND_REFUND_V1: u8 = 15;
+pub const TRANSFER_WITH_PROVENANCE_V1: u8 = 16;
+pub const REVOKE_ALL_PLUGIN_AUTHORITIES_V1: u8 = 17;
+pub const UPDATE_EXTERNAL_PLUGIN_ADAPTER_V1: u8 = 18;
+pub const UPDATE_PLUGINS_BATCH_V1: u8 = 19;
+pub const SET_PERMANENT_BURN_DELEGATE_V1: u8 = 20;
+pub const DELEGATE_BURN_V1: u8 = 21;
+pub const TRANSFER_AND_FREEZE_V1: u8 = 22;
+pub const THAW_AND_TRANSFER_V1: u8 = 23;
+pub const UPDATE_NFT_BATCH_V1: u8 = 24;
+pub const ADD_TO_COLLECTION_V1: u8 = 25;
+pub const REMOVE_FROM_COLLECTION_V1: u8 = 26;
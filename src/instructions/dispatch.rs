@@ -0,0 +1,147 @@
+use borsh::BorshDeserialize;
+#[cfg(feature = "std")]
+use solana_program::msg;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::instructions::*;
+
+pub type InstructionHandler = fn(&[AccountInfo], Instructions) -> ProgramResult;
+
+/// Handlers indexed by the borsh variant index of [`Instructions`].
+///
+/// A new instruction appends its handler here, at the same position as its
+/// variant in the enum. Instructions whose feature is disabled are `None`.
+pub const DISPATCH_TABLE: &[Option<InstructionHandler>] = &[
+    #[cfg(feature = "create")]
+    Some(process_create_nft_v1),
+    #[cfg(not(feature = "create"))]
+    None,
+    #[cfg(feature = "update")]
+    Some(process_update_nft_v1),
+    #[cfg(not(feature = "update"))]
+    None,
+    #[cfg(feature = "transfer")]
+    Some(process_transfer_nft_v1),
+    #[cfg(not(feature = "transfer"))]
+    None,
+    #[cfg(feature = "create")]
+    Some(process_create_nft_v2),
+    #[cfg(not(feature = "create"))]
+    None,
+    Some(process_execute_nft_v1),
+    Some(process_get_asset_info_v1),
+    Some(process_transfer_collection_authority_v1),
+    #[cfg(feature = "transfer")]
+    Some(process_transfer_nft_batch_v1),
+    #[cfg(not(feature = "transfer"))]
+    None,
+    Some(process_add_autograph_v1),
+    #[cfg(feature = "create")]
+    Some(process_create_collection_v2),
+    #[cfg(not(feature = "create"))]
+    None,
+    Some(process_update_attributes_v1),
+    Some(process_remove_plugin_v1),
+    Some(process_update_royalty_ruleset_v1),
+    Some(process_add_plugin_v1),
+    #[cfg(feature = "create")]
+    Some(process_create_nft_with_plugin_v1),
+    #[cfg(not(feature = "create"))]
+    None,
+    Some(process_burn_and_refund_v1),
+    #[cfg(feature = "transfer")]
+    Some(process_transfer_with_provenance_v1),
+    #[cfg(not(feature = "transfer"))]
+    None,
+    Some(process_revoke_all_plugin_authorities_v1),
+    Some(process_update_external_plugin_adapter_v1),
+    Some(process_update_plugins_batch_v1),
+    Some(process_set_permanent_burn_delegate_v1),
+    Some(process_delegate_burn_v1),
+    #[cfg(feature = "transfer")]
+    Some(process_transfer_and_freeze_v1),
+    #[cfg(not(feature = "transfer"))]
+    None,
+    #[cfg(feature = "transfer")]
+    Some(process_thaw_and_transfer_v1),
+    #[cfg(not(feature = "transfer"))]
+    None,
+    #[cfg(feature = "update")]
+    Some(process_update_nft_batch_v1),
+    #[cfg(not(feature = "update"))]
+    None,
+    #[cfg(feature = "update")]
+    Some(process_add_to_collection_v1),
+    #[cfg(not(feature = "update"))]
+    None,
+    #[cfg(feature = "update")]
+    Some(process_remove_from_collection_v1),
+    #[cfg(not(feature = "update"))]
+    None,
+];
+
+/// Fewest accounts the instruction with `discriminant` takes; batch and
+/// execute instructions take more after these.
+///
+/// Checked before deserializing so a short account list fails with the
+/// instruction it was meant for, instead of deep in its handler.
+pub fn min_accounts(discriminant: u8) -> usize {
+    match discriminant {
+        discriminant::CREATE_NFT_V1
+        | discriminant::CREATE_NFT_V2
+        | discriminant::CREATE_NFT_WITH_PLUGIN_V1 => 9,
+        discriminant::TRANSFER_NFT_V1
+        | discriminant::TRANSFER_WITH_PROVENANCE_V1
+        | discriminant::TRANSFER_AND_FREEZE_V1
+        | discriminant::EXECUTE_NFT_V1
+        | discriminant::BURN_AND_REFUND_V1
+        | discriminant::ADD_TO_COLLECTION_V1 => 8,
+        discriminant::THAW_AND_TRANSFER_V1 => 9,
+        discriminant::GET_ASSET_INFO_V1 => 2,
+        discriminant::TRANSFER_NFT_BATCH_V1 | discriminant::CREATE_COLLECTION_V2 => 5,
+        discriminant::UPDATE_NFT_BATCH_V1 => 6,
+        discriminant::UPDATE_NFT_V1
+        | discriminant::TRANSFER_COLLECTION_AUTHORITY_V1
+        | discriminant::ADD_AUTOGRAPH_V1
+        | discriminant::UPDATE_ATTRIBUTES_V1
+        | discriminant::REMOVE_PLUGIN_V1
+        | discriminant::UPDATE_ROYALTY_RULESET_V1
+        | discriminant::ADD_PLUGIN_V1
+        | discriminant::REVOKE_ALL_PLUGIN_AUTHORITIES_V1
+        | discriminant::UPDATE_EXTERNAL_PLUGIN_ADAPTER_V1
+        | discriminant::UPDATE_PLUGINS_BATCH_V1
+        | discriminant::SET_PERMANENT_BURN_DELEGATE_V1
+        | discriminant::DELEGATE_BURN_V1
+        | discriminant::REMOVE_FROM_COLLECTION_V1 => 7,
+        _ => 0,
+    }
+}
+
+pub fn dispatch(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+    // Looked up before deserializing, as a disabled variant cannot decode the
+    // payload its clients send.
+    let discriminant = *instruction_data
+        .first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let handler = DISPATCH_TABLE
+        .get(discriminant as usize)
+        .copied()
+        .flatten()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    let min_accounts = min_accounts(discriminant);
+    if accounts.len() < min_accounts {
+        #[cfg(feature = "std")]
+        msg!(
+            "Instruction {} takes at least {} accounts, got {}",
+            discriminant,
+            min_accounts,
+            accounts.len()
+        );
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    handler(accounts, Instructions::try_from_slice(instruction_data)?)
+}
@@ -0,0 +1,268 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::instructions::ExecuteV1CpiBuilder;
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    instructions::Instructions,
+    utils::{
+        check_asset_signer, invoke_cpi, propagate_return_data, AccountCheck, MplCoreAccount,
+        NonDefaultKey, NotProgramAccount, OptionalAccountCheck, ProcessInstruction, SignerAccount,
+        SystemAccount, ToOptionalAccount, WritableAccount,
+    },
+};
+
+#[derive(Debug)]
+pub struct ExecuteNftV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub asset_signer: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub authority: Option<&'a AccountInfo<'info>>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub program_id: &'a AccountInfo<'info>,
+    pub mpl_core: &'a AccountInfo<'info>,
+    pub remaining_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for ExecuteNftV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, asset_signer, payer, authority, system_program, program_id, mpl_core, remaining_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(3, payer)?;
+        NotProgramAccount::check_at(3, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        check_asset_signer(asset, asset_signer)?;
+        WritableAccount::check_at(3, payer)?;
+        SignerAccount::check_at(3, payer)?;
+        SignerAccount::check_optional_at(4, authority.to_optional())?;
+        SystemAccount::check_at(5, system_program)?;
+        MplCoreAccount::check_at(7, mpl_core)?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            asset_signer,
+            payer,
+            authority: authority.to_optional(),
+            system_program,
+            program_id,
+            mpl_core,
+            remaining_accounts,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for ExecuteNftV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        let mut metas = vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            account_meta(self.asset_signer, false, false),
+            account_meta(self.payer, true, true),
+            optional_account_meta(self.authority, true, false),
+            account_meta(self.system_program, false, false),
+            account_meta(self.program_id, false, false),
+            account_meta(self.mpl_core, false, false),
+        ];
+
+        metas.extend(
+            self.remaining_accounts
+                .iter()
+                .map(|account| account_meta(account, account.is_signer, account.is_writable)),
+        );
+
+        metas
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ExecuteNftV1InstructionData {
+    pub instruction_data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct ExecuteNftV1<'a, 'info> {
+    pub accounts: ExecuteNftV1Accounts<'a, 'info>,
+    pub instruction_data: ExecuteNftV1InstructionData,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], ExecuteNftV1InstructionData)>
+    for ExecuteNftV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], ExecuteNftV1InstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = ExecuteNftV1Accounts::try_from(accounts)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for ExecuteNftV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        invoke_cpi("ExecuteV1", self.accounts.asset.key, || {
+            ExecuteV1CpiBuilder::new(self.accounts.mpl_core)
+                .asset(self.accounts.asset)
+                .collection(self.accounts.collection)
+                .asset_signer(self.accounts.asset_signer)
+                .payer(self.accounts.payer)
+                .authority(self.accounts.authority)
+                .system_program(self.accounts.system_program)
+                .program_id(self.accounts.program_id)
+                .instruction_data(self.instruction_data.instruction_data)
+                .add_remaining_accounts(
+                    &self
+                        .accounts
+                        .remaining_accounts
+                        .iter()
+                        .map(|account| (account, account.is_writable, account.is_signer))
+                        .collect::<Vec<_>>(),
+                )
+                .invoke()
+        })?;
+
+        // The executed program's return data would otherwise stop at this
+        // program.
+        propagate_return_data();
+
+        Ok(())
+    }
+}
+
+pub fn process_execute_nft_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::ExecuteNftV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    ExecuteNftV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::MplCoreWrapperError, utils::derive_asset_signer, utils::test_utils::*};
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(asset_signer: Option<Pubkey>) -> Vec<AccountInfo<'static>> {
+        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, mpl_core::ID);
+        let collection = new_test_account(Pubkey::new_unique(), false, true, 1, 0, mpl_core::ID);
+        let asset_signer = new_test_account(
+            asset_signer.unwrap_or_else(|| derive_asset_signer(asset.key).0),
+            false,
+            false,
+            1,
+            0,
+            system_program::ID,
+        );
+        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let authority =
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        let system_program =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let program_id = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            1,
+            0,
+            Pubkey::new_unique(),
+        );
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        vec![
+            asset,
+            collection,
+            asset_signer,
+            payer,
+            authority,
+            system_program,
+            program_id,
+            mpl_core,
+        ]
+    }
+
+    #[test]
+    fn test_execute_nft_account_success() {
+        let accounts = new_accounts(None);
+        let res = ExecuteNftV1Accounts::try_from(accounts.as_slice());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_execute_nft_account_wrong_asset_signer() {
+        let accounts = new_accounts(Some(Pubkey::new_unique()));
+        let res = ExecuteNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidAssetSigner.into()
+        );
+    }
+
+    #[test]
+    fn test_execute_nft_propagates_return_data() {
+        let accounts = new_accounts(None);
+        let instruction = ExecuteNftV1::try_from((
+            accounts.as_slice(),
+            ExecuteNftV1InstructionData {
+                instruction_data: vec![],
+            },
+        ))
+        .unwrap();
+
+        take_recorded_cpis();
+        mock_return_data(*accounts[6].key, &[1, 2, 3]);
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["ExecuteV1"]);
+        assert_eq!(get_return_data(), Some((crate::ID, vec![1, 2, 3])));
+    }
+
+    #[test]
+    fn test_execute_nft_account_not_enough_accounts() {
+        let accounts = vec![];
+        let res = ExecuteNftV1Accounts::try_from(accounts.as_slice());
+        assert!(
+            res.is_err(),
+            "expected failure because account is not enough, but got Ok: {:?}",
+            res
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_execute_nft_account_metas() {
+        let accounts = new_accounts(None);
+        let metas = ExecuteNftV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            ExecuteNftV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
@@ -0,0 +1,185 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{accounts::BaseAssetV1, types::Key};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::set_return_data,
+    program_error::ProgramError,
+};
+
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, ToAccountMetas};
+use crate::{
+    instructions::Instructions,
+    utils::{
+        assert_account_key, AccountCheck, MinDataLen, MplCoreAccount, NonDefaultKey,
+        NotProgramAccount, ProcessInstruction, BASE_ASSET_MIN_LEN,
+    },
+};
+
+#[derive(Debug)]
+pub struct GetAssetInfoV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for GetAssetInfoV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, mpl_core] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        MplCoreAccount::check_at(0, asset)?;
+        MplCoreAccount::check_at(1, mpl_core)?;
+
+        Ok(Self { asset, mpl_core })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for GetAssetInfoV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+/// Return data emitted by `GetAssetInfoV1`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+pub struct AssetInfo {
+    pub name: String,
+    pub uri: String,
+}
+
+#[derive(Debug)]
+pub struct GetAssetInfoV1<'a, 'info> {
+    accounts: GetAssetInfoV1Accounts<'a, 'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for GetAssetInfoV1<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts = GetAssetInfoV1Accounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a, 'info> GetAssetInfoV1<'a, 'info> {
+    pub fn asset_info(&self) -> Result<AssetInfo, ProgramError> {
+        MinDataLen::check(self.accounts.asset, BASE_ASSET_MIN_LEN)?;
+        assert_account_key(self.accounts.asset, Key::AssetV1)?;
+        let asset = BaseAssetV1::try_from(self.accounts.asset)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        Ok(AssetInfo {
+            name: asset.name,
+            uri: asset.uri,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for GetAssetInfoV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        set_return_data(&self.asset_info()?.try_to_vec()?);
+
+        Ok(())
+    }
+}
+
+pub fn process_get_asset_info_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::GetAssetInfoV1 = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    GetAssetInfoV1::try_from(accounts)?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::MplCoreWrapperError, utils::test_utils::*};
+    use mpl_core::types::UpdateAuthority;
+    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn test_get_asset_info_return_data() {
+        let asset = new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+        let accounts = vec![asset, mpl_core];
+
+        let instruction = GetAssetInfoV1::try_from(accounts.as_slice()).unwrap();
+        let return_data = instruction.asset_info().unwrap().try_to_vec().unwrap();
+
+        assert_eq!(
+            AssetInfo::try_from_slice(&return_data).unwrap(),
+            AssetInfo {
+                name: "Asset".to_string(),
+                uri: "https://example.com/asset.json".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_asset_info_asset_data_too_small() {
+        let asset = new_test_account(Pubkey::new_unique(), false, false, 1, 0, mpl_core::ID);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+        let accounts = vec![asset, mpl_core];
+
+        let instruction = GetAssetInfoV1::try_from(accounts.as_slice()).unwrap();
+        assert_eq!(
+            instruction.asset_info(),
+            Err(MplCoreWrapperError::AccountDataTooSmall.into())
+        );
+    }
+
+    #[test]
+    fn test_get_asset_info_invalid_asset_data() {
+        let collection = new_collection_account(Pubkey::new_unique());
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+        let accounts = vec![collection, mpl_core];
+
+        let instruction = GetAssetInfoV1::try_from(accounts.as_slice()).unwrap();
+        assert_eq!(
+            instruction.asset_info(),
+            Err(MplCoreWrapperError::InvalidAccountKey.into())
+        );
+    }
+
+    #[test]
+    fn test_get_asset_info_account_not_enough_accounts() {
+        let accounts = vec![];
+        let res = GetAssetInfoV1Accounts::try_from(accounts.as_slice());
+        assert!(
+            res.is_err(),
+            "expected failure because account is not enough, but got Ok: {:?}",
+            res
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_get_asset_info_account_metas() {
+        let accounts = vec![
+            new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]),
+            new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+        ];
+        let metas = GetAssetInfoV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            GetAssetInfoV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
@@ -1,16 +1,611 @@
+#[cfg(all(test, feature = "create", feature = "update", feature = "transfer"))]
+mod account_sync;
+pub mod add_autograph_v1;
+pub mod add_plugin_v1;
+#[cfg(feature = "update")]
+pub mod add_to_collection_v1;
+pub mod burn_and_refund_v1;
+#[cfg(feature = "client")]
+pub mod compute_units;
+#[cfg(feature = "create")]
+pub mod create_collection_v2;
+#[cfg(feature = "create")]
 pub mod create_nft_v1;
+#[cfg(feature = "create")]
+pub mod create_nft_v2;
+#[cfg(feature = "create")]
+pub mod create_nft_with_plugin_v1;
+pub mod delegate_burn_v1;
+pub mod discriminant;
+pub mod dispatch;
+pub mod execute_nft_v1;
+pub mod get_asset_info_v1;
+#[cfg(feature = "update")]
+pub mod remove_from_collection_v1;
+pub mod remove_plugin_v1;
+pub mod revoke_all_plugin_authorities_v1;
+pub mod set_permanent_burn_delegate_v1;
+#[cfg(feature = "transfer")]
+pub mod thaw_and_transfer_v1;
+#[cfg(feature = "transfer")]
+pub mod transfer_and_freeze_v1;
+pub mod transfer_collection_authority_v1;
+#[cfg(feature = "transfer")]
+pub mod transfer_nft_batch_v1;
+#[cfg(feature = "transfer")]
 pub mod transfer_nft_v1;
+#[cfg(feature = "transfer")]
+pub mod transfer_with_provenance_v1;
+pub mod update_attributes_v1;
+pub mod update_external_plugin_adapter_v1;
+#[cfg(feature = "update")]
+pub mod update_nft_batch_v1;
+#[cfg(feature = "update")]
 pub mod update_nft_v1;
+pub mod update_plugins_batch_v1;
+pub mod update_royalty_ruleset_v1;
 
+pub use add_autograph_v1::*;
+pub use add_plugin_v1::*;
+#[cfg(feature = "update")]
+pub use add_to_collection_v1::*;
+pub use burn_and_refund_v1::*;
+#[cfg(feature = "client")]
+pub use compute_units::*;
+#[cfg(feature = "create")]
+pub use create_collection_v2::*;
+#[cfg(feature = "create")]
 pub use create_nft_v1::*;
+#[cfg(feature = "create")]
+pub use create_nft_v2::*;
+#[cfg(feature = "create")]
+pub use create_nft_with_plugin_v1::*;
+pub use delegate_burn_v1::*;
+pub use dispatch::*;
+pub use execute_nft_v1::*;
+pub use get_asset_info_v1::*;
+#[cfg(feature = "update")]
+pub use remove_from_collection_v1::*;
+pub use remove_plugin_v1::*;
+pub use revoke_all_plugin_authorities_v1::*;
+pub use set_permanent_burn_delegate_v1::*;
+#[cfg(feature = "transfer")]
+pub use thaw_and_transfer_v1::*;
+#[cfg(feature = "transfer")]
+pub use transfer_and_freeze_v1::*;
+pub use transfer_collection_authority_v1::*;
+#[cfg(feature = "transfer")]
+pub use transfer_nft_batch_v1::*;
+#[cfg(feature = "transfer")]
 pub use transfer_nft_v1::*;
+#[cfg(feature = "transfer")]
+pub use transfer_with_provenance_v1::*;
+pub use update_attributes_v1::*;
+pub use update_external_plugin_adapter_v1::*;
+#[cfg(feature = "update")]
+pub use update_nft_batch_v1::*;
+#[cfg(feature = "update")]
 pub use update_nft_v1::*;
+pub use update_plugins_batch_v1::*;
+pub use update_royalty_ruleset_v1::*;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 
+/// Borsh encodes the variant index as the leading byte, so new variants must
+/// be appended to the end of this enum to keep existing clients working, with
+/// their handler appended to [`DISPATCH_TABLE`] and their account count added
+/// to [`min_accounts`].
+///
+/// Variants of instructions whose feature is disabled stay as unit variants so
+/// the indices of the rest do not move.
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub enum Instructions {
+    #[cfg(feature = "create")]
     CreateNftV1(CreateNftV1InstructionData),
+    #[cfg(not(feature = "create"))]
+    CreateNftV1,
+    #[cfg(feature = "update")]
     UpdateNftV1(UpdateNftV1InstructionData),
+    #[cfg(not(feature = "update"))]
+    UpdateNftV1,
+    #[cfg(feature = "transfer")]
+    TransferNftV1(TransferNftV1InstructionData),
+    #[cfg(not(feature = "transfer"))]
     TransferNftV1,
+    #[cfg(feature = "create")]
+    CreateNftV2(CreateNftV2InstructionData),
+    #[cfg(not(feature = "create"))]
+    CreateNftV2,
+    ExecuteNftV1(ExecuteNftV1InstructionData),
+    GetAssetInfoV1,
+    TransferCollectionAuthorityV1(TransferCollectionAuthorityV1InstructionData),
+    #[cfg(feature = "transfer")]
+    TransferNftBatchV1(TransferNftBatchV1InstructionData),
+    #[cfg(not(feature = "transfer"))]
+    TransferNftBatchV1,
+    AddAutographV1(AddAutographV1InstructionData),
+    #[cfg(feature = "create")]
+    CreateCollectionV2(CreateCollectionV2InstructionData),
+    #[cfg(not(feature = "create"))]
+    CreateCollectionV2,
+    UpdateAttributesV1(UpdateAttributesV1InstructionData),
+    RemovePluginV1(RemovePluginV1InstructionData),
+    UpdateRoyaltyRulesetV1(UpdateRoyaltyRulesetV1InstructionData),
+    AddPluginV1(AddPluginV1InstructionData),
+    #[cfg(feature = "create")]
+    CreateNftWithPluginV1(CreateNftWithPluginV1InstructionData),
+    #[cfg(not(feature = "create"))]
+    CreateNftWithPluginV1,
+    BurnAndRefundV1(BurnAndRefundV1InstructionData),
+    #[cfg(feature = "transfer")]
+    TransferWithProvenanceV1(TransferWithProvenanceV1InstructionData),
+    #[cfg(not(feature = "transfer"))]
+    TransferWithProvenanceV1,
+    RevokeAllPluginAuthoritiesV1,
+    UpdateExternalPluginAdapterV1(UpdateExternalPluginAdapterV1InstructionData),
+    UpdatePluginsBatchV1(UpdatePluginsBatchV1InstructionData),
+    SetPermanentBurnDelegateV1(SetPermanentBurnDelegateV1InstructionData),
+    DelegateBurnV1,
+    #[cfg(feature = "transfer")]
+    TransferAndFreezeV1(TransferAndFreezeV1InstructionData),
+    #[cfg(not(feature = "transfer"))]
+    TransferAndFreezeV1,
+    #[cfg(feature = "transfer")]
+    ThawAndTransferV1(ThawAndTransferV1InstructionData),
+    #[cfg(not(feature = "transfer"))]
+    ThawAndTransferV1,
+    #[cfg(feature = "update")]
+    UpdateNftBatchV1(UpdateNftBatchV1InstructionData),
+    #[cfg(not(feature = "update"))]
+    UpdateNftBatchV1,
+    #[cfg(feature = "update")]
+    AddToCollectionV1(AddToCollectionV1InstructionData),
+    #[cfg(not(feature = "update"))]
+    AddToCollectionV1,
+    #[cfg(feature = "update")]
+    RemoveFromCollectionV1(RemoveFromCollectionV1InstructionData),
+    #[cfg(not(feature = "update"))]
+    RemoveFromCollectionV1,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::absent_account;
+    use solana_program::program_error::ProgramError;
+
+    fn all_instructions() -> Vec<Instructions> {
+        #[cfg(feature = "create")]
+        let create = Instructions::CreateNftV1(CreateNftV1InstructionData {
+            data_state: None,
+            name: String::new(),
+            uri: String::new(),
+            plugins: None,
+            default_owner_to_payer: false,
+            nonce: None,
+            owner_is_update_authority: None,
+            update_authority_controller: None,
+            report_size: false,
+            processed_flag_id: None,
+            program_controlled: false,
+            raw_plugins: None,
+            prefund_lamports: None,
+        });
+        #[cfg(not(feature = "create"))]
+        let create = Instructions::CreateNftV1;
+        #[cfg(feature = "update")]
+        let update = Instructions::UpdateNftV1(UpdateNftV1InstructionData {
+            new_name: None,
+            new_uri: None,
+            uri_suffix: None,
+            delegated_authority: false,
+            approved_authorities: None,
+            new_collection: None,
+            expected_name: None,
+            expected_uri: None,
+        });
+        #[cfg(not(feature = "update"))]
+        let update = Instructions::UpdateNftV1;
+        #[cfg(feature = "transfer")]
+        let transfer = Instructions::TransferNftV1(TransferNftV1InstructionData {
+            delegated_authority: false,
+            recipient_allow_list: None,
+            require_owner_signature: None,
+        });
+        #[cfg(not(feature = "transfer"))]
+        let transfer = Instructions::TransferNftV1;
+        #[cfg(feature = "create")]
+        let create_v2 = Instructions::CreateNftV2(CreateNftV2InstructionData {
+            data_state: None,
+            name: String::new(),
+            uri: String::new(),
+            plugins: None,
+            external_plugin_adapters: None,
+            default_owner_to_payer: false,
+        });
+        #[cfg(not(feature = "create"))]
+        let create_v2 = Instructions::CreateNftV2;
+        let execute = Instructions::ExecuteNftV1(ExecuteNftV1InstructionData {
+            instruction_data: vec![],
+        });
+        let get_asset_info = Instructions::GetAssetInfoV1;
+        let transfer_collection_authority = Instructions::TransferCollectionAuthorityV1(
+            TransferCollectionAuthorityV1InstructionData {
+                new_authority: Default::default(),
+            },
+        );
+        #[cfg(feature = "transfer")]
+        let transfer_batch =
+            Instructions::TransferNftBatchV1(TransferNftBatchV1InstructionData { count: 0 });
+        #[cfg(not(feature = "transfer"))]
+        let transfer_batch = Instructions::TransferNftBatchV1;
+        let add_autograph = Instructions::AddAutographV1(AddAutographV1InstructionData {
+            message: String::new(),
+        });
+        #[cfg(feature = "create")]
+        let create_collection_v2 =
+            Instructions::CreateCollectionV2(CreateCollectionV2InstructionData {
+                name: String::new(),
+                uri: String::new(),
+                plugins: None,
+                external_plugin_adapters: None,
+            });
+        #[cfg(not(feature = "create"))]
+        let create_collection_v2 = Instructions::CreateCollectionV2;
+        let update_attributes =
+            Instructions::UpdateAttributesV1(UpdateAttributesV1InstructionData {
+                attributes: vec![],
+            });
+        let remove_plugin = Instructions::RemovePluginV1(RemovePluginV1InstructionData {
+            plugin_type: mpl_core::types::PluginType::Royalties,
+        });
+        let update_royalty_ruleset =
+            Instructions::UpdateRoyaltyRulesetV1(UpdateRoyaltyRulesetV1InstructionData {
+                rule_set: mpl_core::types::RuleSet::None,
+            });
+        let add_plugin = Instructions::AddPluginV1(AddPluginV1InstructionData {
+            plugin: mpl_core::types::Plugin::ImmutableMetadata(
+                mpl_core::types::ImmutableMetadata {},
+            ),
+            init_authority: None,
+            fund_realloc: false,
+        });
+        #[cfg(feature = "create")]
+        let create_nft_with_plugin =
+            Instructions::CreateNftWithPluginV1(CreateNftWithPluginV1InstructionData {
+                create: CreateNftV1InstructionData {
+                    data_state: None,
+                    name: String::new(),
+                    uri: String::new(),
+                    plugins: None,
+                    default_owner_to_payer: false,
+                    nonce: None,
+                    owner_is_update_authority: None,
+                    update_authority_controller: None,
+                    report_size: false,
+                    processed_flag_id: None,
+                    program_controlled: false,
+                    raw_plugins: None,
+                    prefund_lamports: None,
+                },
+                plugin: mpl_core::types::Plugin::ImmutableMetadata(
+                    mpl_core::types::ImmutableMetadata {},
+                ),
+                init_authority: None,
+            });
+        #[cfg(not(feature = "create"))]
+        let create_nft_with_plugin = Instructions::CreateNftWithPluginV1;
+        let burn_and_refund = Instructions::BurnAndRefundV1(BurnAndRefundV1InstructionData {
+            delegated_authority: false,
+        });
+        #[cfg(feature = "transfer")]
+        let transfer_with_provenance =
+            Instructions::TransferWithProvenanceV1(TransferWithProvenanceV1InstructionData {
+                transfer: TransferNftV1InstructionData {
+                    delegated_authority: false,
+                    recipient_allow_list: None,
+                    require_owner_signature: None,
+                },
+            });
+        #[cfg(not(feature = "transfer"))]
+        let transfer_with_provenance = Instructions::TransferWithProvenanceV1;
+        let revoke_all_plugin_authorities = Instructions::RevokeAllPluginAuthoritiesV1;
+        let update_external_plugin_adapter = Instructions::UpdateExternalPluginAdapterV1(
+            UpdateExternalPluginAdapterV1InstructionData {
+                key: mpl_core::types::ExternalPluginAdapterKey::Oracle(
+                    solana_program::pubkey::Pubkey::default(),
+                ),
+                lifecycle_checks: vec![],
+            },
+        );
+        let update_plugins_batch =
+            Instructions::UpdatePluginsBatchV1(UpdatePluginsBatchV1InstructionData {
+                plugins: vec![],
+            });
+        let set_permanent_burn_delegate =
+            Instructions::SetPermanentBurnDelegateV1(SetPermanentBurnDelegateV1InstructionData {
+                delegate: solana_program::pubkey::Pubkey::default(),
+            });
+        let delegate_burn = Instructions::DelegateBurnV1;
+        #[cfg(feature = "transfer")]
+        let transfer_and_freeze =
+            Instructions::TransferAndFreezeV1(TransferAndFreezeV1InstructionData {
+                transfer: TransferNftV1InstructionData {
+                    delegated_authority: false,
+                    recipient_allow_list: None,
+                    require_owner_signature: None,
+                },
+            });
+        #[cfg(not(feature = "transfer"))]
+        let transfer_and_freeze = Instructions::TransferAndFreezeV1;
+        #[cfg(feature = "transfer")]
+        let thaw_and_transfer = Instructions::ThawAndTransferV1(ThawAndTransferV1InstructionData {
+            transfer: TransferNftV1InstructionData {
+                delegated_authority: false,
+                recipient_allow_list: None,
+                require_owner_signature: None,
+            },
+        });
+        #[cfg(not(feature = "transfer"))]
+        let thaw_and_transfer = Instructions::ThawAndTransferV1;
+        #[cfg(feature = "update")]
+        let update_batch =
+            Instructions::UpdateNftBatchV1(UpdateNftBatchV1InstructionData { updates: vec![] });
+        #[cfg(not(feature = "update"))]
+        let update_batch = Instructions::UpdateNftBatchV1;
+        #[cfg(feature = "update")]
+        let add_to_collection = Instructions::AddToCollectionV1(AddToCollectionV1InstructionData {
+            delegated_authority: false,
+        });
+        #[cfg(not(feature = "update"))]
+        let add_to_collection = Instructions::AddToCollectionV1;
+        #[cfg(feature = "update")]
+        let remove_from_collection =
+            Instructions::RemoveFromCollectionV1(RemoveFromCollectionV1InstructionData {
+                new_update_authority: solana_program::pubkey::Pubkey::default(),
+                delegated_authority: false,
+            });
+        #[cfg(not(feature = "update"))]
+        let remove_from_collection = Instructions::RemoveFromCollectionV1;
+
+        vec![
+            create,
+            update,
+            transfer,
+            create_v2,
+            execute,
+            get_asset_info,
+            transfer_collection_authority,
+            transfer_batch,
+            add_autograph,
+            create_collection_v2,
+            update_attributes,
+            remove_plugin,
+            update_royalty_ruleset,
+            add_plugin,
+            create_nft_with_plugin,
+            burn_and_refund,
+            transfer_with_provenance,
+            revoke_all_plugin_authorities,
+            update_external_plugin_adapter,
+            update_plugins_batch,
+            set_permanent_burn_delegate,
+            delegate_burn,
+            transfer_and_freeze,
+            thaw_and_transfer,
+            update_batch,
+            add_to_collection,
+            remove_from_collection,
+        ]
+    }
+
+    /// Fails to compile when a variant is added without being listed here.
+    fn variant_index(instruction: &Instructions) -> u8 {
+        match instruction {
+            #[cfg(feature = "create")]
+            Instructions::CreateNftV1(_) => discriminant::CREATE_NFT_V1,
+            #[cfg(not(feature = "create"))]
+            Instructions::CreateNftV1 => discriminant::CREATE_NFT_V1,
+            #[cfg(feature = "update")]
+            Instructions::UpdateNftV1(_) => discriminant::UPDATE_NFT_V1,
+            #[cfg(not(feature = "update"))]
+            Instructions::UpdateNftV1 => discriminant::UPDATE_NFT_V1,
+            #[cfg(feature = "transfer")]
+            Instructions::TransferNftV1(_) => discriminant::TRANSFER_NFT_V1,
+            #[cfg(not(feature = "transfer"))]
+            Instructions::TransferNftV1 => discriminant::TRANSFER_NFT_V1,
+            #[cfg(feature = "create")]
+            Instructions::CreateNftV2(_) => discriminant::CREATE_NFT_V2,
+            #[cfg(not(feature = "create"))]
+            Instructions::CreateNftV2 => discriminant::CREATE_NFT_V2,
+            Instructions::ExecuteNftV1(_) => discriminant::EXECUTE_NFT_V1,
+            Instructions::GetAssetInfoV1 => discriminant::GET_ASSET_INFO_V1,
+            Instructions::TransferCollectionAuthorityV1(_) => {
+                discriminant::TRANSFER_COLLECTION_AUTHORITY_V1
+            }
+            #[cfg(feature = "transfer")]
+            Instructions::TransferNftBatchV1(_) => discriminant::TRANSFER_NFT_BATCH_V1,
+            #[cfg(not(feature = "transfer"))]
+            Instructions::TransferNftBatchV1 => discriminant::TRANSFER_NFT_BATCH_V1,
+            Instructions::AddAutographV1(_) => discriminant::ADD_AUTOGRAPH_V1,
+            #[cfg(feature = "create")]
+            Instructions::CreateCollectionV2(_) => discriminant::CREATE_COLLECTION_V2,
+            #[cfg(not(feature = "create"))]
+            Instructions::CreateCollectionV2 => discriminant::CREATE_COLLECTION_V2,
+            Instructions::UpdateAttributesV1(_) => discriminant::UPDATE_ATTRIBUTES_V1,
+            Instructions::RemovePluginV1(_) => discriminant::REMOVE_PLUGIN_V1,
+            Instructions::UpdateRoyaltyRulesetV1(_) => discriminant::UPDATE_ROYALTY_RULESET_V1,
+            Instructions::AddPluginV1(_) => discriminant::ADD_PLUGIN_V1,
+            #[cfg(feature = "create")]
+            Instructions::CreateNftWithPluginV1(_) => discriminant::CREATE_NFT_WITH_PLUGIN_V1,
+            #[cfg(not(feature = "create"))]
+            Instructions::CreateNftWithPluginV1 => discriminant::CREATE_NFT_WITH_PLUGIN_V1,
+            Instructions::BurnAndRefundV1(_) => discriminant::BURN_AND_REFUND_V1,
+            #[cfg(feature = "transfer")]
+            Instructions::TransferWithProvenanceV1(_) => discriminant::TRANSFER_WITH_PROVENANCE_V1,
+            #[cfg(not(feature = "transfer"))]
+            Instructions::TransferWithProvenanceV1 => discriminant::TRANSFER_WITH_PROVENANCE_V1,
+            Instructions::RevokeAllPluginAuthoritiesV1 => {
+                discriminant::REVOKE_ALL_PLUGIN_AUTHORITIES_V1
+            }
+            Instructions::UpdateExternalPluginAdapterV1(_) => {
+                discriminant::UPDATE_EXTERNAL_PLUGIN_ADAPTER_V1
+            }
+            Instructions::UpdatePluginsBatchV1(_) => discriminant::UPDATE_PLUGINS_BATCH_V1,
+            Instructions::SetPermanentBurnDelegateV1(_) => {
+                discriminant::SET_PERMANENT_BURN_DELEGATE_V1
+            }
+            Instructions::DelegateBurnV1 => discriminant::DELEGATE_BURN_V1,
+            #[cfg(feature = "transfer")]
+            Instructions::TransferAndFreezeV1(_) => discriminant::TRANSFER_AND_FREEZE_V1,
+            #[cfg(not(feature = "transfer"))]
+            Instructions::TransferAndFreezeV1 => discriminant::TRANSFER_AND_FREEZE_V1,
+            #[cfg(feature = "transfer")]
+            Instructions::ThawAndTransferV1(_) => discriminant::THAW_AND_TRANSFER_V1,
+            #[cfg(not(feature = "transfer"))]
+            Instructions::ThawAndTransferV1 => discriminant::THAW_AND_TRANSFER_V1,
+            #[cfg(feature = "update")]
+            Instructions::UpdateNftBatchV1(_) => discriminant::UPDATE_NFT_BATCH_V1,
+            #[cfg(not(feature = "update"))]
+            Instructions::UpdateNftBatchV1 => discriminant::UPDATE_NFT_BATCH_V1,
+            #[cfg(feature = "update")]
+            Instructions::AddToCollectionV1(_) => discriminant::ADD_TO_COLLECTION_V1,
+            #[cfg(not(feature = "update"))]
+            Instructions::AddToCollectionV1 => discriminant::ADD_TO_COLLECTION_V1,
+            #[cfg(feature = "update")]
+            Instructions::RemoveFromCollectionV1(_) => discriminant::REMOVE_FROM_COLLECTION_V1,
+            #[cfg(not(feature = "update"))]
+            Instructions::RemoveFromCollectionV1 => discriminant::REMOVE_FROM_COLLECTION_V1,
+        }
+    }
+
+    #[test]
+    fn test_instructions_discriminants() {
+        for (index, instruction) in all_instructions().iter().enumerate() {
+            assert_eq!(variant_index(instruction) as usize, index);
+            assert_eq!(
+                instruction.try_to_vec().unwrap()[0],
+                variant_index(instruction)
+            );
+        }
+    }
+
+    #[test]
+    fn test_discriminant_constants_match_borsh_positions() {
+        let serialized = all_instructions()
+            .iter()
+            .map(|instruction| instruction.try_to_vec().unwrap()[0])
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            serialized,
+            vec![
+                discriminant::CREATE_NFT_V1,
+                discriminant::UPDATE_NFT_V1,
+                discriminant::TRANSFER_NFT_V1,
+                discriminant::CREATE_NFT_V2,
+                discriminant::EXECUTE_NFT_V1,
+                discriminant::GET_ASSET_INFO_V1,
+                discriminant::TRANSFER_COLLECTION_AUTHORITY_V1,
+                discriminant::TRANSFER_NFT_BATCH_V1,
+                discriminant::ADD_AUTOGRAPH_V1,
+                discriminant::CREATE_COLLECTION_V2,
+                discriminant::UPDATE_ATTRIBUTES_V1,
+                discriminant::REMOVE_PLUGIN_V1,
+                discriminant::UPDATE_ROYALTY_RULESET_V1,
+                discriminant::ADD_PLUGIN_V1,
+                discriminant::CREATE_NFT_WITH_PLUGIN_V1,
+                discriminant::BURN_AND_REFUND_V1,
+                discriminant::TRANSFER_WITH_PROVENANCE_V1,
+                discriminant::REVOKE_ALL_PLUGIN_AUTHORITIES_V1,
+                discriminant::UPDATE_EXTERNAL_PLUGIN_ADAPTER_V1,
+                discriminant::UPDATE_PLUGINS_BATCH_V1,
+                discriminant::SET_PERMANENT_BURN_DELEGATE_V1,
+                discriminant::DELEGATE_BURN_V1,
+                discriminant::TRANSFER_AND_FREEZE_V1,
+                discriminant::THAW_AND_TRANSFER_V1,
+                discriminant::UPDATE_NFT_BATCH_V1,
+                discriminant::ADD_TO_COLLECTION_V1,
+                discriminant::REMOVE_FROM_COLLECTION_V1,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dispatch_table_covers_every_variant() {
+        let instructions = all_instructions();
+        assert_eq!(DISPATCH_TABLE.len(), instructions.len());
+
+        for instruction in instructions {
+            match DISPATCH_TABLE[variant_index(&instruction) as usize] {
+                Some(handler) => {
+                    let res = handler(&[], instruction);
+                    assert_ne!(res, Err(ProgramError::InvalidInstructionData));
+                }
+                None => assert_eq!(
+                    dispatch(&[], &instruction.try_to_vec().unwrap()),
+                    Err(ProgramError::InvalidInstructionData)
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_dispatch_too_few_accounts() {
+        for instruction in all_instructions() {
+            let index = variant_index(&instruction);
+            if DISPATCH_TABLE[index as usize].is_none() {
+                continue;
+            }
+
+            let min_accounts = min_accounts(index);
+            assert!(min_accounts > 0, "no minimum for instruction {}", index);
+
+            let accounts = vec![absent_account(); min_accounts - 1];
+            assert_eq!(
+                dispatch(&accounts, &instruction.try_to_vec().unwrap()),
+                Err(ProgramError::NotEnoughAccountKeys),
+                "instruction {}",
+                index
+            );
+
+            // The handler's own account parsing must not need more.
+            let accounts = vec![absent_account(); min_accounts];
+            assert_ne!(
+                DISPATCH_TABLE[index as usize].unwrap()(&accounts, instruction),
+                Err(ProgramError::NotEnoughAccountKeys),
+                "instruction {}",
+                index
+            );
+        }
+    }
+
+    #[cfg(all(feature = "create", feature = "update"))]
+    #[test]
+    fn test_dispatch_rejects_mismatched_variant() {
+        let update = all_instructions().swap_remove(discriminant::UPDATE_NFT_V1 as usize);
+        assert_eq!(
+            DISPATCH_TABLE[discriminant::CREATE_NFT_V1 as usize].unwrap()(&[], update),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_dispatch_rejects_unknown_variant() {
+        assert!(dispatch(&[], &[DISPATCH_TABLE.len() as u8]).is_err());
+    }
+
+    #[cfg(all(feature = "create", not(feature = "update"), not(feature = "transfer")))]
+    #[test]
+    fn test_create_only_dispatch() {
+        // UpdateNftV1 with all eight of its optional fields unset.
+        let data = [discriminant::UPDATE_NFT_V1, 0, 0, 0, 0, 0, 0, 0, 0];
+
+        assert!(DISPATCH_TABLE[discriminant::CREATE_NFT_V1 as usize].is_some());
+        assert!(DISPATCH_TABLE[discriminant::UPDATE_NFT_V1 as usize].is_none());
+        assert_eq!(
+            dispatch(&[], &data),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
 }
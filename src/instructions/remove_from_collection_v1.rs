@@ -0,0 +1,379 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    instructions::UpdateV2CpiBuilder,
+    types::{Key, UpdateAuthority},
+};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+#[cfg(feature = "ledger-state")]
+use crate::utils::is_ledger_state;
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::Instructions,
+    utils::{
+        assert_account_key, invoke_cpi, resolve_authority, AccountCheck, MinDataLen,
+        MplCoreAccount, NonDefaultKey, NonWritable, NotProgramAccount, OptionalAccountCheck,
+        ProcessInstruction, SignerAccount, SystemAccount, ToOptionalAccount, WritableAccount,
+        BASE_ASSET_MIN_LEN, BASE_COLLECTION_MIN_LEN,
+    },
+};
+
+#[derive(Debug)]
+pub struct RemoveFromCollectionV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    /// Collection the asset leaves.
+    pub collection: &'a AccountInfo<'info>,
+    /// Update authority of the collection.
+    pub collection_authority: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for RemoveFromCollectionV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, collection_authority, payer, system_program, log_wrapper, mpl_core] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(1, collection)?;
+        NotProgramAccount::check_at(1, collection)?;
+        NonDefaultKey::check_at(3, payer)?;
+        NotProgramAccount::check_at(3, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_at(1, collection)?;
+        WritableAccount::check_at(3, payer)?;
+        SignerAccount::check_at(3, payer)?;
+        SystemAccount::check_at(4, system_program)?;
+        NonWritable::check_optional_at(5, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(6, mpl_core)?;
+
+        // mpl_core only updates a compressed asset after it is decompressed.
+        #[cfg(feature = "reject-compressed")]
+        if is_ledger_state(asset) {
+            return Err(MplCoreWrapperError::AssetIsCompressed.into());
+        }
+
+        #[cfg(feature = "ledger-state")]
+        if is_ledger_state(asset) && log_wrapper.to_optional().is_none() {
+            return Err(MplCoreWrapperError::MissingLogWrapper.into());
+        }
+
+        Ok(Self {
+            asset,
+            collection,
+            collection_authority,
+            payer,
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for RemoveFromCollectionV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            account_meta(self.collection, false, true),
+            account_meta(self.collection_authority, true, false),
+            account_meta(self.payer, true, true),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+impl<'a, 'info> RemoveFromCollectionV1Accounts<'a, 'info> {
+    /// Checks the asset currently belongs to `collection`.
+    pub fn check_asset_collection(&self) -> ProgramResult {
+        MinDataLen::check(self.asset, BASE_ASSET_MIN_LEN)?;
+        assert_account_key(self.asset, Key::AssetV1)?;
+        let asset =
+            BaseAssetV1::try_from(self.asset).map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if asset.update_authority != UpdateAuthority::Collection(*self.collection.key) {
+            return Err(MplCoreWrapperError::AssetNotInCollection.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks `collection_authority` is the collection's update authority,
+    /// which mpl_core requires to update an asset of the collection.
+    pub fn check_collection_authority(&self) -> ProgramResult {
+        MinDataLen::check(self.collection, BASE_COLLECTION_MIN_LEN)?;
+        assert_account_key(self.collection, Key::CollectionV1)?;
+        let collection = BaseCollectionV1::try_from(self.collection)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if self.collection_authority.key != &collection.update_authority {
+            return Err(MplCoreWrapperError::CollectionAuthorityRequired.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct RemoveFromCollectionV1InstructionData {
+    /// Update authority the asset takes once it leaves the collection.
+    pub new_update_authority: Pubkey,
+    /// When `true`, `collection_authority` is the payer's delegated authority
+    /// PDA of this program, which the program signs for.
+    pub delegated_authority: bool,
+}
+
+#[derive(Debug)]
+pub struct RemoveFromCollectionV1<'a, 'info> {
+    pub accounts: RemoveFromCollectionV1Accounts<'a, 'info>,
+    pub instruction_data: RemoveFromCollectionV1InstructionData,
+    /// Seeds, bump included, the program signs for `collection_authority` with.
+    pub authority_seeds: Option<Vec<Vec<u8>>>,
+}
+
+impl<'a, 'info>
+    TryFrom<(
+        &'a [AccountInfo<'info>],
+        RemoveFromCollectionV1InstructionData,
+    )> for RemoveFromCollectionV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (
+            &'a [AccountInfo<'info>],
+            RemoveFromCollectionV1InstructionData,
+        ),
+    ) -> Result<Self, Self::Error> {
+        let accounts = RemoveFromCollectionV1Accounts::try_from(accounts)?;
+        let authority_seeds = resolve_authority(
+            Some(accounts.collection_authority),
+            accounts.payer,
+            instruction_data.delegated_authority,
+        )?;
+        accounts.check_asset_collection()?;
+        accounts.check_collection_authority()?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            authority_seeds,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for RemoveFromCollectionV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        let mut update_cpi = UpdateV2CpiBuilder::new(self.accounts.mpl_core);
+
+        update_cpi
+            .asset(self.accounts.asset)
+            .collection(Some(self.accounts.collection))
+            .authority(Some(self.accounts.collection_authority))
+            .payer(self.accounts.payer)
+            .system_program(self.accounts.system_program)
+            .log_wrapper(self.accounts.log_wrapper)
+            .new_update_authority(UpdateAuthority::Address(
+                self.instruction_data.new_update_authority,
+            ));
+
+        invoke_cpi("UpdateV2", self.accounts.asset.key, || {
+            match self.authority_seeds {
+                Some(seeds) => {
+                    let seeds = seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
+                    update_cpi.invoke_signed(&[&seeds])
+                }
+                None => update_cpi.invoke(),
+            }
+        })?;
+
+        Ok(())
+    }
+}
+
+pub fn process_remove_from_collection_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::RemoveFromCollectionV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    RemoveFromCollectionV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{derive_delegated_authority, test_utils::*};
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(collection_authority: Pubkey) -> Vec<AccountInfo<'static>> {
+        let collection = new_collection_account(collection_authority);
+
+        vec![
+            new_asset_account(
+                Pubkey::new_unique(),
+                UpdateAuthority::Collection(*collection.key),
+                &[],
+            ),
+            collection,
+            new_test_account(collection_authority, true, false, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+            absent_account(),
+            new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+        ]
+    }
+
+    fn new_data() -> RemoveFromCollectionV1InstructionData {
+        RemoveFromCollectionV1InstructionData {
+            new_update_authority: Pubkey::new_unique(),
+            delegated_authority: false,
+        }
+    }
+
+    #[test]
+    fn test_remove_from_collection_success() {
+        let accounts = new_accounts(Pubkey::new_unique());
+
+        let instruction = RemoveFromCollectionV1::try_from((accounts.as_slice(), new_data()));
+        assert!(
+            instruction.is_ok(),
+            "expected Ok, but got Err: {:?}",
+            instruction
+        );
+
+        let res = instruction.unwrap().process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_remove_from_collection_authority_not_signer() {
+        let collection_authority = Pubkey::new_unique();
+        let mut accounts = new_accounts(collection_authority);
+        accounts[2] =
+            new_test_account(collection_authority, false, false, 1, 0, system_program::ID);
+
+        let res = RemoveFromCollectionV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[test]
+    fn test_remove_from_collection_wrong_authority() {
+        let mut accounts = new_accounts(Pubkey::new_unique());
+        accounts[2] = new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+
+        let res = RemoveFromCollectionV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::CollectionAuthorityRequired.into()
+        );
+    }
+
+    #[test]
+    fn test_remove_from_collection_not_in_collection() {
+        let mut accounts = new_accounts(Pubkey::new_unique());
+        accounts[0] = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::Address(Pubkey::new_unique()),
+            &[],
+        );
+
+        let res = RemoveFromCollectionV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AssetNotInCollection.into()
+        );
+    }
+
+    #[test]
+    fn test_remove_from_collection_other_collection() {
+        let mut accounts = new_accounts(Pubkey::new_unique());
+        accounts[0] = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::Collection(Pubkey::new_unique()),
+            &[],
+        );
+
+        let res = RemoveFromCollectionV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AssetNotInCollection.into()
+        );
+    }
+
+    fn remove_as_delegated_authority(
+        controller: Pubkey,
+        payer: AccountInfo<'static>,
+    ) -> ProgramResult {
+        let (pda, _) = derive_delegated_authority(&controller);
+        let mut accounts = new_accounts(pda);
+        accounts[2] = new_test_account(pda, false, false, 1, 0, system_program::ID);
+        accounts[3] = payer;
+
+        RemoveFromCollectionV1::try_from((
+            accounts.as_slice(),
+            RemoveFromCollectionV1InstructionData {
+                delegated_authority: true,
+                ..new_data()
+            },
+        ))?
+        .process()
+    }
+
+    #[test]
+    fn test_remove_from_collection_signed_by_delegated_authority() {
+        let controller = Pubkey::new_unique();
+        let payer = new_test_account(controller, true, true, 1, 0, system_program::ID);
+
+        take_recorded_cpis();
+        let res = remove_as_delegated_authority(controller, payer);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["UpdateV2"]);
+    }
+
+    #[test]
+    fn test_remove_from_collection_delegated_authority_of_other_controller() {
+        let caller = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+
+        take_recorded_cpis();
+        let res = remove_as_delegated_authority(Pubkey::new_unique(), caller);
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidSignerSeeds.into()
+        );
+        assert!(take_recorded_cpis().is_empty());
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_remove_from_collection_account_metas() {
+        let accounts = new_accounts(Pubkey::new_unique());
+        let metas = RemoveFromCollectionV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[2], |accounts| {
+            RemoveFromCollectionV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
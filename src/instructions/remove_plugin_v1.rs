@@ -0,0 +1,245 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{instructions::RemovePluginV1CpiBuilder, types::PluginType};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+#[cfg(feature = "asset-state")]
+use crate::utils::check_collection_account;
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    instructions::Instructions,
+    plugins::check_plugin_removable,
+    utils::{
+        invoke_cpi, AccountCheck, MplCoreAccount, NonDefaultKey, NonWritable, NotProgramAccount,
+        OptionalAccountCheck, ProcessInstruction, SignerAccount, SystemAccount, ToOptionalAccount,
+        WritableAccount,
+    },
+};
+
+#[derive(Debug)]
+pub struct RemovePluginV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub authority: Option<&'a AccountInfo<'info>>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for RemovePluginV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, payer, authority, system_program, log_wrapper, mpl_core] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(2, payer)?;
+        NotProgramAccount::check_at(2, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        WritableAccount::check_at(2, payer)?;
+        SignerAccount::check_at(2, payer)?;
+        SignerAccount::check_optional_at(3, authority.to_optional())?;
+        SystemAccount::check_at(4, system_program)?;
+        NonWritable::check_optional_at(5, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(6, mpl_core)?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            payer,
+            authority: authority.to_optional(),
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for RemovePluginV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            account_meta(self.payer, true, true),
+            optional_account_meta(self.authority, true, false),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct RemovePluginV1InstructionData {
+    pub plugin_type: PluginType,
+}
+
+#[derive(Debug)]
+pub struct RemovePluginV1<'a, 'info> {
+    pub accounts: RemovePluginV1Accounts<'a, 'info>,
+    pub instruction_data: RemovePluginV1InstructionData,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], RemovePluginV1InstructionData)>
+    for RemovePluginV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], RemovePluginV1InstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = RemovePluginV1Accounts::try_from(accounts)?;
+        #[cfg(feature = "asset-state")]
+        check_collection_account(accounts.asset, accounts.collection)?;
+        check_plugin_removable(&instruction_data.plugin_type)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for RemovePluginV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        invoke_cpi("RemovePluginV1", self.accounts.asset.key, || {
+            RemovePluginV1CpiBuilder::new(self.accounts.mpl_core)
+                .asset(self.accounts.asset)
+                .collection(self.accounts.collection)
+                .payer(self.accounts.payer)
+                .authority(self.accounts.authority)
+                .system_program(self.accounts.system_program)
+                .log_wrapper(self.accounts.log_wrapper)
+                .plugin_type(self.instruction_data.plugin_type)
+                .invoke()
+        })?;
+
+        Ok(())
+    }
+}
+
+pub fn process_remove_plugin_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::RemovePluginV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    RemovePluginV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::MplCoreWrapperError, utils::test_utils::*};
+    use mpl_core::types::UpdateAuthority;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts() -> Vec<AccountInfo<'static>> {
+        let asset = new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]);
+        let collection =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let authority =
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        let system_program =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let log_wrapper =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        vec![
+            asset,
+            collection,
+            payer,
+            authority,
+            system_program,
+            log_wrapper,
+            mpl_core,
+        ]
+    }
+
+    #[test]
+    fn test_remove_plugin_permanent_rejected() {
+        let accounts = new_accounts();
+
+        for plugin_type in [
+            PluginType::PermanentFreezeDelegate,
+            PluginType::PermanentTransferDelegate,
+            PluginType::PermanentBurnDelegate,
+        ] {
+            let res = RemovePluginV1::try_from((
+                accounts.as_slice(),
+                RemovePluginV1InstructionData { plugin_type },
+            ));
+            assert_eq!(
+                res.unwrap_err(),
+                MplCoreWrapperError::CannotRemovePermanentPlugin.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_remove_plugin_add_blocker_rejected() {
+        let accounts = new_accounts();
+        let res = RemovePluginV1::try_from((
+            accounts.as_slice(),
+            RemovePluginV1InstructionData {
+                plugin_type: PluginType::AddBlocker,
+            },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::PluginAdditionsBlocked.into()
+        );
+    }
+
+    #[test]
+    fn test_remove_plugin_allowed() {
+        let accounts = new_accounts();
+
+        let instruction = RemovePluginV1::try_from((
+            accounts.as_slice(),
+            RemovePluginV1InstructionData {
+                plugin_type: PluginType::FreezeDelegate,
+            },
+        ))
+        .unwrap();
+
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_remove_plugin_account_not_enough_accounts() {
+        let accounts = vec![];
+        let res = RemovePluginV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_remove_plugin_account_metas() {
+        let accounts = new_accounts();
+        let metas = RemovePluginV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            RemovePluginV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
@@ -0,0 +1,292 @@
+use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_plugins,
+    instructions::RevokePluginAuthorityV1CpiBuilder,
+    types::{PluginAuthority, PluginType},
+    DataBlob,
+};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+#[cfg(feature = "asset-state")]
+use crate::utils::check_collection_account;
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::Instructions,
+    utils::{
+        invoke_cpi, AccountCheck, MinDataLen, MplCoreAccount, NonDefaultKey, NonWritable,
+        NotProgramAccount, OptionalAccountCheck, ProcessInstruction, SignerAccount, SystemAccount,
+        ToOptionalAccount, WritableAccount, BASE_ASSET_MIN_LEN,
+    },
+};
+
+/// Most delegated plugins revoked by one instruction, keeping its CPI count
+/// within the compute budget.
+pub const MAX_REVOKED_PLUGINS: usize = 8;
+
+#[derive(Debug)]
+pub struct RevokeAllPluginAuthoritiesV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub authority: Option<&'a AccountInfo<'info>>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]>
+    for RevokeAllPluginAuthoritiesV1Accounts<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, payer, authority, system_program, log_wrapper, mpl_core] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(2, payer)?;
+        NotProgramAccount::check_at(2, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        WritableAccount::check_at(2, payer)?;
+        SignerAccount::check_at(2, payer)?;
+        SignerAccount::check_optional_at(3, authority.to_optional())?;
+        SystemAccount::check_at(4, system_program)?;
+        NonWritable::check_optional_at(5, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(6, mpl_core)?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            payer,
+            authority: authority.to_optional(),
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for RevokeAllPluginAuthoritiesV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            account_meta(self.payer, true, true),
+            optional_account_meta(self.authority, true, false),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+#[derive(Debug)]
+pub struct RevokeAllPluginAuthoritiesV1<'a, 'info> {
+    pub accounts: RevokeAllPluginAuthoritiesV1Accounts<'a, 'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for RevokeAllPluginAuthoritiesV1<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let accounts = RevokeAllPluginAuthoritiesV1Accounts::try_from(accounts)?;
+        #[cfg(feature = "asset-state")]
+        check_collection_account(accounts.asset, accounts.collection)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a, 'info> RevokeAllPluginAuthoritiesV1<'a, 'info> {
+    /// Plugins whose authority was delegated to an address, in registry order.
+    /// Revoking hands each back to its manager (owner or update authority).
+    pub fn delegated_plugins(&self) -> Result<Vec<PluginType>, ProgramError> {
+        let asset = self.accounts.asset;
+        MinDataLen::check(asset, BASE_ASSET_MIN_LEN)?;
+
+        let data = asset.try_borrow_data()?;
+        let asset_len = BaseAssetV1::from_bytes(&data)
+            .map_err(|_| ProgramError::InvalidAccountData)?
+            .len();
+        if data.len() <= asset_len {
+            return Ok(vec![]);
+        }
+
+        let delegated = fetch_plugins(&data)
+            .map_err(|_| ProgramError::InvalidAccountData)?
+            .into_iter()
+            .filter(|record| matches!(record.authority, PluginAuthority::Address { .. }))
+            .map(|record| record.plugin_type)
+            .collect::<Vec<_>>();
+
+        if delegated.len() > MAX_REVOKED_PLUGINS {
+            return Err(MplCoreWrapperError::TooManyPlugins.into());
+        }
+
+        Ok(delegated)
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for RevokeAllPluginAuthoritiesV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        for plugin_type in self.delegated_plugins()? {
+            invoke_cpi("RevokePluginAuthorityV1", self.accounts.asset.key, || {
+                RevokePluginAuthorityV1CpiBuilder::new(self.accounts.mpl_core)
+                    .asset(self.accounts.asset)
+                    .collection(self.accounts.collection)
+                    .payer(self.accounts.payer)
+                    .authority(self.accounts.authority)
+                    .system_program(self.accounts.system_program)
+                    .log_wrapper(self.accounts.log_wrapper)
+                    .plugin_type(plugin_type)
+                    .invoke()
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn process_revoke_all_plugin_authorities_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::RevokeAllPluginAuthoritiesV1 = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    RevokeAllPluginAuthoritiesV1::try_from(accounts)?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::{
+        BurnDelegate, FreezeDelegate, Plugin, TransferDelegate, UpdateAuthority,
+    };
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(plugins: &[(Plugin, PluginAuthority)]) -> Vec<AccountInfo<'static>> {
+        let mut asset = new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, plugins);
+        asset.is_writable = true;
+        let collection =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let authority =
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        let system_program =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let log_wrapper =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        vec![
+            asset,
+            collection,
+            payer,
+            authority,
+            system_program,
+            log_wrapper,
+            mpl_core,
+        ]
+    }
+
+    fn delegate() -> PluginAuthority {
+        PluginAuthority::Address {
+            address: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_revoke_all_plugin_authorities_two_delegated() {
+        let accounts = new_accounts(&[
+            (
+                Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                delegate(),
+            ),
+            (
+                Plugin::BurnDelegate(BurnDelegate {}),
+                PluginAuthority::Owner,
+            ),
+            (Plugin::TransferDelegate(TransferDelegate {}), delegate()),
+        ]);
+        let instruction = RevokeAllPluginAuthoritiesV1::try_from(accounts.as_slice()).unwrap();
+
+        assert_eq!(
+            instruction.delegated_plugins().unwrap(),
+            vec![PluginType::FreezeDelegate, PluginType::TransferDelegate]
+        );
+
+        take_recorded_cpis();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(
+            take_recorded_cpis(),
+            vec!["RevokePluginAuthorityV1", "RevokePluginAuthorityV1"]
+        );
+    }
+
+    #[test]
+    fn test_revoke_all_plugin_authorities_no_plugins() {
+        let accounts = new_accounts(&[]);
+        let instruction = RevokeAllPluginAuthoritiesV1::try_from(accounts.as_slice()).unwrap();
+
+        take_recorded_cpis();
+        assert_eq!(instruction.process(), Ok(()));
+        assert!(take_recorded_cpis().is_empty());
+    }
+
+    #[test]
+    fn test_revoke_all_plugin_authorities_too_many() {
+        let plugins = (0..=MAX_REVOKED_PLUGINS)
+            .map(|_| {
+                (
+                    Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                    delegate(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let accounts = new_accounts(&plugins);
+        let instruction = RevokeAllPluginAuthoritiesV1::try_from(accounts.as_slice()).unwrap();
+
+        take_recorded_cpis();
+        assert_eq!(
+            instruction.process(),
+            Err(MplCoreWrapperError::TooManyPlugins.into())
+        );
+        assert!(take_recorded_cpis().is_empty());
+    }
+
+    #[test]
+    fn test_revoke_all_plugin_authorities_account_not_enough_accounts() {
+        let accounts = vec![];
+        let res = RevokeAllPluginAuthoritiesV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_revoke_all_plugin_authorities_account_metas() {
+        let accounts = new_accounts(&[]);
+        let metas = RevokeAllPluginAuthoritiesV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            RevokeAllPluginAuthoritiesV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
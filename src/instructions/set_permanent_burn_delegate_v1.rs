@@ -0,0 +1,335 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    accounts::BaseAssetV1,
+    instructions::AddPluginV1CpiBuilder,
+    types::{Key, PermanentBurnDelegate, Plugin, PluginAuthority, PluginAuthorityPair},
+};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+#[cfg(feature = "asset-state")]
+use crate::utils::check_collection_account;
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::Instructions,
+    plugins::{build_plugin_pair, check_plugin_additions_allowed, is_plugin_authority},
+    utils::{
+        assert_account_key, invoke_cpi, AccountCheck, MinDataLen, MplCoreAccount, NonDefaultKey,
+        NonWritable, NotProgramAccount, OptionalAccountCheck, ProcessInstruction, SignerAccount,
+        SystemAccount, ToOptionalAccount, WritableAccount, BASE_ASSET_MIN_LEN,
+    },
+};
+
+#[derive(Debug)]
+pub struct SetPermanentBurnDelegateV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub update_authority: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]>
+    for SetPermanentBurnDelegateV1Accounts<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, payer, update_authority, system_program, log_wrapper, mpl_core] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(2, payer)?;
+        NotProgramAccount::check_at(2, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        WritableAccount::check_at(2, payer)?;
+        SignerAccount::check_at(2, payer)?;
+        SignerAccount::check_at(3, update_authority)?;
+        SystemAccount::check_at(4, system_program)?;
+        NonWritable::check_optional_at(5, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(6, mpl_core)?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            payer,
+            update_authority,
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for SetPermanentBurnDelegateV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            account_meta(self.payer, true, true),
+            account_meta(self.update_authority, true, false),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+impl<'a, 'info> SetPermanentBurnDelegateV1Accounts<'a, 'info> {
+    /// mpl_core only lets the update authority manage permanent delegates.
+    /// Assets managed by a collection are left for mpl_core to check.
+    pub fn check_update_authority(&self) -> ProgramResult {
+        MinDataLen::check(self.asset, BASE_ASSET_MIN_LEN)?;
+        assert_account_key(self.asset, Key::AssetV1)?;
+        let asset =
+            BaseAssetV1::try_from(self.asset).map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if self.collection.is_none()
+            && !is_plugin_authority(
+                &PluginAuthority::UpdateAuthority,
+                self.update_authority.key,
+                &asset,
+                None,
+            )
+        {
+            return Err(MplCoreWrapperError::AuthorityMismatch.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SetPermanentBurnDelegateV1InstructionData {
+    /// Address allowed to burn the asset at any time, e.g. a ticket program.
+    pub delegate: Pubkey,
+}
+
+#[derive(Debug)]
+pub struct SetPermanentBurnDelegateV1<'a, 'info> {
+    pub accounts: SetPermanentBurnDelegateV1Accounts<'a, 'info>,
+    pub instruction_data: SetPermanentBurnDelegateV1InstructionData,
+}
+
+impl<'a, 'info>
+    TryFrom<(
+        &'a [AccountInfo<'info>],
+        SetPermanentBurnDelegateV1InstructionData,
+    )> for SetPermanentBurnDelegateV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (
+            &'a [AccountInfo<'info>],
+            SetPermanentBurnDelegateV1InstructionData,
+        ),
+    ) -> Result<Self, Self::Error> {
+        let accounts = SetPermanentBurnDelegateV1Accounts::try_from(accounts)?;
+        #[cfg(feature = "asset-state")]
+        check_collection_account(accounts.asset, accounts.collection)?;
+        accounts.check_update_authority()?;
+        check_plugin_additions_allowed(accounts.asset)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a, 'info> SetPermanentBurnDelegateV1<'a, 'info> {
+    pub fn plugin_pair(&self) -> Result<PluginAuthorityPair, ProgramError> {
+        build_plugin_pair(
+            Plugin::PermanentBurnDelegate(PermanentBurnDelegate {}),
+            Some(PluginAuthority::Address {
+                address: self.instruction_data.delegate,
+            }),
+        )
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for SetPermanentBurnDelegateV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        let PluginAuthorityPair { plugin, authority } = self.plugin_pair()?;
+
+        let mut add_plugin_cpi = AddPluginV1CpiBuilder::new(self.accounts.mpl_core);
+
+        add_plugin_cpi
+            .asset(self.accounts.asset)
+            .collection(self.accounts.collection)
+            .payer(self.accounts.payer)
+            .authority(Some(self.accounts.update_authority))
+            .system_program(self.accounts.system_program)
+            .log_wrapper(self.accounts.log_wrapper)
+            .plugin(plugin);
+
+        if let Some(authority) = authority {
+            add_plugin_cpi.init_authority(authority);
+        }
+
+        invoke_cpi("AddPluginV1", self.accounts.asset.key, || {
+            add_plugin_cpi.invoke()
+        })
+    }
+}
+
+pub fn process_set_permanent_burn_delegate_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::SetPermanentBurnDelegateV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    SetPermanentBurnDelegateV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::UpdateAuthority;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(
+        asset: AccountInfo<'static>,
+        update_authority: AccountInfo<'static>,
+    ) -> Vec<AccountInfo<'static>> {
+        vec![
+            asset,
+            absent_account(),
+            new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+            update_authority,
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+            absent_account(),
+            new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+        ]
+    }
+
+    fn asset_with_update_authority(update_authority: Pubkey) -> AccountInfo<'static> {
+        new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::Address(update_authority),
+            &[],
+        )
+    }
+
+    #[test]
+    fn test_set_permanent_burn_delegate_sets_delegate_authority() {
+        let update_authority = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let accounts = new_accounts(
+            asset_with_update_authority(update_authority),
+            new_test_account(update_authority, true, false, 1, 0, system_program::ID),
+        );
+
+        let instruction = SetPermanentBurnDelegateV1::try_from((
+            accounts.as_slice(),
+            SetPermanentBurnDelegateV1InstructionData { delegate },
+        ))
+        .unwrap();
+
+        let pair = instruction.plugin_pair().unwrap();
+        assert_eq!(
+            pair.plugin,
+            Plugin::PermanentBurnDelegate(PermanentBurnDelegate {})
+        );
+        assert_eq!(
+            pair.authority,
+            Some(PluginAuthority::Address { address: delegate })
+        );
+
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_set_permanent_burn_delegate_update_authority_not_signer() {
+        let update_authority = Pubkey::new_unique();
+        let accounts = new_accounts(
+            asset_with_update_authority(update_authority),
+            new_test_account(update_authority, false, false, 1, 0, system_program::ID),
+        );
+
+        let res = SetPermanentBurnDelegateV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[test]
+    fn test_set_permanent_burn_delegate_wrong_update_authority() {
+        let accounts = new_accounts(
+            asset_with_update_authority(Pubkey::new_unique()),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
+
+        let res = SetPermanentBurnDelegateV1::try_from((
+            accounts.as_slice(),
+            SetPermanentBurnDelegateV1InstructionData {
+                delegate: Pubkey::new_unique(),
+            },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AuthorityMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_set_permanent_burn_delegate_wrong_mpl_core() {
+        let update_authority = Pubkey::new_unique();
+        let mut accounts = new_accounts(
+            asset_with_update_authority(update_authority),
+            new_test_account(update_authority, true, false, 1, 0, system_program::ID),
+        );
+        accounts[6] = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            1,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        let res = SetPermanentBurnDelegateV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountOwner);
+    }
+
+    #[test]
+    fn test_set_permanent_burn_delegate_not_enough_accounts() {
+        let accounts = vec![];
+        let res = SetPermanentBurnDelegateV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_set_permanent_burn_delegate_account_metas() {
+        let update_authority = Pubkey::new_unique();
+        let accounts = new_accounts(
+            asset_with_update_authority(update_authority),
+            new_test_account(update_authority, true, false, 1, 0, system_program::ID),
+        );
+        let metas = SetPermanentBurnDelegateV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            SetPermanentBurnDelegateV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
@@ -0,0 +1,204 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_asset_plugin,
+    instructions::UpdatePluginV1CpiBuilder,
+    types::{FreezeDelegate, Key, Plugin, PluginType},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::{Instructions, TransferNftV1, TransferNftV1InstructionData},
+    plugins::{is_plugin_authority, load_asset_collection},
+    utils::{
+        assert_account_key, invoke_cpi, AccountCheck, MinDataLen, ProcessInstruction,
+        SignerAccount, SystemAccount, BASE_ASSET_MIN_LEN,
+    },
+};
+
+/// Accounts are those of `TransferNftV1` followed by the freeze delegate
+/// authority, which must sign to thaw the asset before it is released to
+/// `new_owner`. `system_program` must be the system program, as mpl_core
+/// requires it to update the plugin.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ThawAndTransferV1InstructionData {
+    pub transfer: TransferNftV1InstructionData,
+}
+
+#[derive(Debug)]
+pub struct ThawAndTransferV1<'a, 'info> {
+    pub transfer: TransferNftV1<'a, 'info>,
+    /// Holder of the asset's FreezeDelegate plugin.
+    pub freeze_authority: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], ThawAndTransferV1InstructionData)>
+    for ThawAndTransferV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], ThawAndTransferV1InstructionData),
+    ) -> Result<Self, Self::Error> {
+        let [transfer_accounts @ .., freeze_authority] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let transfer = TransferNftV1::try_from((transfer_accounts, instruction_data.transfer))?;
+        let system_program = &transfer_accounts[5];
+        SystemAccount::check_at(5, system_program)?;
+        SignerAccount::check_at(8, freeze_authority)?;
+
+        let instruction = Self {
+            transfer,
+            freeze_authority,
+            system_program,
+        };
+        instruction.check_freeze_authority()?;
+
+        Ok(instruction)
+    }
+}
+
+impl<'a, 'info> ThawAndTransferV1<'a, 'info> {
+    /// Checks that `freeze_authority` holds the asset's FreezeDelegate
+    /// plugin, so the thaw can't fail inside the mpl_core CPI.
+    pub fn check_freeze_authority(&self) -> ProgramResult {
+        let asset_info = self.transfer.accounts.asset;
+        MinDataLen::check(asset_info, BASE_ASSET_MIN_LEN)?;
+        assert_account_key(asset_info, Key::AssetV1)?;
+        let asset =
+            BaseAssetV1::try_from(asset_info).map_err(|_| ProgramError::InvalidAccountData)?;
+
+        let (plugin_authority, _, _) =
+            fetch_asset_plugin::<FreezeDelegate>(asset_info, PluginType::FreezeDelegate)
+                .map_err(|_| MplCoreWrapperError::UnauthorizedFreezeDelegate)?;
+
+        let collection = load_asset_collection(&asset, self.transfer.accounts.collection)?;
+
+        if !is_plugin_authority(
+            &plugin_authority,
+            self.freeze_authority.key,
+            &asset,
+            collection.as_ref(),
+        ) {
+            return Err(MplCoreWrapperError::UnauthorizedFreezeDelegate.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for ThawAndTransferV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        let accounts = &self.transfer.accounts;
+
+        invoke_cpi("UpdatePluginV1", accounts.asset.key, || {
+            UpdatePluginV1CpiBuilder::new(accounts.mpl_core)
+                .asset(accounts.asset)
+                .collection(accounts.collection)
+                .payer(accounts.payer)
+                .authority(Some(self.freeze_authority))
+                .system_program(self.system_program)
+                .log_wrapper(accounts.log_wrapper)
+                .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+                .invoke()
+        })?;
+
+        self.transfer.process()
+    }
+}
+
+pub fn process_thaw_and_transfer_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::ThawAndTransferV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    ThawAndTransferV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::{PluginAuthority, UpdateAuthority};
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(
+        freeze_delegate: Pubkey,
+        freeze_authority: AccountInfo<'static>,
+    ) -> Vec<AccountInfo<'static>> {
+        let mut accounts = transfer_nft_accounts(absent_account(), absent_account());
+        accounts[0] = new_asset_account(
+            *accounts[4].key,
+            UpdateAuthority::None,
+            &[(
+                Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+                PluginAuthority::Address {
+                    address: freeze_delegate,
+                },
+            )],
+        );
+        accounts.push(freeze_authority);
+        accounts
+    }
+
+    fn new_data() -> ThawAndTransferV1InstructionData {
+        ThawAndTransferV1InstructionData {
+            transfer: TransferNftV1InstructionData {
+                delegated_authority: false,
+                recipient_allow_list: None,
+                require_owner_signature: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_thaw_and_transfer_cpi_order() {
+        let freeze_delegate = Pubkey::new_unique();
+        let accounts = new_accounts(
+            freeze_delegate,
+            new_test_account(freeze_delegate, true, false, 1, 0, system_program::ID),
+        );
+        let instruction = ThawAndTransferV1::try_from((accounts.as_slice(), new_data())).unwrap();
+
+        take_recorded_cpis();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["UpdatePluginV1", "TransferV1"]);
+    }
+
+    #[test]
+    fn test_thaw_and_transfer_freeze_authority_not_signer() {
+        let freeze_delegate = Pubkey::new_unique();
+        let accounts = new_accounts(
+            freeze_delegate,
+            new_test_account(freeze_delegate, false, false, 1, 0, system_program::ID),
+        );
+
+        let res = ThawAndTransferV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[test]
+    fn test_thaw_and_transfer_wrong_freeze_authority() {
+        let accounts = new_accounts(
+            Pubkey::new_unique(),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
+
+        let res = ThawAndTransferV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::UnauthorizedFreezeDelegate.into()
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    fetch_asset_plugin,
+    instructions::{AddPluginV1CpiBuilder, UpdatePluginV1CpiBuilder},
+    types::{FreezeDelegate, Plugin, PluginType},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::{
+    instructions::{Instructions, TransferNftV1, TransferNftV1InstructionData},
+    utils::{
+        check_distinct_signers, invoke_cpi, AccountCheck, ProcessInstruction, SignerAccount,
+        SystemAccount,
+    },
+};
+
+/// Accounts are those of `TransferNftV1`. The new owner (the escrow) must
+/// sign with a key other than the transfer authority's, as mpl_core resets
+/// the FreezeDelegate to the owner on transfer, and `system_program` must be
+/// the system program since adding the plugin may realloc the asset.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct TransferAndFreezeV1InstructionData {
+    pub transfer: TransferNftV1InstructionData,
+}
+
+#[derive(Debug)]
+pub struct TransferAndFreezeV1<'a, 'info> {
+    pub transfer: TransferNftV1<'a, 'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], TransferAndFreezeV1InstructionData)>
+    for TransferAndFreezeV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (
+            &'a [AccountInfo<'info>],
+            TransferAndFreezeV1InstructionData,
+        ),
+    ) -> Result<Self, Self::Error> {
+        let transfer = TransferNftV1::try_from((accounts, instruction_data.transfer))?;
+        let [_, _, _, new_owner, _, system_program, _, _] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check_at(3, new_owner)?;
+        SystemAccount::check_at(5, system_program)?;
+        // Freezing the asset under the key that transferred it would leave
+        // the sender in control of the escrow.
+        check_distinct_signers(&[
+            transfer
+                .accounts
+                .authority
+                .unwrap_or(transfer.accounts.payer),
+            new_owner,
+        ])?;
+
+        Ok(Self {
+            system_program,
+            transfer,
+        })
+    }
+}
+
+impl<'a, 'info> TransferAndFreezeV1<'a, 'info> {
+    /// Whether the asset already carries a FreezeDelegate, which is then
+    /// updated rather than added.
+    pub fn has_freeze_delegate(&self) -> bool {
+        fetch_asset_plugin::<FreezeDelegate>(
+            self.transfer.accounts.asset,
+            PluginType::FreezeDelegate,
+        )
+        .is_ok()
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for TransferAndFreezeV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        let accounts = &self.transfer.accounts;
+
+        let plugin = Plugin::FreezeDelegate(FreezeDelegate { frozen: true });
+        let has_freeze_delegate = self.has_freeze_delegate();
+        let (asset, collection, new_owner, payer, log_wrapper, mpl_core) = (
+            accounts.asset,
+            accounts.collection,
+            accounts.new_owner,
+            accounts.payer,
+            accounts.log_wrapper,
+            accounts.mpl_core,
+        );
+
+        self.transfer.process()?;
+
+        if has_freeze_delegate {
+            invoke_cpi("UpdatePluginV1", asset.key, || {
+                UpdatePluginV1CpiBuilder::new(mpl_core)
+                    .asset(asset)
+                    .collection(collection)
+                    .payer(payer)
+                    .authority(Some(new_owner))
+                    .system_program(self.system_program)
+                    .log_wrapper(log_wrapper)
+                    .plugin(plugin)
+                    .invoke()
+            })
+        } else {
+            invoke_cpi("AddPluginV1", asset.key, || {
+                AddPluginV1CpiBuilder::new(mpl_core)
+                    .asset(asset)
+                    .collection(collection)
+                    .payer(payer)
+                    .authority(Some(new_owner))
+                    .system_program(self.system_program)
+                    .log_wrapper(log_wrapper)
+                    .plugin(plugin)
+                    .invoke()
+            })
+        }
+    }
+}
+
+pub fn process_transfer_and_freeze_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::TransferAndFreezeV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    TransferAndFreezeV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::MplCoreWrapperError, utils::test_utils::*};
+    use mpl_core::types::{PluginAuthority, UpdateAuthority};
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(plugins: &[(Plugin, PluginAuthority)]) -> Vec<AccountInfo<'static>> {
+        let mut accounts = transfer_nft_accounts(absent_account(), absent_account());
+        accounts[0] = new_asset_account(*accounts[4].key, UpdateAuthority::None, plugins);
+        accounts[3] = new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        accounts
+    }
+
+    fn new_data() -> TransferAndFreezeV1InstructionData {
+        TransferAndFreezeV1InstructionData {
+            transfer: TransferNftV1InstructionData {
+                delegated_authority: false,
+                recipient_allow_list: None,
+                require_owner_signature: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_transfer_and_freeze_cpi_order() {
+        let accounts = new_accounts(&[]);
+        let instruction = TransferAndFreezeV1::try_from((accounts.as_slice(), new_data())).unwrap();
+
+        take_recorded_cpis();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["TransferV1", "AddPluginV1"]);
+    }
+
+    #[test]
+    fn test_transfer_and_freeze_existing_freeze_delegate() {
+        let accounts = new_accounts(&[(
+            Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+            PluginAuthority::Owner,
+        )]);
+        let instruction = TransferAndFreezeV1::try_from((accounts.as_slice(), new_data())).unwrap();
+
+        take_recorded_cpis();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["TransferV1", "UpdatePluginV1"]);
+    }
+
+    #[test]
+    fn test_transfer_and_freeze_new_owner_not_signer() {
+        let mut accounts = new_accounts(&[]);
+        accounts[3] =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
+
+        let res = TransferAndFreezeV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[test]
+    fn test_transfer_and_freeze_new_owner_is_authority() {
+        let mut accounts = new_accounts(&[]);
+        accounts[3] = new_test_account(*accounts[4].key, true, false, 1, 0, system_program::ID);
+
+        let res = TransferAndFreezeV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::DuplicateSigner.into()
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::instructions::UpdateCollectionV1CpiBuilder;
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::Instructions,
+    utils::{
+        invoke_cpi, AccountCheck, MplCoreAccount, NonDefaultKey, NonWritable, NotProgramAccount,
+        OptionalAccountCheck, ProcessInstruction, SignerAccount, SystemAccount, ToOptionalAccount,
+        WritableAccount,
+    },
+};
+
+#[derive(Debug)]
+pub struct TransferCollectionAuthorityV1Accounts<'a, 'info> {
+    pub collection: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+    pub new_update_authority: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]>
+    for TransferCollectionAuthorityV1Accounts<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [collection, payer, authority, new_update_authority, system_program, log_wrapper, mpl_core] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, collection)?;
+        NotProgramAccount::check_at(0, collection)?;
+        NonDefaultKey::check_at(1, payer)?;
+        NotProgramAccount::check_at(1, payer)?;
+        NonDefaultKey::check_at(3, new_update_authority)?;
+        NotProgramAccount::check_at(3, new_update_authority)?;
+        WritableAccount::check_at(0, collection)?;
+        WritableAccount::check_at(1, payer)?;
+        SignerAccount::check_at(1, payer)?;
+        SignerAccount::check_at(2, authority)?;
+        SystemAccount::check_at(4, system_program)?;
+        NonWritable::check_optional_at(5, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(6, mpl_core)?;
+
+        Ok(Self {
+            collection,
+            payer,
+            authority,
+            new_update_authority,
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for TransferCollectionAuthorityV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.collection, false, true),
+            account_meta(self.payer, true, true),
+            account_meta(self.authority, true, false),
+            account_meta(self.new_update_authority, false, false),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct TransferCollectionAuthorityV1InstructionData {
+    pub new_authority: Pubkey,
+}
+
+#[derive(Debug)]
+pub struct TransferCollectionAuthorityV1<'a, 'info> {
+    pub accounts: TransferCollectionAuthorityV1Accounts<'a, 'info>,
+    pub instruction_data: TransferCollectionAuthorityV1InstructionData,
+}
+
+impl<'a, 'info>
+    TryFrom<(
+        &'a [AccountInfo<'info>],
+        TransferCollectionAuthorityV1InstructionData,
+    )> for TransferCollectionAuthorityV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (
+            &'a [AccountInfo<'info>],
+            TransferCollectionAuthorityV1InstructionData,
+        ),
+    ) -> Result<Self, Self::Error> {
+        let accounts = TransferCollectionAuthorityV1Accounts::try_from(accounts)?;
+
+        if accounts.new_update_authority.key != &instruction_data.new_authority {
+            return Err(MplCoreWrapperError::AuthorityMismatch.into());
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for TransferCollectionAuthorityV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        invoke_cpi("UpdateCollectionV1", self.accounts.collection.key, || {
+            UpdateCollectionV1CpiBuilder::new(self.accounts.mpl_core)
+                .collection(self.accounts.collection)
+                .payer(self.accounts.payer)
+                .authority(Some(self.accounts.authority))
+                .new_update_authority(Some(self.accounts.new_update_authority))
+                .system_program(self.accounts.system_program)
+                .log_wrapper(self.accounts.log_wrapper)
+                .invoke()
+        })?;
+
+        Ok(())
+    }
+}
+
+pub fn process_transfer_collection_authority_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::TransferCollectionAuthorityV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    TransferCollectionAuthorityV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(authority_is_signer: bool, new_authority: Pubkey) -> Vec<AccountInfo<'static>> {
+        let collection = new_test_account(Pubkey::new_unique(), false, true, 1, 0, mpl_core::ID);
+        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let authority = new_test_account(
+            Pubkey::new_unique(),
+            authority_is_signer,
+            false,
+            1,
+            0,
+            system_program::ID,
+        );
+        let new_update_authority =
+            new_test_account(new_authority, false, false, 1, 0, system_program::ID);
+        let system_program =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let log_wrapper =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        vec![
+            collection,
+            payer,
+            authority,
+            new_update_authority,
+            system_program,
+            log_wrapper,
+            mpl_core,
+        ]
+    }
+
+    #[test]
+    fn test_transfer_collection_authority_success() {
+        let new_authority = Pubkey::new_unique();
+        let accounts = new_accounts(true, new_authority);
+
+        let res = TransferCollectionAuthorityV1::try_from((
+            accounts.as_slice(),
+            TransferCollectionAuthorityV1InstructionData { new_authority },
+        ));
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(
+            res.unwrap().accounts.new_update_authority.key,
+            &new_authority
+        );
+    }
+
+    #[test]
+    fn test_transfer_collection_authority_not_signer() {
+        let new_authority = Pubkey::new_unique();
+        let accounts = new_accounts(false, new_authority);
+
+        let res = TransferCollectionAuthorityV1::try_from((
+            accounts.as_slice(),
+            TransferCollectionAuthorityV1InstructionData { new_authority },
+        ));
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[test]
+    fn test_transfer_collection_authority_mismatch() {
+        let accounts = new_accounts(true, Pubkey::new_unique());
+
+        let res = TransferCollectionAuthorityV1::try_from((
+            accounts.as_slice(),
+            TransferCollectionAuthorityV1InstructionData {
+                new_authority: Pubkey::new_unique(),
+            },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AuthorityMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_transfer_collection_authority_not_enough_accounts() {
+        let accounts = vec![];
+        let res = TransferCollectionAuthorityV1Accounts::try_from(accounts.as_slice());
+        assert!(
+            res.is_err(),
+            "expected failure because account is not enough, but got Ok: {:?}",
+            res
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_transfer_collection_authority_account_metas() {
+        let accounts = new_accounts(true, Pubkey::new_unique());
+        let metas = TransferCollectionAuthorityV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            TransferCollectionAuthorityV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
@@ -0,0 +1,387 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::{
+        Instructions, TransferNftV1, TransferNftV1Accounts, TransferNftV1InstructionData,
+    },
+    utils::{
+        check_cpi_programs, split_accounts, AccountCheck, MplCoreAccount, NonDefaultKey,
+        NonWritable, NotProgramAccount, OptionalAccountCheck, ProcessInstruction, SignerAccount,
+        SystemAccount, ToOptionalAccount, WritableAccount,
+    },
+};
+
+/// Upper bound on transfers per batch so the CPIs fit in the compute budget.
+pub const MAX_TRANSFER_BATCH: u8 = 8;
+
+/// Accounts per batch item: asset, collection, new_owner.
+pub const TRANSFER_BATCH_ITEM_LEN: usize = 3;
+
+#[derive(Debug)]
+pub struct TransferNftBatchV1Item<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub new_owner: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for TransferNftBatchV1Item<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        Self::try_from_at(0, accounts)
+    }
+}
+
+impl<'a, 'info> TransferNftBatchV1Item<'a, 'info> {
+    /// Parses an item whose accounts start at slot `offset` of the
+    /// instruction, which failed checks log their slots against.
+    pub fn try_from_at(
+        offset: usize,
+        accounts: &'a [AccountInfo<'info>],
+    ) -> Result<Self, ProgramError> {
+        let [asset, collection, new_owner] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(offset, asset)?;
+        NotProgramAccount::check_at(offset, asset)?;
+        NonDefaultKey::check_at(offset + 2, new_owner)?;
+        NotProgramAccount::check_at(offset + 2, new_owner)?;
+        WritableAccount::check_at(offset, asset)?;
+        WritableAccount::check_optional_at(offset + 1, collection.to_optional())?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            new_owner,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct TransferNftBatchV1Accounts<'a, 'info> {
+    pub authority: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: Option<&'a AccountInfo<'info>>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+    pub items: Vec<TransferNftBatchV1Item<'a, 'info>>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for TransferNftBatchV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [authority, payer, system_program, log_wrapper, mpl_core, items @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(1, payer)?;
+        NotProgramAccount::check_at(1, payer)?;
+        SignerAccount::check_optional_at(0, authority.to_optional())?;
+        WritableAccount::check_at(1, payer)?;
+        SignerAccount::check_at(1, payer)?;
+        SystemAccount::check_optional_at(2, system_program.to_optional())?;
+        NonWritable::check_optional_at(3, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(4, mpl_core)?;
+        check_cpi_programs(accounts, mpl_core, log_wrapper.to_optional())?;
+
+        let offset = accounts.len() - items.len();
+        let layout = vec![TRANSFER_BATCH_ITEM_LEN; items.len() / TRANSFER_BATCH_ITEM_LEN];
+        let items = split_accounts(items, &layout)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| {
+                TransferNftBatchV1Item::try_from_at(offset + i * TRANSFER_BATCH_ITEM_LEN, item)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            authority: authority.to_optional(),
+            payer,
+            system_program: system_program.to_optional(),
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+            items,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for TransferNftBatchV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        let mut metas = vec![
+            optional_account_meta(self.authority, true, false),
+            account_meta(self.payer, true, true),
+            optional_account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ];
+
+        for item in self.items.iter() {
+            metas.push(account_meta(item.asset, false, true));
+            metas.push(optional_account_meta(item.collection, false, true));
+            metas.push(account_meta(item.new_owner, false, false));
+        }
+
+        metas
+    }
+}
+
+impl<'a, 'info> TransferNftBatchV1Accounts<'a, 'info> {
+    /// `TransferNftV1` accounts of the `i`th item.
+    pub fn transfer_accounts(&self, i: usize) -> TransferNftV1Accounts<'a, 'info> {
+        let item = &self.items[i];
+
+        TransferNftV1Accounts {
+            asset: item.asset,
+            collection: item.collection,
+            authority: self.authority,
+            new_owner: item.new_owner,
+            payer: self.payer,
+            system_program: self.system_program,
+            log_wrapper: self.log_wrapper,
+            mpl_core: self.mpl_core,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct TransferNftBatchV1InstructionData {
+    pub count: u8,
+}
+
+#[derive(Debug)]
+pub struct TransferNftBatchV1<'a, 'info> {
+    pub transfers: Vec<TransferNftV1<'a, 'info>>,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], TransferNftBatchV1InstructionData)>
+    for TransferNftBatchV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], TransferNftBatchV1InstructionData),
+    ) -> Result<Self, Self::Error> {
+        if instruction_data.count == 0 || instruction_data.count > MAX_TRANSFER_BATCH {
+            return Err(MplCoreWrapperError::InvalidBatchSize.into());
+        }
+
+        let accounts = TransferNftBatchV1Accounts::try_from(accounts)?;
+
+        if accounts.items.len() != instruction_data.count as usize {
+            return Err(MplCoreWrapperError::AccountLayoutMismatch.into());
+        }
+
+        let transfers = (0..accounts.items.len())
+            .map(|i| {
+                TransferNftV1::new(
+                    accounts.transfer_accounts(i),
+                    TransferNftV1InstructionData::default(),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { transfers })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for TransferNftBatchV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        for transfer in self.transfers {
+            transfer.process()?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn process_transfer_nft_batch_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::TransferNftBatchV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    TransferNftBatchV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::UpdateAuthority;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(item_count: usize) -> Vec<AccountInfo<'static>> {
+        let authority =
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        let owner = *authority.key;
+        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let system_program =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let log_wrapper =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        let mut accounts = vec![authority, payer, system_program, log_wrapper, mpl_core];
+
+        for _ in 0..item_count {
+            accounts.push(new_asset_account(owner, UpdateAuthority::None, &[]));
+            accounts.push(new_test_account(
+                system_program::ID,
+                false,
+                false,
+                1,
+                0,
+                system_program::ID,
+            ));
+            accounts.push(new_test_account(
+                Pubkey::new_unique(),
+                false,
+                false,
+                1,
+                0,
+                system_program::ID,
+            ));
+        }
+
+        accounts
+    }
+
+    #[test]
+    fn test_transfer_nft_batch_three_assets() {
+        let accounts = new_accounts(3);
+
+        let instruction = TransferNftBatchV1::try_from((
+            accounts.as_slice(),
+            TransferNftBatchV1InstructionData { count: 3 },
+        ))
+        .unwrap();
+
+        let items = accounts[5..].chunks_exact(TRANSFER_BATCH_ITEM_LEN);
+        assert_eq!(instruction.transfers.len(), 3);
+        for (transfer, window) in instruction.transfers.iter().zip(items.clone()) {
+            assert_eq!(transfer.accounts.asset.key, window[0].key);
+            assert!(transfer.accounts.collection.is_none());
+            assert_eq!(transfer.accounts.new_owner.key, window[2].key);
+        }
+
+        take_recorded_cpis();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(
+            take_recorded_cpi_targets(),
+            items
+                .map(|window| ("TransferV1", *window[0].key))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_transfer_nft_batch_logs_item_account_index() {
+        let mut accounts = new_accounts(2);
+        accounts[8] = new_test_account(Pubkey::new_unique(), false, false, 1, 0, mpl_core::ID);
+
+        take_recorded_account_errors();
+        let res = TransferNftBatchV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountData);
+        assert_eq!(
+            take_recorded_account_errors(),
+            vec!["account[8] must be writable"]
+        );
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_transfer_nft_batch_checks_each_transfer() {
+        let mut accounts = new_accounts(2);
+        accounts[8] = new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]);
+
+        let res = TransferNftBatchV1::try_from((
+            accounts.as_slice(),
+            TransferNftBatchV1InstructionData { count: 2 },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::UnauthorizedTransferAuthority.into()
+        );
+    }
+
+    #[test]
+    fn test_transfer_nft_batch_count_mismatch() {
+        let accounts = new_accounts(2);
+
+        let res = TransferNftBatchV1::try_from((
+            accounts.as_slice(),
+            TransferNftBatchV1InstructionData { count: 3 },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AccountLayoutMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_transfer_nft_batch_partial_item() {
+        let mut accounts = new_accounts(2);
+        accounts.pop();
+
+        let res = TransferNftBatchV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AccountLayoutMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_transfer_nft_batch_too_large() {
+        let accounts = new_accounts(MAX_TRANSFER_BATCH as usize + 1);
+
+        let res = TransferNftBatchV1::try_from((
+            accounts.as_slice(),
+            TransferNftBatchV1InstructionData {
+                count: MAX_TRANSFER_BATCH + 1,
+            },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidBatchSize.into()
+        );
+    }
+
+    #[test]
+    fn test_transfer_nft_batch_account_not_enough_accounts() {
+        let accounts = vec![];
+        let res = TransferNftBatchV1Accounts::try_from(accounts.as_slice());
+        assert!(
+            res.is_err(),
+            "expected failure because account is not enough, but got Ok: {:?}",
+            res
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_transfer_nft_batch_account_metas() {
+        let accounts = new_accounts(2);
+        let metas = TransferNftBatchV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            TransferNftBatchV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
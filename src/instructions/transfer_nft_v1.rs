@@ -1,13 +1,39 @@
+use borsh::{
+    maybestd::io::{self, Read},
+    BorshDeserialize, BorshSerialize,
+};
 use mpl_core::instructions::TransferV1CpiBuilder;
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
 };
+#[cfg(feature = "recipient-check")]
+use solana_sdk_ids::system_program;
 
-use crate::utils::{
-    AccountCheck, MplCoreAccount, OptionalAccountCheck, ProcessInstruction, SignerAccount,
-    SystemAccount, ToOptionalAccount, WritableAccount,
+use mpl_core::{accounts::BaseAssetV1, types::Key};
+#[cfg(feature = "asset-state")]
+use mpl_core::{
+    fetch_asset_plugin, fetch_collection_plugin,
+    types::{PermanentTransferDelegate, PluginType, TransferDelegate},
 };
 
+#[cfg(feature = "asset-state")]
+use crate::plugins::{is_plugin_authority, load_asset_collection};
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    error::MplCoreWrapperError,
+    events::{MplCoreWrapperEvent, TransferNftEvent},
+    instructions::Instructions,
+    utils::{
+        assert_account_key, invoke_cpi, resolve_authority, AccountCheck, MinDataLen,
+        MplCoreAccount, NonDefaultKey, NonWritable, NotProgramAccount, OptionalAccountCheck,
+        ProcessInstruction, SignerAccount, SystemAccount, ToOptionalAccount, WritableAccount,
+        BASE_ASSET_MIN_LEN,
+    },
+};
 #[derive(Debug)]
 pub struct TransferNftV1Accounts<'a, 'info> {
     pub asset: &'a AccountInfo<'info>,
@@ -30,13 +56,19 @@ impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for TransferNftV1Accounts<'a,
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        WritableAccount::check(asset)?;
-        WritableAccount::check_optional(collection.to_optional())?;
-        SignerAccount::check_optional(authority.to_optional())?;
-        WritableAccount::check(payer)?;
-        SignerAccount::check(payer)?;
-        SystemAccount::check_optional(system_program.to_optional())?;
-        MplCoreAccount::check(mpl_core)?;
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(3, new_owner)?;
+        NotProgramAccount::check_at(3, new_owner)?;
+        NonDefaultKey::check_at(4, payer)?;
+        NotProgramAccount::check_at(4, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        WritableAccount::check_at(4, payer)?;
+        SignerAccount::check_at(4, payer)?;
+        SystemAccount::check_optional_at(5, system_program.to_optional())?;
+        NonWritable::check_optional_at(6, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(7, mpl_core)?;
 
         Ok(Self {
             asset,
@@ -51,70 +83,273 @@ impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for TransferNftV1Accounts<'a,
     }
 }
 
+#[cfg(feature = "client")]
+impl ToAccountMetas for TransferNftV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            optional_account_meta(self.authority, true, false),
+            account_meta(self.new_owner, false, false),
+            account_meta(self.payer, true, true),
+            optional_account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+impl<'a, 'info> TransferNftV1Accounts<'a, 'info> {
+    /// Checks that the transfer authority (the payer when none is given) is
+    /// the asset owner and signed, ruling out delegate transfers.
+    pub fn check_owner_signature(&self) -> ProgramResult {
+        let authority = self.authority.unwrap_or(self.payer);
+        MinDataLen::check(self.asset, BASE_ASSET_MIN_LEN)?;
+        assert_account_key(self.asset, Key::AssetV1)?;
+        let asset =
+            BaseAssetV1::try_from(self.asset).map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if authority.key != &asset.owner || !authority.is_signer {
+            return Err(MplCoreWrapperError::OwnerSignatureRequired.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "distinct-transfer-authority")]
+impl<'a, 'info> TransferNftV1Accounts<'a, 'info> {
+    /// A delegate handing the asset to itself is most likely a client
+    /// mixing up accounts, so `authority` and `new_owner` must differ.
+    pub fn check_distinct_new_owner(&self) -> ProgramResult {
+        match self.authority {
+            Some(authority) if authority.key == self.new_owner.key => {
+                Err(MplCoreWrapperError::DuplicateAccount.into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "asset-state")]
+impl<'a, 'info> TransferNftV1Accounts<'a, 'info> {
+    /// Checks that the transfer authority (the payer when none is given) is
+    /// the owner or holds a transfer delegate, on the asset or as its
+    /// collection's permanent delegate, so a bad authority fails here instead
+    /// of inside the mpl_core CPI.
+    pub fn check_transfer_authority(&self) -> ProgramResult {
+        let authority = self.authority.unwrap_or(self.payer).key;
+        MinDataLen::check(self.asset, BASE_ASSET_MIN_LEN)?;
+        assert_account_key(self.asset, Key::AssetV1)?;
+        let asset =
+            BaseAssetV1::try_from(self.asset).map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if authority == &asset.owner {
+            return Ok(());
+        }
+
+        let permanent_delegate = fetch_asset_plugin::<PermanentTransferDelegate>(
+            self.asset,
+            PluginType::PermanentTransferDelegate,
+        )
+        .ok()
+        .map(|(plugin_authority, _, _)| plugin_authority);
+        let delegate =
+            fetch_asset_plugin::<TransferDelegate>(self.asset, PluginType::TransferDelegate)
+                .ok()
+                .map(|(plugin_authority, _, _)| plugin_authority);
+        let collection = load_asset_collection(&asset, self.collection)?;
+        // Only the plugins of the collection the asset is in apply.
+        let collection_delegate = self
+            .collection
+            .filter(|_| collection.is_some())
+            .and_then(|collection| {
+                fetch_collection_plugin::<PermanentTransferDelegate>(
+                    collection,
+                    PluginType::PermanentTransferDelegate,
+                )
+                .ok()
+            })
+            .map(|(plugin_authority, _, _)| plugin_authority);
+
+        if permanent_delegate
+            .iter()
+            .chain(delegate.iter())
+            .chain(collection_delegate.iter())
+            .any(|plugin_authority| {
+                is_plugin_authority(plugin_authority, authority, &asset, collection.as_ref())
+            })
+        {
+            return Ok(());
+        }
+
+        Err(MplCoreWrapperError::UnauthorizedTransferAuthority.into())
+    }
+}
+
+#[cfg(feature = "recipient-check")]
+impl<'a, 'info> TransferNftV1Accounts<'a, 'info> {
+    /// Program-owned recipients may never be able to sign the asset back out,
+    /// so only system accounts and allow-listed programs can receive it.
+    pub fn check_recipient(&self, allow_list: &[Pubkey]) -> ProgramResult {
+        let owner = self.new_owner.owner;
+
+        if owner != &system_program::ID && !allow_list.contains(owner) {
+            return Err(MplCoreWrapperError::UnsafeTransferRecipient.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(BorshSerialize, Debug, Default)]
+pub struct TransferNftV1InstructionData {
+    /// When `true`, `authority` is the payer's delegated authority PDA of
+    /// this program, which the program signs for.
+    pub delegated_authority: bool,
+    /// Programs whose accounts may receive the asset besides system accounts.
+    pub recipient_allow_list: Option<Vec<Pubkey>>,
+    /// When `true`, only the asset owner signing as `authority` may transfer.
+    pub require_owner_signature: Option<bool>,
+}
+
+/// `TransferNftV1` used to carry no data, so an empty payload decodes to the
+/// defaults and those clients keep working.
+impl BorshDeserialize for TransferNftV1InstructionData {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut first = [0u8; 1];
+        if reader.read(&mut first)? == 0 {
+            return Ok(Self::default());
+        }
+
+        let mut reader = first.as_slice().chain(reader);
+        Ok(Self {
+            delegated_authority: BorshDeserialize::deserialize_reader(&mut reader)?,
+            recipient_allow_list: BorshDeserialize::deserialize_reader(&mut reader)?,
+            require_owner_signature: BorshDeserialize::deserialize_reader(&mut reader)?,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct TransferNftV1<'a, 'info> {
-    accounts: TransferNftV1Accounts<'a, 'info>,
+    pub accounts: TransferNftV1Accounts<'a, 'info>,
+    pub instruction_data: TransferNftV1InstructionData,
+    /// Seeds, bump included, the program signs for `authority` with.
+    pub authority_seeds: Option<Vec<Vec<u8>>>,
 }
 
-impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for TransferNftV1<'a, 'info> {
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], TransferNftV1InstructionData)>
+    for TransferNftV1<'a, 'info>
+{
     type Error = ProgramError;
 
-    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
-        let accounts = TransferNftV1Accounts::try_from(accounts)?;
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], TransferNftV1InstructionData),
+    ) -> Result<Self, Self::Error> {
+        Self::new(TransferNftV1Accounts::try_from(accounts)?, instruction_data)
+    }
+}
 
-        Ok(Self { accounts })
+impl<'a, 'info> TransferNftV1<'a, 'info> {
+    /// Checks `instruction_data` against already parsed accounts, so batches
+    /// can share one set of accounts across transfers.
+    pub fn new(
+        accounts: TransferNftV1Accounts<'a, 'info>,
+        instruction_data: TransferNftV1InstructionData,
+    ) -> Result<Self, ProgramError> {
+        let authority_seeds = resolve_authority(
+            accounts.authority,
+            accounts.payer,
+            instruction_data.delegated_authority,
+        )?;
+        if instruction_data.require_owner_signature == Some(true) {
+            accounts.check_owner_signature()?;
+        }
+        #[cfg(feature = "distinct-transfer-authority")]
+        accounts.check_distinct_new_owner()?;
+        #[cfg(feature = "asset-state")]
+        accounts.check_transfer_authority()?;
+        #[cfg(feature = "recipient-check")]
+        accounts.check_recipient(
+            instruction_data
+                .recipient_allow_list
+                .as_deref()
+                .unwrap_or_default(),
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            authority_seeds,
+        })
+    }
+
+    pub fn event(&self) -> MplCoreWrapperEvent {
+        MplCoreWrapperEvent::TransferNft(TransferNftEvent {
+            asset: *self.accounts.asset.key,
+            new_owner: *self.accounts.new_owner.key,
+        })
     }
 }
 
 impl<'a, 'info> ProcessInstruction for TransferNftV1<'a, 'info> {
     fn process(self) -> ProgramResult {
-        TransferV1CpiBuilder::new(self.accounts.mpl_core)
+        let event = self.event();
+
+        let mut transfer_cpi = TransferV1CpiBuilder::new(self.accounts.mpl_core);
+
+        transfer_cpi
             .asset(self.accounts.asset)
             .collection(self.accounts.collection)
             .authority(self.accounts.authority)
             .new_owner(self.accounts.new_owner)
             .payer(self.accounts.payer)
             .system_program(self.accounts.system_program)
-            .log_wrapper(self.accounts.log_wrapper)
-            .invoke()?;
+            .log_wrapper(self.accounts.log_wrapper);
 
-        Ok(())
+        invoke_cpi("TransferV1", self.accounts.asset.key, || {
+            match self.authority_seeds {
+                Some(seeds) => {
+                    let seeds = seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
+                    transfer_cpi.invoke_signed(&[&seeds])
+                }
+                None => transfer_cpi.invoke(),
+            }
+        })?;
+
+        event.emit()
     }
 }
 
+pub fn process_transfer_nft_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::TransferNftV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    TransferNftV1::try_from((accounts, instruction_data))?.process()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::test_utils::*;
+    use crate::{
+        error::MplCoreWrapperError,
+        utils::{derive_delegated_authority, test_utils::*},
+    };
+    use mpl_core::types::UpdateAuthority;
     use solana_program::pubkey::Pubkey;
     use solana_sdk_ids::system_program;
 
     #[test]
     fn test_transfer_nft_account_success() {
-        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let collection =
-            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let new_owner =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
-        let system_program =
-            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
-        let log_wrapper =
-            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
-        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
-
-        let accounts = vec![
-            asset,
-            collection,
-            authority,
-            new_owner,
-            payer,
-            system_program,
-            log_wrapper,
-            mpl_core,
-        ];
+        let accounts = transfer_nft_accounts(
+            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
 
         let res = TransferNftV1Accounts::try_from(accounts.as_slice());
         assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
@@ -122,15 +357,11 @@ mod tests {
 
     #[test]
     fn test_transfer_nft_account_wrong_system_program() {
-        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let collection =
-            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let new_owner =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
-        let bad_system_program = new_test_account(
+        let mut accounts = transfer_nft_accounts(
+            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
+        accounts[5] = new_test_account(
             Pubkey::new_unique(),
             false,
             false,
@@ -138,44 +369,18 @@ mod tests {
             0,
             Pubkey::new_unique(),
         );
-        let log_wrapper =
-            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
-        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
-
-        let accounts = vec![
-            asset,
-            collection,
-            authority,
-            new_owner,
-            payer,
-            bad_system_program,
-            log_wrapper,
-            mpl_core,
-        ];
 
         let res = TransferNftV1Accounts::try_from(accounts.as_slice());
-        assert!(
-            res.is_err(),
-            "expected failure because system_program was wrong, but got Ok: {:?}",
-            res,
-        );
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountOwner);
     }
 
     #[test]
     fn test_transfer_nft_account_wrong_mpl_core() {
-        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let collection =
-            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let new_owner =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
-        let system_program =
-            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
-        let log_wrapper =
-            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
-        let bad_mpl_core = new_test_account(
+        let mut accounts = transfer_nft_accounts(
+            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
+        accounts[7] = new_test_account(
             Pubkey::new_unique(),
             false,
             false,
@@ -184,23 +389,490 @@ mod tests {
             Pubkey::new_unique(),
         );
 
-        let accounts = vec![
-            asset,
-            collection,
-            authority,
-            new_owner,
-            payer,
-            system_program,
-            log_wrapper,
-            bad_mpl_core,
-        ];
+        let res = TransferNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountOwner);
+    }
+
+    #[test]
+    fn test_transfer_nft_account_log_wrapper_read_only() {
+        let mut accounts = transfer_nft_accounts(
+            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
+        accounts[6] =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
+
+        let res = TransferNftV1Accounts::try_from(accounts.as_slice());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_transfer_nft_account_log_wrapper_writable() {
+        let mut accounts = transfer_nft_accounts(
+            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
+        accounts[6] = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
 
+        take_recorded_account_errors();
         let res = TransferNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::UnexpectedWritableAccount.into()
+        );
+        assert_eq!(
+            take_recorded_account_errors(),
+            vec!["account[6] must not be writable"]
+        );
+    }
+
+    #[cfg(feature = "distinct-transfer-authority")]
+    #[test]
+    fn test_transfer_nft_distinct_new_owner() {
+        let accounts = transfer_nft_accounts(
+            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
+
+        let res = TransferNftV1Accounts::try_from(accounts.as_slice())
+            .and_then(|accounts| accounts.check_distinct_new_owner());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[cfg(feature = "distinct-transfer-authority")]
+    #[test]
+    fn test_transfer_nft_authority_is_new_owner() {
+        let delegate = Pubkey::new_unique();
+        let mut accounts = transfer_nft_accounts(
+            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+            new_test_account(delegate, true, false, 1, 0, system_program::ID),
+        );
+        accounts[3] = new_test_account(delegate, false, false, 1, 0, system_program::ID);
+
+        let res = TransferNftV1Accounts::try_from(accounts.as_slice())
+            .and_then(|accounts| accounts.check_distinct_new_owner());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::DuplicateAccount.into()
+        );
+    }
+
+    #[test]
+    fn test_transfer_nft_account_default_new_owner() {
+        let mut accounts = transfer_nft_accounts(
+            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        );
+        accounts[3] = new_test_account(Pubkey::default(), false, false, 1, 0, system_program::ID);
+
+        let res = TransferNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidAccountAddress.into()
+        );
+    }
+
+    fn transfer_as_delegated_authority(
+        controller: Pubkey,
+        payer: AccountInfo<'static>,
+    ) -> ProgramResult {
+        let (pda, _) = derive_delegated_authority(&controller);
+        let mut accounts = transfer_nft_accounts(
+            new_asset_account(pda, UpdateAuthority::None, &[]),
+            new_test_account(pda, false, false, 1, 0, system_program::ID),
+        );
+        accounts[4] = payer;
+
+        TransferNftV1::try_from((
+            accounts.as_slice(),
+            TransferNftV1InstructionData {
+                delegated_authority: true,
+                recipient_allow_list: None,
+                require_owner_signature: None,
+            },
+        ))?
+        .process()
+    }
+
+    #[test]
+    fn test_transfer_nft_signed_by_delegated_authority() {
+        let controller = Pubkey::new_unique();
+        let payer = new_test_account(controller, true, true, 1, 0, system_program::ID);
+
+        take_recorded_cpis();
+        let res = transfer_as_delegated_authority(controller, payer);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["TransferV1"]);
+    }
+
+    #[test]
+    fn test_transfer_nft_delegated_authority_of_other_controller() {
+        let caller = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+
+        take_recorded_cpis();
+        let res = transfer_as_delegated_authority(Pubkey::new_unique(), caller);
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidSignerSeeds.into()
+        );
+        assert!(take_recorded_cpis().is_empty());
+    }
+
+    fn transfer_requiring_owner(
+        asset: AccountInfo<'static>,
+        authority: Pubkey,
+        require_owner_signature: bool,
+    ) -> ProgramResult {
+        let accounts = transfer_nft_accounts(
+            asset,
+            new_test_account(authority, true, false, 1, 0, system_program::ID),
+        );
+
+        TransferNftV1::try_from((
+            accounts.as_slice(),
+            TransferNftV1InstructionData {
+                delegated_authority: false,
+                recipient_allow_list: None,
+                require_owner_signature: Some(require_owner_signature),
+            },
+        ))?
+        .process()
+    }
+
+    fn delegated_asset(delegate: Pubkey) -> AccountInfo<'static> {
+        use mpl_core::types::{Plugin, PluginAuthority, TransferDelegate};
+
+        new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::None,
+            &[(
+                Plugin::TransferDelegate(TransferDelegate {}),
+                PluginAuthority::Address { address: delegate },
+            )],
+        )
+    }
+
+    #[test]
+    fn test_transfer_nft_require_owner_signature_owner_signed() {
+        let owner = Pubkey::new_unique();
+        let res = transfer_requiring_owner(
+            new_asset_account(owner, UpdateAuthority::None, &[]),
+            owner,
+            true,
+        );
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_transfer_nft_require_owner_signature_unset_allows_delegate() {
+        let delegate = Pubkey::new_unique();
+        let res = transfer_requiring_owner(delegated_asset(delegate), delegate, false);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+
+        let res = transfer_requiring_owner(delegated_asset(delegate), delegate, true);
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::OwnerSignatureRequired.into()
+        );
+    }
+
+    #[cfg(feature = "asset-state")]
+    fn transfer_with(asset: AccountInfo<'static>, authority: Pubkey) -> ProgramResult {
+        let accounts = transfer_nft_accounts(
+            asset,
+            new_test_account(authority, true, false, 1, 0, system_program::ID),
+        );
+
+        TransferNftV1::try_from((
+            accounts.as_slice(),
+            TransferNftV1InstructionData {
+                delegated_authority: false,
+                recipient_allow_list: None,
+                require_owner_signature: None,
+            },
+        ))?
+        .process()
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_transfer_nft_owner_authority() {
+        let owner = Pubkey::new_unique();
+        let res = transfer_with(new_asset_account(owner, UpdateAuthority::None, &[]), owner);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_transfer_nft_delegate_authority() {
+        use mpl_core::types::{Plugin, PluginAuthority, TransferDelegate};
+
+        let delegate = Pubkey::new_unique();
+        let asset = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::None,
+            &[(
+                Plugin::TransferDelegate(TransferDelegate {}),
+                PluginAuthority::Address { address: delegate },
+            )],
+        );
+
+        let res = transfer_with(asset, delegate);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_transfer_nft_permanent_delegate_update_authority() {
+        use mpl_core::types::{PermanentTransferDelegate, Plugin, PluginAuthority};
+
+        let update_authority = Pubkey::new_unique();
+        let asset = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::Address(update_authority),
+            &[(
+                Plugin::PermanentTransferDelegate(PermanentTransferDelegate {}),
+                PluginAuthority::UpdateAuthority,
+            )],
+        );
+
+        let res = transfer_with(asset, update_authority);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[cfg(feature = "asset-state")]
+    fn transfer_in_collection(
+        asset: AccountInfo<'static>,
+        collection: AccountInfo<'static>,
+        authority: Pubkey,
+    ) -> ProgramResult {
+        let mut accounts = transfer_nft_accounts(
+            asset,
+            new_test_account(authority, true, false, 1, 0, system_program::ID),
+        );
+        accounts[1] = collection;
+
+        TransferNftV1::try_from((accounts.as_slice(), TransferNftV1InstructionData::default()))?
+            .process()
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_transfer_nft_permanent_delegate_collection_update_authority() {
+        use mpl_core::types::{PermanentTransferDelegate, Plugin, PluginAuthority};
+
+        let collection_authority = Pubkey::new_unique();
+        let collection = new_collection_account(collection_authority);
+        let asset = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::Collection(*collection.key),
+            &[(
+                Plugin::PermanentTransferDelegate(PermanentTransferDelegate {}),
+                PluginAuthority::UpdateAuthority,
+            )],
+        );
+
+        let res = transfer_in_collection(asset, collection, collection_authority);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_transfer_nft_collection_permanent_delegate() {
+        use mpl_core::types::{PermanentTransferDelegate, Plugin, PluginAuthority};
+
+        let delegate = Pubkey::new_unique();
+        let collection = || {
+            new_sized_collection_account(
+                Pubkey::new_unique(),
+                0,
+                &[(
+                    Plugin::PermanentTransferDelegate(PermanentTransferDelegate {}),
+                    PluginAuthority::Address { address: delegate },
+                )],
+            )
+        };
+
+        let in_collection = collection();
+        let asset = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::Collection(*in_collection.key),
+            &[],
+        );
+        let res = transfer_in_collection(asset, in_collection, delegate);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+
+        // A collection the asset isn't in grants nothing.
+        let asset = new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]);
+        let res = transfer_in_collection(asset, collection(), delegate);
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::UnauthorizedTransferAuthority.into()
+        );
+
+        let asset = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::Collection(Pubkey::new_unique()),
+            &[],
+        );
+        let res = transfer_in_collection(asset, collection(), delegate);
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::CollectionAccountRequired.into()
+        );
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_transfer_nft_unrelated_authority() {
+        use mpl_core::types::{Plugin, PluginAuthority, TransferDelegate};
+
+        let update_authority = Pubkey::new_unique();
+        let asset = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::Address(update_authority),
+            &[(
+                Plugin::TransferDelegate(TransferDelegate {}),
+                PluginAuthority::Address {
+                    address: Pubkey::new_unique(),
+                },
+            )],
+        );
+
+        let res = transfer_with(asset, Pubkey::new_unique());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::UnauthorizedTransferAuthority.into()
+        );
+
+        // The update authority alone can't transfer without a permanent delegate.
+        let res = transfer_with(
+            new_asset_account(
+                Pubkey::new_unique(),
+                UpdateAuthority::Address(update_authority),
+                &[],
+            ),
+            update_authority,
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::UnauthorizedTransferAuthority.into()
+        );
+    }
+
+    #[cfg(feature = "recipient-check")]
+    fn transfer_to(
+        new_owner: AccountInfo<'static>,
+        recipient_allow_list: Option<Vec<Pubkey>>,
+    ) -> Result<(), ProgramError> {
+        let owner = Pubkey::new_unique();
+        let mut accounts = transfer_nft_accounts(
+            new_asset_account(owner, UpdateAuthority::None, &[]),
+            new_test_account(owner, true, false, 1, 0, system_program::ID),
+        );
+        accounts[3] = new_owner;
+
+        TransferNftV1::try_from((
+            accounts.as_slice(),
+            TransferNftV1InstructionData {
+                delegated_authority: false,
+                recipient_allow_list,
+                require_owner_signature: None,
+            },
+        ))
+        .map(|_| ())
+    }
+
+    #[cfg(feature = "recipient-check")]
+    #[test]
+    fn test_transfer_nft_system_owned_recipient() {
+        let new_owner =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
+
+        let res = transfer_to(new_owner, None);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[cfg(feature = "recipient-check")]
+    #[test]
+    fn test_transfer_nft_program_owned_recipient() {
+        let program = Pubkey::new_unique();
+        let new_owner = new_test_account(Pubkey::new_unique(), false, false, 1, 0, program);
+
+        let res = transfer_to(new_owner, Some(vec![Pubkey::new_unique()]));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::UnsafeTransferRecipient.into()
+        );
+    }
+
+    #[cfg(feature = "recipient-check")]
+    #[test]
+    fn test_transfer_nft_allow_listed_recipient() {
+        let program = Pubkey::new_unique();
+        let new_owner = new_test_account(Pubkey::new_unique(), false, false, 1, 0, program);
+
+        let res = transfer_to(new_owner, Some(vec![program]));
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_transfer_nft_event() {
+        use crate::events::{MplCoreWrapperEvent, TransferNftEvent};
+
+        let owner = Pubkey::new_unique();
+        let accounts = transfer_nft_accounts(
+            new_asset_account(owner, UpdateAuthority::None, &[]),
+            new_test_account(owner, true, false, 1, 0, system_program::ID),
+        );
+        let instruction = TransferNftV1::try_from((
+            accounts.as_slice(),
+            TransferNftV1InstructionData {
+                delegated_authority: false,
+                recipient_allow_list: None,
+                require_owner_signature: None,
+            },
+        ))
+        .unwrap();
+
+        let log = instruction.event().encode().unwrap();
+        assert_eq!(
+            MplCoreWrapperEvent::decode(&log),
+            Ok(MplCoreWrapperEvent::TransferNft(TransferNftEvent {
+                asset: *accounts[0].key,
+                new_owner: *accounts[3].key,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_transfer_nft_data_without_payload() {
+        use crate::instructions::discriminant;
+
+        let instruction = Instructions::try_from_slice(&[discriminant::TRANSFER_NFT_V1]);
         assert!(
-            res.is_err(),
-            "expected failure because mpl_core was wrong, but got Ok: {:?}",
-            res
+            matches!(
+                &instruction,
+                Ok(Instructions::TransferNftV1(TransferNftV1InstructionData {
+                    delegated_authority: false,
+                    recipient_allow_list: None,
+                    require_owner_signature: None,
+                }))
+            ),
+            "expected default data, but got: {:?}",
+            instruction
         );
+
+        let data = TransferNftV1InstructionData {
+            delegated_authority: true,
+            recipient_allow_list: Some(vec![Pubkey::new_unique()]),
+            require_owner_signature: Some(true),
+        };
+        let decoded =
+            TransferNftV1InstructionData::try_from_slice(&data.try_to_vec().unwrap()).unwrap();
+        assert!(decoded.delegated_authority);
+        assert_eq!(decoded.recipient_allow_list, data.recipient_allow_list);
+        assert_eq!(decoded.require_owner_signature, Some(true));
     }
 
     #[test]
@@ -213,4 +885,22 @@ mod tests {
             res
         );
     }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_transfer_nft_account_metas() {
+        let authority =
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        let accounts = transfer_nft_accounts(
+            new_asset_account(*authority.key, UpdateAuthority::None, &[]),
+            authority,
+        );
+        let metas = TransferNftV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[2], |accounts| {
+            TransferNftV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
 }
@@ -0,0 +1,339 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_asset_plugin,
+    instructions::UpdatePluginV1CpiBuilder,
+    types::{Attribute, Attributes, Plugin, PluginType},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::{Instructions, TransferNftV1, TransferNftV1InstructionData},
+    plugins::{merge_attributes, MAX_ATTRIBUTES},
+    utils::{
+        invoke_cpi, AccountCheck, MinDataLen, ProcessInstruction, SystemAccount, BASE_ASSET_MIN_LEN,
+    },
+};
+
+/// Attribute key holding the owner the asset was last transferred from.
+pub const LAST_OWNER_KEY: &str = "last_owner";
+/// Attribute key holding how many provenance transfers the asset went through.
+pub const TRANSFER_COUNT_KEY: &str = "transfer_count";
+
+/// Accounts are those of `TransferNftV1`; `system_program` must be the system
+/// program since UpdatePluginV1 may realloc the asset. The transfer authority (the payer
+/// when none is given) must also be the authority of the Attributes plugin.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct TransferWithProvenanceV1InstructionData {
+    pub transfer: TransferNftV1InstructionData,
+}
+
+#[derive(Debug)]
+pub struct TransferWithProvenanceV1<'a, 'info> {
+    pub transfer: TransferNftV1<'a, 'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info>
+    TryFrom<(
+        &'a [AccountInfo<'info>],
+        TransferWithProvenanceV1InstructionData,
+    )> for TransferWithProvenanceV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (
+            &'a [AccountInfo<'info>],
+            TransferWithProvenanceV1InstructionData,
+        ),
+    ) -> Result<Self, Self::Error> {
+        let transfer = TransferNftV1::try_from((accounts, instruction_data.transfer))?;
+        let [_, _, _, _, _, system_program, _, _] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SystemAccount::check_at(5, system_program)?;
+
+        Ok(Self {
+            system_program,
+            transfer,
+        })
+    }
+}
+
+impl<'a, 'info> TransferWithProvenanceV1<'a, 'info> {
+    /// Reads the asset's Attributes plugin and returns it with `last_owner`
+    /// set to the current owner and `transfer_count` incremented.
+    pub fn provenance_attributes(&self) -> Result<Attributes, ProgramError> {
+        let (_, attributes, _) =
+            fetch_asset_plugin::<Attributes>(self.transfer.accounts.asset, PluginType::Attributes)
+                .map_err(|_| MplCoreWrapperError::PluginNotFound)?;
+        MinDataLen::check(self.transfer.accounts.asset, BASE_ASSET_MIN_LEN)?;
+        let asset = BaseAssetV1::try_from(self.transfer.accounts.asset)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        let transfer_count = match attributes
+            .attribute_list
+            .iter()
+            .find(|attribute| attribute.key == TRANSFER_COUNT_KEY)
+        {
+            Some(attribute) => attribute
+                .value
+                .parse::<u64>()
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+            None => 0,
+        };
+        let transfer_count = transfer_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(Attributes {
+            attribute_list: merge_attributes(
+                attributes.attribute_list,
+                vec![
+                    Attribute {
+                        key: LAST_OWNER_KEY.to_string(),
+                        value: asset.owner.to_string(),
+                    },
+                    Attribute {
+                        key: TRANSFER_COUNT_KEY.to_string(),
+                        value: transfer_count.to_string(),
+                    },
+                ],
+                MAX_ATTRIBUTES,
+            )?,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for TransferWithProvenanceV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        let accounts = &self.transfer.accounts;
+        let asset = accounts.asset;
+
+        // Read before the transfer, while `owner` is still the previous owner.
+        let attributes = self.provenance_attributes()?;
+        let signer_seeds = self.transfer.authority_seeds.clone();
+
+        let mut update_plugin_cpi = UpdatePluginV1CpiBuilder::new(accounts.mpl_core);
+
+        update_plugin_cpi
+            .asset(accounts.asset)
+            .collection(accounts.collection)
+            .payer(accounts.payer)
+            .authority(accounts.authority)
+            .system_program(self.system_program)
+            .log_wrapper(accounts.log_wrapper)
+            .plugin(Plugin::Attributes(attributes));
+
+        self.transfer.process()?;
+        invoke_cpi("UpdatePluginV1", asset.key, || match signer_seeds {
+            Some(seeds) => {
+                let seeds = seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
+                update_plugin_cpi.invoke_signed(&[&seeds])
+            }
+            None => update_plugin_cpi.invoke(),
+        })?;
+
+        Ok(())
+    }
+}
+
+pub fn process_transfer_with_provenance_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::TransferWithProvenanceV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    TransferWithProvenanceV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{derive_delegated_authority, test_utils::*};
+    use mpl_core::types::{PluginAuthority, UpdateAuthority};
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(attributes: Option<Vec<Attribute>>) -> Vec<AccountInfo<'static>> {
+        let plugins = attributes
+            .map(|attribute_list| {
+                vec![(
+                    Plugin::Attributes(Attributes { attribute_list }),
+                    PluginAuthority::Owner,
+                )]
+            })
+            .unwrap_or_default();
+
+        let mut accounts = transfer_nft_accounts(absent_account(), absent_account());
+        accounts[0] = new_asset_account(*accounts[4].key, UpdateAuthority::None, &plugins);
+        accounts
+    }
+
+    fn new_data() -> TransferWithProvenanceV1InstructionData {
+        TransferWithProvenanceV1InstructionData {
+            transfer: TransferNftV1InstructionData {
+                delegated_authority: false,
+                recipient_allow_list: None,
+                require_owner_signature: None,
+            },
+        }
+    }
+
+    fn attribute(key: &str, value: &str) -> Attribute {
+        Attribute {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_transfer_with_provenance_cpi_order() {
+        let accounts = new_accounts(Some(vec![]));
+        let instruction =
+            TransferWithProvenanceV1::try_from((accounts.as_slice(), new_data())).unwrap();
+
+        take_recorded_cpis();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["TransferV1", "UpdatePluginV1"]);
+    }
+
+    fn transfer_as_delegated_authority(
+        controller: Pubkey,
+        payer: AccountInfo<'static>,
+    ) -> ProgramResult {
+        let (pda, _) = derive_delegated_authority(&controller);
+        let mut accounts = transfer_nft_accounts(
+            new_asset_account(
+                pda,
+                UpdateAuthority::None,
+                &[(
+                    Plugin::Attributes(Attributes {
+                        attribute_list: vec![],
+                    }),
+                    PluginAuthority::Owner,
+                )],
+            ),
+            new_test_account(pda, false, false, 1, 0, system_program::ID),
+        );
+        accounts[4] = payer;
+
+        TransferWithProvenanceV1::try_from((
+            accounts.as_slice(),
+            TransferWithProvenanceV1InstructionData {
+                transfer: TransferNftV1InstructionData {
+                    delegated_authority: true,
+                    ..TransferNftV1InstructionData::default()
+                },
+            },
+        ))?
+        .process()
+    }
+
+    #[test]
+    fn test_transfer_with_provenance_delegated_authority() {
+        let controller = Pubkey::new_unique();
+        let payer = new_test_account(controller, true, true, 1, 0, system_program::ID);
+
+        take_recorded_cpis();
+        let res = transfer_as_delegated_authority(controller, payer);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["TransferV1", "UpdatePluginV1"]);
+    }
+
+    #[test]
+    fn test_transfer_with_provenance_delegated_authority_of_other_controller() {
+        let caller = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+
+        take_recorded_cpis();
+        let res = transfer_as_delegated_authority(Pubkey::new_unique(), caller);
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidSignerSeeds.into()
+        );
+        assert!(take_recorded_cpis().is_empty());
+    }
+
+    #[test]
+    fn test_transfer_with_provenance_increments_count() {
+        let accounts = new_accounts(Some(vec![
+            attribute("color", "red"),
+            attribute(TRANSFER_COUNT_KEY, "4"),
+        ]));
+        let owner = accounts[4].key.to_string();
+        let instruction =
+            TransferWithProvenanceV1::try_from((accounts.as_slice(), new_data())).unwrap();
+
+        assert_eq!(
+            instruction.provenance_attributes().unwrap().attribute_list,
+            vec![
+                attribute("color", "red"),
+                attribute(TRANSFER_COUNT_KEY, "5"),
+                attribute(LAST_OWNER_KEY, &owner),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transfer_with_provenance_first_transfer() {
+        let accounts = new_accounts(Some(vec![]));
+        let owner = accounts[4].key.to_string();
+        let instruction =
+            TransferWithProvenanceV1::try_from((accounts.as_slice(), new_data())).unwrap();
+
+        assert_eq!(
+            instruction.provenance_attributes().unwrap().attribute_list,
+            vec![
+                attribute(LAST_OWNER_KEY, &owner),
+                attribute(TRANSFER_COUNT_KEY, "1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transfer_with_provenance_invalid_count() {
+        let accounts = new_accounts(Some(vec![attribute(TRANSFER_COUNT_KEY, "many")]));
+        let instruction =
+            TransferWithProvenanceV1::try_from((accounts.as_slice(), new_data())).unwrap();
+
+        take_recorded_cpis();
+        assert_eq!(instruction.process(), Err(ProgramError::InvalidAccountData));
+        assert!(take_recorded_cpis().is_empty());
+    }
+
+    #[test]
+    fn test_transfer_with_provenance_missing_attributes() {
+        let accounts = new_accounts(None);
+        let instruction =
+            TransferWithProvenanceV1::try_from((accounts.as_slice(), new_data())).unwrap();
+
+        assert_eq!(
+            instruction.provenance_attributes(),
+            Err(MplCoreWrapperError::PluginNotFound.into())
+        );
+    }
+
+    #[test]
+    fn test_transfer_with_provenance_invalid_system_program() {
+        let mut accounts = new_accounts(Some(vec![]));
+        accounts[5] = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            1,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        let res = TransferWithProvenanceV1::try_from((accounts.as_slice(), new_data()));
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountOwner);
+    }
+}
@@ -0,0 +1,322 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    fetch_asset_plugin,
+    instructions::{AddPluginV1CpiBuilder, UpdatePluginV1CpiBuilder},
+    types::{Attribute, Attributes, Plugin, PluginType},
+};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+#[cfg(feature = "asset-state")]
+use crate::utils::check_collection_account;
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    instructions::Instructions,
+    plugins::{check_plugin_additions_allowed, merge_attributes, MAX_ATTRIBUTES},
+    utils::{
+        invoke_cpi, AccountCheck, MplCoreAccount, NonDefaultKey, NonWritable, NotProgramAccount,
+        OptionalAccountCheck, ProcessInstruction, SignerAccount, SystemAccount, ToOptionalAccount,
+        WritableAccount,
+    },
+};
+
+#[derive(Debug)]
+pub struct UpdateAttributesV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for UpdateAttributesV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, payer, authority, system_program, log_wrapper, mpl_core] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(2, payer)?;
+        NotProgramAccount::check_at(2, payer)?;
+        NonDefaultKey::check_at(3, authority)?;
+        NotProgramAccount::check_at(3, authority)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        WritableAccount::check_at(2, payer)?;
+        SignerAccount::check_at(2, payer)?;
+        SignerAccount::check_at(3, authority)?;
+        SystemAccount::check_at(4, system_program)?;
+        NonWritable::check_optional_at(5, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(6, mpl_core)?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            payer,
+            authority,
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for UpdateAttributesV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            account_meta(self.payer, true, true),
+            account_meta(self.authority, true, false),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct UpdateAttributesV1InstructionData {
+    /// Attributes to set; an existing key is overwritten by the last value given.
+    pub attributes: Vec<Attribute>,
+}
+
+#[derive(Debug)]
+pub struct UpdateAttributesV1<'a, 'info> {
+    pub accounts: UpdateAttributesV1Accounts<'a, 'info>,
+    pub instruction_data: UpdateAttributesV1InstructionData,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], UpdateAttributesV1InstructionData)>
+    for UpdateAttributesV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], UpdateAttributesV1InstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = UpdateAttributesV1Accounts::try_from(accounts)?;
+        #[cfg(feature = "asset-state")]
+        check_collection_account(accounts.asset, accounts.collection)?;
+
+        let instruction_data = UpdateAttributesV1InstructionData {
+            attributes: merge_attributes(vec![], instruction_data.attributes, MAX_ATTRIBUTES)?,
+        };
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a, 'info> UpdateAttributesV1<'a, 'info> {
+    /// Merges the requested attributes into the ones already on the asset.
+    pub fn attributes(&self, existing: Option<Attributes>) -> Result<Attributes, ProgramError> {
+        let existing = existing.map_or_else(Vec::new, |attributes| attributes.attribute_list);
+
+        Ok(Attributes {
+            attribute_list: merge_attributes(
+                existing,
+                self.instruction_data.attributes.clone(),
+                MAX_ATTRIBUTES,
+            )?,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for UpdateAttributesV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        let existing =
+            fetch_asset_plugin::<Attributes>(self.accounts.asset, PluginType::Attributes)
+                .ok()
+                .map(|(_, attributes, _)| attributes);
+
+        if existing.is_some() {
+            invoke_cpi("UpdatePluginV1", self.accounts.asset.key, || {
+                UpdatePluginV1CpiBuilder::new(self.accounts.mpl_core)
+                    .asset(self.accounts.asset)
+                    .collection(self.accounts.collection)
+                    .payer(self.accounts.payer)
+                    .authority(Some(self.accounts.authority))
+                    .system_program(self.accounts.system_program)
+                    .log_wrapper(self.accounts.log_wrapper)
+                    .plugin(Plugin::Attributes(self.attributes(existing)?))
+                    .invoke()
+            })?;
+        } else {
+            check_plugin_additions_allowed(self.accounts.asset)?;
+
+            invoke_cpi("AddPluginV1", self.accounts.asset.key, || {
+                AddPluginV1CpiBuilder::new(self.accounts.mpl_core)
+                    .asset(self.accounts.asset)
+                    .collection(self.accounts.collection)
+                    .payer(self.accounts.payer)
+                    .authority(Some(self.accounts.authority))
+                    .system_program(self.accounts.system_program)
+                    .log_wrapper(self.accounts.log_wrapper)
+                    .plugin(Plugin::Attributes(self.attributes(None)?))
+                    .invoke()
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn process_update_attributes_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::UpdateAttributesV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    UpdateAttributesV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::MplCoreWrapperError, utils::test_utils::*};
+    use mpl_core::types::UpdateAuthority;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts() -> Vec<AccountInfo<'static>> {
+        let asset = new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]);
+        let collection =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let authority =
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        let system_program =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let log_wrapper =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        vec![
+            asset,
+            collection,
+            payer,
+            authority,
+            system_program,
+            log_wrapper,
+            mpl_core,
+        ]
+    }
+
+    fn attribute(key: &str, value: &str) -> Attribute {
+        Attribute {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_update_attributes_dedups_keys() {
+        let accounts = new_accounts();
+        let instruction = UpdateAttributesV1::try_from((
+            accounts.as_slice(),
+            UpdateAttributesV1InstructionData {
+                attributes: vec![
+                    attribute("color", "red"),
+                    attribute("size", "m"),
+                    attribute("color", "blue"),
+                ],
+            },
+        ))
+        .unwrap();
+
+        assert_eq!(
+            instruction.instruction_data.attributes,
+            vec![attribute("color", "blue"), attribute("size", "m")]
+        );
+
+        let attributes = instruction
+            .attributes(Some(Attributes {
+                attribute_list: vec![attribute("size", "s"), attribute("rarity", "rare")],
+            }))
+            .unwrap();
+        assert_eq!(
+            attributes.attribute_list,
+            vec![
+                attribute("size", "m"),
+                attribute("rarity", "rare"),
+                attribute("color", "blue"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_attributes_too_many() {
+        let accounts = new_accounts();
+        let res = UpdateAttributesV1::try_from((
+            accounts.as_slice(),
+            UpdateAttributesV1InstructionData {
+                attributes: (0..=MAX_ATTRIBUTES)
+                    .map(|i| attribute(&i.to_string(), "v"))
+                    .collect(),
+            },
+        ));
+
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::TooManyAttributes.into()
+        );
+    }
+
+    #[test]
+    fn test_update_attributes_merged_over_limit() {
+        let accounts = new_accounts();
+        let instruction = UpdateAttributesV1::try_from((
+            accounts.as_slice(),
+            UpdateAttributesV1InstructionData {
+                attributes: vec![attribute("new", "v")],
+            },
+        ))
+        .unwrap();
+
+        let existing = Attributes {
+            attribute_list: (0..MAX_ATTRIBUTES)
+                .map(|i| attribute(&i.to_string(), "v"))
+                .collect(),
+        };
+        assert_eq!(
+            instruction.attributes(Some(existing)).unwrap_err(),
+            MplCoreWrapperError::TooManyAttributes.into()
+        );
+    }
+
+    #[test]
+    fn test_update_attributes_account_not_enough_accounts() {
+        let accounts = vec![];
+        let res = UpdateAttributesV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_update_attributes_account_metas() {
+        let accounts = new_accounts();
+        let metas = UpdateAttributesV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            UpdateAttributesV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
@@ -0,0 +1,348 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    instructions::UpdateExternalPluginAdapterV1CpiBuilder,
+    types::{
+        ExternalCheckResult, ExternalPluginAdapterKey, ExternalPluginAdapterUpdateInfo,
+        HookableLifecycleEvent, LifecycleHookUpdateInfo, LinkedLifecycleHookUpdateInfo,
+        OracleUpdateInfo,
+    },
+};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+#[cfg(feature = "asset-state")]
+use crate::utils::check_collection_account;
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::Instructions,
+    utils::{
+        invoke_cpi, AccountCheck, MplCoreAccount, NonDefaultKey, NonWritable, NotProgramAccount,
+        OptionalAccountCheck, ProcessInstruction, SignerAccount, SystemAccount, ToOptionalAccount,
+        WritableAccount,
+    },
+};
+
+#[derive(Debug)]
+pub struct UpdateExternalPluginAdapterV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub authority: Option<&'a AccountInfo<'info>>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]>
+    for UpdateExternalPluginAdapterV1Accounts<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, payer, authority, system_program, log_wrapper, mpl_core] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(2, payer)?;
+        NotProgramAccount::check_at(2, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        WritableAccount::check_at(2, payer)?;
+        SignerAccount::check_at(2, payer)?;
+        SignerAccount::check_optional_at(3, authority.to_optional())?;
+        SystemAccount::check_at(4, system_program)?;
+        NonWritable::check_optional_at(5, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(6, mpl_core)?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            payer,
+            authority: authority.to_optional(),
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for UpdateExternalPluginAdapterV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            account_meta(self.payer, true, true),
+            optional_account_meta(self.authority, true, false),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct UpdateExternalPluginAdapterV1InstructionData {
+    pub key: ExternalPluginAdapterKey,
+    /// Replaces the adapter's lifecycle checks; other adapter settings are kept.
+    pub lifecycle_checks: Vec<(HookableLifecycleEvent, ExternalCheckResult)>,
+}
+
+impl UpdateExternalPluginAdapterV1InstructionData {
+    /// Update info for the adapter `key` that only sets its lifecycle checks.
+    ///
+    /// App data adapters and data sections don't hook lifecycle events, and an
+    /// oracle needs at least one check, as at create.
+    pub fn update_info(&self) -> Result<ExternalPluginAdapterUpdateInfo, ProgramError> {
+        let lifecycle_checks = Some(self.lifecycle_checks.clone());
+
+        match self.key {
+            ExternalPluginAdapterKey::LifecycleHook(_) => Ok(
+                ExternalPluginAdapterUpdateInfo::LifecycleHook(LifecycleHookUpdateInfo {
+                    lifecycle_checks,
+                    extra_accounts: None,
+                    schema: None,
+                }),
+            ),
+            ExternalPluginAdapterKey::Oracle(_) => {
+                if self.lifecycle_checks.is_empty() {
+                    return Err(MplCoreWrapperError::EmptyOracleChecks.into());
+                }
+
+                Ok(ExternalPluginAdapterUpdateInfo::Oracle(OracleUpdateInfo {
+                    lifecycle_checks,
+                    base_address_config: None,
+                    results_offset: None,
+                }))
+            }
+            ExternalPluginAdapterKey::LinkedLifecycleHook(_) => {
+                Ok(ExternalPluginAdapterUpdateInfo::LinkedLifecycleHook(
+                    LinkedLifecycleHookUpdateInfo {
+                        lifecycle_checks,
+                        extra_accounts: None,
+                        schema: None,
+                    },
+                ))
+            }
+            ExternalPluginAdapterKey::AppData(_)
+            | ExternalPluginAdapterKey::LinkedAppData(_)
+            | ExternalPluginAdapterKey::DataSection(_) => {
+                Err(MplCoreWrapperError::AdapterWithoutLifecycleChecks.into())
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UpdateExternalPluginAdapterV1<'a, 'info> {
+    pub accounts: UpdateExternalPluginAdapterV1Accounts<'a, 'info>,
+    pub instruction_data: UpdateExternalPluginAdapterV1InstructionData,
+}
+
+impl<'a, 'info>
+    TryFrom<(
+        &'a [AccountInfo<'info>],
+        UpdateExternalPluginAdapterV1InstructionData,
+    )> for UpdateExternalPluginAdapterV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (
+            &'a [AccountInfo<'info>],
+            UpdateExternalPluginAdapterV1InstructionData,
+        ),
+    ) -> Result<Self, Self::Error> {
+        let accounts = UpdateExternalPluginAdapterV1Accounts::try_from(accounts)?;
+        #[cfg(feature = "asset-state")]
+        check_collection_account(accounts.asset, accounts.collection)?;
+        instruction_data.update_info()?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for UpdateExternalPluginAdapterV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        invoke_cpi(
+            "UpdateExternalPluginAdapterV1",
+            self.accounts.asset.key,
+            || {
+                UpdateExternalPluginAdapterV1CpiBuilder::new(self.accounts.mpl_core)
+                    .asset(self.accounts.asset)
+                    .collection(self.accounts.collection)
+                    .payer(self.accounts.payer)
+                    .authority(self.accounts.authority)
+                    .system_program(self.accounts.system_program)
+                    .log_wrapper(self.accounts.log_wrapper)
+                    .update_info(self.instruction_data.update_info()?)
+                    .key(self.instruction_data.key)
+                    .invoke()
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+pub fn process_update_external_plugin_adapter_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::UpdateExternalPluginAdapterV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    UpdateExternalPluginAdapterV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::{PluginAuthority, UpdateAuthority};
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts() -> Vec<AccountInfo<'static>> {
+        vec![
+            new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]),
+            absent_account(),
+            new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+            absent_account(),
+            new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+        ]
+    }
+
+    fn transfer_check() -> Vec<(HookableLifecycleEvent, ExternalCheckResult)> {
+        vec![(
+            HookableLifecycleEvent::Transfer,
+            ExternalCheckResult { flags: 4 },
+        )]
+    }
+
+    fn new_data(key: ExternalPluginAdapterKey) -> UpdateExternalPluginAdapterV1InstructionData {
+        UpdateExternalPluginAdapterV1InstructionData {
+            key,
+            lifecycle_checks: transfer_check(),
+        }
+    }
+
+    #[test]
+    fn test_update_external_plugin_adapter_account_success() {
+        let accounts = new_accounts();
+        let res = UpdateExternalPluginAdapterV1Accounts::try_from(accounts.as_slice());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_update_external_plugin_adapter_account_payer_not_signer() {
+        let mut accounts = new_accounts();
+        accounts[2] = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
+
+        let res = UpdateExternalPluginAdapterV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[test]
+    fn test_update_external_plugin_adapter_account_wrong_mpl_core() {
+        let mut accounts = new_accounts();
+        accounts[6] = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            1,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        let res = UpdateExternalPluginAdapterV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountOwner);
+    }
+
+    #[test]
+    fn test_update_external_plugin_adapter_forwards_checks() {
+        let accounts = new_accounts();
+        let instruction = UpdateExternalPluginAdapterV1::try_from((
+            accounts.as_slice(),
+            new_data(ExternalPluginAdapterKey::Oracle(Pubkey::new_unique())),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            instruction.instruction_data.update_info(),
+            Ok(ExternalPluginAdapterUpdateInfo::Oracle(OracleUpdateInfo {
+                lifecycle_checks: Some(transfer_check()),
+                base_address_config: None,
+                results_offset: None,
+            }))
+        );
+
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_update_external_plugin_adapter_empty_oracle_checks() {
+        let accounts = new_accounts();
+        let res = UpdateExternalPluginAdapterV1::try_from((
+            accounts.as_slice(),
+            UpdateExternalPluginAdapterV1InstructionData {
+                key: ExternalPluginAdapterKey::Oracle(Pubkey::new_unique()),
+                lifecycle_checks: vec![],
+            },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::EmptyOracleChecks.into()
+        );
+    }
+
+    #[test]
+    fn test_update_external_plugin_adapter_app_data_rejected() {
+        let accounts = new_accounts();
+        let res = UpdateExternalPluginAdapterV1::try_from((
+            accounts.as_slice(),
+            new_data(ExternalPluginAdapterKey::AppData(
+                PluginAuthority::UpdateAuthority,
+            )),
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AdapterWithoutLifecycleChecks.into()
+        );
+    }
+
+    #[test]
+    fn test_update_external_plugin_adapter_account_not_enough_accounts() {
+        let accounts = vec![];
+        let res = UpdateExternalPluginAdapterV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_update_external_plugin_adapter_account_metas() {
+        let accounts = new_accounts();
+        let metas = UpdateExternalPluginAdapterV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            UpdateExternalPluginAdapterV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
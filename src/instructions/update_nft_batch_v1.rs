@@ -0,0 +1,306 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+#[cfg(feature = "ledger-state")]
+use crate::utils::is_ledger_state;
+#[cfg(feature = "client")]
+use crate::utils::{
+    account_meta, optional_account_meta, optional_system_program_meta, ToAccountMetas,
+};
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::{Instructions, UpdateNftV1, UpdateNftV1Accounts, UpdateNftV1InstructionData},
+    utils::{
+        check_cpi_programs, optional_system_program, split_accounts, AccountCheck, MplCoreAccount,
+        NonDefaultKey, NonWritable, NotProgramAccount, OptionalAccountCheck, ProcessInstruction,
+        SignerAccount, SystemAccount, ToOptionalAccount, WritableAccount,
+    },
+};
+
+/// Upper bound on updates per batch so the CPIs fit in the compute budget.
+pub const MAX_UPDATE_BATCH: usize = 8;
+
+#[derive(Debug)]
+pub struct UpdateNftBatchV1Accounts<'a, 'info> {
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub authority: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: Option<&'a AccountInfo<'info>>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+    /// One asset per update, in the order of the instruction data.
+    pub assets: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for UpdateNftBatchV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [collection, authority, payer, system_program, log_wrapper, mpl_core, assets @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(2, payer)?;
+        NotProgramAccount::check_at(2, payer)?;
+        WritableAccount::check_optional_at(0, collection.to_optional())?;
+        SignerAccount::check_optional_at(1, authority.to_optional())?;
+        WritableAccount::check_at(2, payer)?;
+        SignerAccount::check_at(2, payer)?;
+        SystemAccount::check_optional_at(3, optional_system_program(system_program))?;
+        NonWritable::check_optional_at(4, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(5, mpl_core)?;
+        check_cpi_programs(accounts, mpl_core, log_wrapper.to_optional())?;
+
+        let offset = accounts.len() - assets.len();
+        for (i, asset) in assets.iter().enumerate() {
+            NonDefaultKey::check_at(offset + i, asset)?;
+            NotProgramAccount::check_at(offset + i, asset)?;
+            WritableAccount::check_at(offset + i, asset)?;
+
+            #[cfg(feature = "reject-compressed")]
+            if is_ledger_state(asset) {
+                return Err(MplCoreWrapperError::AssetIsCompressed.into());
+            }
+
+            #[cfg(feature = "ledger-state")]
+            if is_ledger_state(asset) && log_wrapper.to_optional().is_none() {
+                return Err(MplCoreWrapperError::MissingLogWrapper.into());
+            }
+        }
+
+        Ok(Self {
+            collection: collection.to_optional(),
+            authority: authority.to_optional(),
+            payer,
+            system_program: optional_system_program(system_program),
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+            assets,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for UpdateNftBatchV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        let mut metas = vec![
+            optional_account_meta(self.collection, false, true),
+            optional_account_meta(self.authority, true, false),
+            account_meta(self.payer, true, true),
+            optional_system_program_meta(self.system_program),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ];
+
+        metas.extend(
+            self.assets
+                .iter()
+                .map(|asset| account_meta(asset, false, true)),
+        );
+
+        metas
+    }
+}
+
+impl<'a, 'info> UpdateNftBatchV1Accounts<'a, 'info> {
+    /// `UpdateNftV1` accounts of the `i`th asset; batches don't move assets
+    /// between collections.
+    pub fn update_accounts(&self, i: usize) -> UpdateNftV1Accounts<'a, 'info> {
+        UpdateNftV1Accounts {
+            asset: &self.assets[i],
+            collection: self.collection,
+            authority: self.authority,
+            payer: self.payer,
+            system_program: self.system_program,
+            log_wrapper: self.log_wrapper,
+            mpl_core: self.mpl_core,
+            new_collection: None,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct UpdateNftBatchV1InstructionData {
+    pub updates: Vec<UpdateNftV1InstructionData>,
+}
+
+#[derive(Debug)]
+pub struct UpdateNftBatchV1<'a, 'info> {
+    pub updates: Vec<UpdateNftV1<'a, 'info>>,
+}
+
+impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], UpdateNftBatchV1InstructionData)>
+    for UpdateNftBatchV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo<'info>], UpdateNftBatchV1InstructionData),
+    ) -> Result<Self, Self::Error> {
+        let count = instruction_data.updates.len();
+        if count == 0 || count > MAX_UPDATE_BATCH {
+            return Err(MplCoreWrapperError::InvalidBatchSize.into());
+        }
+
+        let accounts = UpdateNftBatchV1Accounts::try_from(accounts)?;
+
+        // One asset account per update, with none left over.
+        split_accounts(accounts.assets, &vec![1; count])?;
+
+        let updates = instruction_data
+            .updates
+            .into_iter()
+            .enumerate()
+            .map(|(i, update)| UpdateNftV1::new(accounts.update_accounts(i), update))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { updates })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for UpdateNftBatchV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        for update in self.updates {
+            update.process()?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn process_update_nft_batch_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::UpdateNftBatchV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    UpdateNftBatchV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::UpdateAuthority;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(asset_count: usize) -> Vec<AccountInfo<'static>> {
+        let mut accounts = vec![
+            absent_account(),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+            absent_account(),
+            new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+        ];
+
+        for _ in 0..asset_count {
+            accounts.push(new_asset_account(
+                Pubkey::new_unique(),
+                UpdateAuthority::None,
+                &[],
+            ));
+        }
+
+        accounts
+    }
+
+    fn new_update(uri: &str) -> UpdateNftV1InstructionData {
+        UpdateNftV1InstructionData {
+            new_name: None,
+            new_uri: Some(uri.to_string()),
+            uri_suffix: None,
+            delegated_authority: false,
+            approved_authorities: None,
+            new_collection: None,
+            expected_name: None,
+            expected_uri: None,
+        }
+    }
+
+    fn new_data(count: usize) -> UpdateNftBatchV1InstructionData {
+        UpdateNftBatchV1InstructionData {
+            updates: (0..count)
+                .map(|i| new_update(&format!("https://example.com/{i}.json")))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_update_nft_batch_three_assets() {
+        let accounts = new_accounts(3);
+
+        let instruction = UpdateNftBatchV1::try_from((accounts.as_slice(), new_data(3))).unwrap();
+
+        assert_eq!(instruction.updates.len(), 3);
+        for (update, asset) in instruction.updates.iter().zip(accounts[6..].iter()) {
+            assert_eq!(update.accounts.asset.key, asset.key);
+        }
+
+        take_recorded_cpis();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["UpdateV2"; 3]);
+    }
+
+    #[test]
+    fn test_update_nft_batch_count_mismatch() {
+        let accounts = new_accounts(2);
+
+        let res = UpdateNftBatchV1::try_from((accounts.as_slice(), new_data(3)));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AccountLayoutMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_update_nft_batch_too_large() {
+        let accounts = new_accounts(MAX_UPDATE_BATCH + 1);
+
+        let res = UpdateNftBatchV1::try_from((accounts.as_slice(), new_data(MAX_UPDATE_BATCH + 1)));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidBatchSize.into()
+        );
+    }
+
+    #[test]
+    fn test_update_nft_batch_rejects_new_collection() {
+        let accounts = new_accounts(1);
+        let data = UpdateNftBatchV1InstructionData {
+            updates: vec![UpdateNftV1InstructionData {
+                new_collection: Some(Pubkey::new_unique()),
+                ..new_update("https://example.com/moved.json")
+            }],
+        };
+
+        let res = UpdateNftBatchV1::try_from((accounts.as_slice(), data));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InconsistentOptionalAccounts.into()
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_update_nft_batch_account_metas() {
+        let accounts = new_accounts(2);
+        let metas = UpdateNftBatchV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            UpdateNftBatchV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
@@ -1,13 +1,44 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use mpl_core::instructions::UpdateV1CpiBuilder;
+use mpl_core::{
+    accounts::BaseAssetV1,
+    instructions::UpdateV2CpiBuilder,
+    types::{Key, UpdateAuthority},
+};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+#[cfg(feature = "asset-state")]
+use mpl_core::{
+    fetch_asset_plugin,
+    types::{ImmutableMetadata, PluginType},
 };
 
+#[cfg(feature = "asset-state")]
+use crate::utils::is_collection_authority;
+#[cfg(feature = "ledger-state")]
+use crate::utils::is_ledger_state;
+#[cfg(feature = "client")]
 use crate::utils::{
-    AccountCheck, MplCoreAccount, OptionalAccountCheck, ProcessInstruction, SignerAccount,
-    SystemAccount, ToOptionalAccount, WritableAccount,
+    account_meta, optional_account_meta, optional_system_program_meta, ToAccountMetas,
 };
+use crate::{
+    error::MplCoreWrapperError,
+    events::{MplCoreWrapperEvent, UpdateNftEvent},
+    instructions::Instructions,
+    utils::{
+        assert_account_key, check_approved_authority, check_metadata_text, invoke_cpi,
+        optional_system_program, resolve_authority, AccountCheckBatch, MinDataLen, MplCoreAccount,
+        NonDefaultKey, NonWritable, NotProgramAccount, ProcessInstruction, SignerAccount,
+        SystemAccount, ToOptionalAccount, WritableAccount, BASE_ASSET_MIN_LEN,
+    },
+};
+
+/// Longest uri mpl_core accepts on an asset.
+pub const MAX_URI_LEN: usize = 200;
 
 #[derive(Debug)]
 pub struct UpdateNftV1Accounts<'a, 'info> {
@@ -15,50 +46,231 @@ pub struct UpdateNftV1Accounts<'a, 'info> {
     pub collection: Option<&'a AccountInfo<'info>>,
     pub authority: Option<&'a AccountInfo<'info>>,
     pub payer: &'a AccountInfo<'info>,
-    pub system_program: &'a AccountInfo<'info>,
+    /// Absent when the asset needs no rent moved, in which case the slot
+    /// holds mpl_core and is passed on as such.
+    pub system_program: Option<&'a AccountInfo<'info>>,
     pub log_wrapper: Option<&'a AccountInfo<'info>>,
     pub mpl_core: &'a AccountInfo<'info>,
+    /// Collection the asset moves to; `authority` must be its update
+    /// authority or an update delegate of it.
+    pub new_collection: Option<&'a AccountInfo<'info>>,
 }
 
 impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for UpdateNftV1Accounts<'a, 'info> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
-        let [asset, collection, authority, payer, system_program, log_wrapper, mpl_core] = accounts
+        let [asset, collection, authority, payer, system_program, log_wrapper, mpl_core, new_collection @ ..] =
+            accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        WritableAccount::check(asset)?;
-        WritableAccount::check(collection)?;
-        SignerAccount::check_optional(authority.to_optional())?;
-        WritableAccount::check(payer)?;
-        SignerAccount::check(payer)?;
-        SystemAccount::check(system_program)?;
-        MplCoreAccount::check(mpl_core)?;
+        // Every broken slot is logged before failing with the first error.
+        let mut checks = AccountCheckBatch::new();
+        checks
+            .check_at::<NonDefaultKey>(0, asset)
+            .check_at::<NotProgramAccount>(0, asset)
+            .check_at::<NonDefaultKey>(3, payer)
+            .check_at::<NotProgramAccount>(3, payer)
+            .check_at::<WritableAccount>(0, asset)
+            .check_at::<WritableAccount>(1, collection)
+            .check_at::<WritableAccount>(3, payer)
+            .check_at::<SignerAccount>(3, payer)
+            .check_optional_at::<SystemAccount>(4, optional_system_program(system_program))
+            .check_optional_at::<NonWritable>(5, log_wrapper.to_optional())
+            .check_at::<MplCoreAccount>(6, mpl_core)
+            .check_optional_at::<WritableAccount>(7, new_collection.to_optional());
+        checks.finish()?;
+
+        // mpl_core only updates a compressed asset after it is decompressed.
+        #[cfg(feature = "reject-compressed")]
+        if is_ledger_state(asset) {
+            return Err(MplCoreWrapperError::AssetIsCompressed.into());
+        }
+
+        #[cfg(feature = "ledger-state")]
+        if is_ledger_state(asset) && log_wrapper.to_optional().is_none() {
+            return Err(MplCoreWrapperError::MissingLogWrapper.into());
+        }
 
         Ok(Self {
             asset,
             collection: collection.to_optional(),
             authority: authority.to_optional(),
             payer,
-            system_program,
+            system_program: optional_system_program(system_program),
             log_wrapper: log_wrapper.to_optional(),
             mpl_core,
+            new_collection: new_collection.to_optional(),
         })
     }
 }
 
+#[cfg(feature = "client")]
+impl ToAccountMetas for UpdateNftV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            optional_account_meta(self.authority, true, false),
+            account_meta(self.payer, true, true),
+            optional_system_program_meta(self.system_program),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+            optional_account_meta(self.new_collection, false, true),
+        ]
+    }
+}
+
+#[cfg(feature = "asset-state")]
+impl<'a, 'info> UpdateNftV1Accounts<'a, 'info> {
+    /// Fails fast when the asset carries an ImmutableMetadata plugin, which
+    /// mpl_core would otherwise reject inside the CPI.
+    pub fn check_metadata_mutable(&self) -> ProgramResult {
+        if fetch_asset_plugin::<ImmutableMetadata>(self.asset, PluginType::ImmutableMetadata)
+            .is_ok()
+        {
+            return Err(MplCoreWrapperError::MetadataImmutable.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the authority (the payer when none is given) is the update
+    /// authority, or an update delegate, of both the current and the new
+    /// collection, as mpl_core checks it against both in the CPI.
+    pub fn check_collection_authorities(&self) -> ProgramResult {
+        let Some(new_collection) = self.new_collection else {
+            return Ok(());
+        };
+        let authority = self.authority.unwrap_or(self.payer);
+
+        for collection in self.collection.into_iter().chain([new_collection]) {
+            if !is_collection_authority(collection, authority.key)? {
+                return Err(MplCoreWrapperError::AuthorityMismatch.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct UpdateNftV1InstructionData {
     pub new_name: Option<String>,
     pub new_uri: Option<String>,
+    /// Appended to the asset's current uri; conflicts with `new_uri`.
+    pub uri_suffix: Option<String>,
+    /// When `true`, `authority` is the payer's delegated authority PDA of
+    /// this program, which the program signs for.
+    pub delegated_authority: bool,
+    /// When set, `authority` must be one of these keys.
+    pub approved_authorities: Option<Vec<Pubkey>>,
+    /// Moves the asset to this collection; must match the `new_collection` account.
+    pub new_collection: Option<Pubkey>,
+    /// Name the asset must still have for the update to apply. Only checked
+    /// with the `asset-state` feature.
+    pub expected_name: Option<String>,
+    /// Uri the asset must still have for the update to apply. Only checked
+    /// with the `asset-state` feature.
+    pub expected_uri: Option<String>,
 }
 
 #[derive(Debug)]
 pub struct UpdateNftV1<'a, 'info> {
     pub accounts: UpdateNftV1Accounts<'a, 'info>,
     pub instruction_data: UpdateNftV1InstructionData,
+    /// Seeds, bump included, the program signs for `authority` with.
+    pub authority_seeds: Option<Vec<Vec<u8>>>,
+}
+
+impl UpdateNftV1InstructionData {
+    /// Whether the update touches the asset's name or uri, which an
+    /// ImmutableMetadata plugin forbids; a collection move alone does not.
+    pub fn changes_metadata(&self) -> bool {
+        self.new_name.is_some() || self.new_uri.is_some() || self.uri_suffix.is_some()
+    }
+
+    /// Folds `uri_suffix` into `new_uri` using the asset's current uri.
+    pub fn resolve_uri(self, asset: &AccountInfo) -> Result<Self, ProgramError> {
+        let Some(suffix) = self.uri_suffix else {
+            return Ok(self);
+        };
+
+        if self.new_uri.is_some() {
+            return Err(MplCoreWrapperError::ConflictingUriUpdate.into());
+        }
+
+        MinDataLen::check(asset, BASE_ASSET_MIN_LEN)?;
+        assert_account_key(asset, Key::AssetV1)?;
+        let asset = BaseAssetV1::from_bytes(&asset.try_borrow_data()?)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let uri = asset.uri + &suffix;
+
+        if uri.len() > MAX_URI_LEN {
+            return Err(MplCoreWrapperError::UriTooLong.into());
+        }
+
+        Ok(Self {
+            new_uri: Some(uri),
+            uri_suffix: None,
+            ..self
+        })
+    }
+}
+
+#[cfg(feature = "asset-state")]
+impl UpdateNftV1InstructionData {
+    /// Rejects the update when the asset's name or uri no longer matches the
+    /// value the client last read, so concurrent updates aren't clobbered.
+    pub fn check_expected_metadata(&self, asset: &AccountInfo) -> ProgramResult {
+        if self.expected_name.is_none() && self.expected_uri.is_none() {
+            return Ok(());
+        }
+
+        MinDataLen::check(asset, BASE_ASSET_MIN_LEN)?;
+        assert_account_key(asset, Key::AssetV1)?;
+        let asset = BaseAssetV1::from_bytes(&asset.try_borrow_data()?)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        let name_changed = self
+            .expected_name
+            .as_ref()
+            .is_some_and(|name| name != &asset.name);
+        let uri_changed = self
+            .expected_uri
+            .as_ref()
+            .is_some_and(|uri| uri != &asset.uri);
+        if name_changed || uri_changed {
+            return Err(MplCoreWrapperError::ConcurrentModification.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "reject-noop-update")]
+impl UpdateNftV1InstructionData {
+    /// Rejects a `new_name` or `new_uri` equal to the asset's current value,
+    /// which mpl_core would still charge compute to write.
+    pub fn check_effective_change(&self, asset: &AccountInfo) -> ProgramResult {
+        if self.new_name.is_none() && self.new_uri.is_none() {
+            return Ok(());
+        }
+
+        MinDataLen::check(asset, BASE_ASSET_MIN_LEN)?;
+        assert_account_key(asset, Key::AssetV1)?;
+        let asset = BaseAssetV1::from_bytes(&asset.try_borrow_data()?)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if self.new_name.as_ref() == Some(&asset.name) || self.new_uri.as_ref() == Some(&asset.uri)
+        {
+            return Err(MplCoreWrapperError::NoEffectiveChange.into());
+        }
+
+        Ok(())
+    }
 }
 
 impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], UpdateNftV1InstructionData)>
@@ -69,27 +281,85 @@ impl<'a, 'info> TryFrom<(&'a [AccountInfo<'info>], UpdateNftV1InstructionData)>
     fn try_from(
         (accounts, instruction_data): (&'a [AccountInfo<'info>], UpdateNftV1InstructionData),
     ) -> Result<Self, Self::Error> {
-        let accounts = UpdateNftV1Accounts::try_from(accounts)?;
+        Self::new(UpdateNftV1Accounts::try_from(accounts)?, instruction_data)
+    }
+}
+
+impl<'a, 'info> UpdateNftV1<'a, 'info> {
+    /// Checks `instruction_data` against already parsed accounts, so batches
+    /// can share one set of accounts across updates.
+    pub fn new(
+        accounts: UpdateNftV1Accounts<'a, 'info>,
+        instruction_data: UpdateNftV1InstructionData,
+    ) -> Result<Self, ProgramError> {
+        let authority_seeds = resolve_authority(
+            accounts.authority,
+            accounts.payer,
+            instruction_data.delegated_authority,
+        )?;
+        if let Some(approved) = &instruction_data.approved_authorities {
+            check_approved_authority(accounts.authority, approved)?;
+        }
+        if instruction_data.new_collection.as_ref() != accounts.new_collection.map(|a| a.key) {
+            return Err(MplCoreWrapperError::InconsistentOptionalAccounts.into());
+        }
+        #[cfg(feature = "asset-state")]
+        if instruction_data.changes_metadata() {
+            accounts.check_metadata_mutable()?;
+        }
+        #[cfg(feature = "asset-state")]
+        accounts.check_collection_authorities()?;
+        #[cfg(feature = "asset-state")]
+        instruction_data.check_expected_metadata(accounts.asset)?;
+        let instruction_data = instruction_data.resolve_uri(accounts.asset)?;
+        #[cfg(feature = "reject-noop-update")]
+        instruction_data.check_effective_change(accounts.asset)?;
+        for text in [&instruction_data.new_name, &instruction_data.new_uri]
+            .into_iter()
+            .flatten()
+        {
+            check_metadata_text(text)?;
+        }
 
         Ok(Self {
             accounts,
             instruction_data,
+            authority_seeds,
+        })
+    }
+
+    pub fn event(&self) -> MplCoreWrapperEvent {
+        MplCoreWrapperEvent::UpdateNft(UpdateNftEvent {
+            asset: *self.accounts.asset.key,
+            new_name: self.instruction_data.new_name.clone(),
+            new_uri: self.instruction_data.new_uri.clone(),
         })
     }
 }
 
 impl<'a, 'info> ProcessInstruction for UpdateNftV1<'a, 'info> {
     fn process(self) -> ProgramResult {
-        let mut update_cpi = UpdateV1CpiBuilder::new(self.accounts.mpl_core);
+        let event = self.event();
+
+        let mut update_cpi = UpdateV2CpiBuilder::new(self.accounts.mpl_core);
 
         update_cpi
             .asset(self.accounts.asset)
             .collection(self.accounts.collection)
             .authority(self.accounts.authority)
             .payer(self.accounts.payer)
-            .system_program(self.accounts.system_program)
+            .new_collection(self.accounts.new_collection)
+            .system_program(
+                self.accounts
+                    .system_program
+                    .unwrap_or(self.accounts.mpl_core),
+            )
             .log_wrapper(self.accounts.log_wrapper);
 
+        if let Some(collection) = self.instruction_data.new_collection {
+            update_cpi.new_update_authority(UpdateAuthority::Collection(collection));
+        }
+
         if let Some(name) = self.instruction_data.new_name {
             update_cpi.new_name(name);
         }
@@ -98,42 +368,41 @@ impl<'a, 'info> ProcessInstruction for UpdateNftV1<'a, 'info> {
             update_cpi.new_uri(uri);
         }
 
-        update_cpi.invoke()?;
+        invoke_cpi("UpdateV2", self.accounts.asset.key, || {
+            match self.authority_seeds {
+                Some(seeds) => {
+                    let seeds = seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
+                    update_cpi.invoke_signed(&[&seeds])
+                }
+                None => update_cpi.invoke(),
+            }
+        })?;
 
-        Ok(())
+        event.emit()
     }
 }
 
+pub fn process_update_nft_v1(accounts: &[AccountInfo], instruction: Instructions) -> ProgramResult {
+    let Instructions::UpdateNftV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    UpdateNftV1::try_from((accounts, instruction_data))?.process()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::test_utils::*;
+    use crate::{
+        error::MplCoreWrapperError,
+        utils::{derive_delegated_authority, test_utils::*},
+    };
     use solana_program::pubkey::Pubkey;
     use solana_sdk_ids::system_program;
 
     #[test]
     fn test_create_nft_account_success() {
-        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let collection =
-            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
-        let system_program =
-            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
-        let log_wrapper =
-            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
-        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
-
-        let accounts = vec![
-            asset,
-            collection,
-            authority,
-            payer,
-            system_program,
-            log_wrapper,
-            mpl_core,
-        ];
+        let accounts = update_nft_accounts(Pubkey::new_unique());
 
         let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
         assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
@@ -141,13 +410,8 @@ mod tests {
 
     #[test]
     fn test_create_nft_account_wrong_system_program() {
-        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let collection =
-            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
-        let bad_system_program = new_test_account(
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        accounts[4] = new_test_account(
             Pubkey::new_unique(),
             false,
             false,
@@ -155,41 +419,36 @@ mod tests {
             0,
             Pubkey::new_unique(),
         );
-        let log_wrapper =
-            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
-        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
 
-        let accounts = vec![
-            asset,
-            collection,
-            authority,
-            payer,
-            bad_system_program,
-            log_wrapper,
-            mpl_core,
-        ];
+        let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountOwner);
+    }
+
+    #[test]
+    fn test_update_nft_account_present_system_program() {
+        let accounts = update_nft_accounts(Pubkey::new_unique());
 
         let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
-        assert!(
-            res.is_err(),
-            "expected failure because system_program was wrong, but got Ok: {:?}",
-            res,
+        assert_eq!(
+            res.unwrap().system_program.map(|account| *account.key),
+            Some(system_program::ID)
         );
     }
 
+    #[test]
+    fn test_update_nft_account_absent_system_program() {
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        accounts[4] = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert!(res.unwrap().system_program.is_none());
+    }
+
     #[test]
     fn test_create_nft_account_wrong_mpl_core() {
-        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let collection =
-            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
-        let authority =
-            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
-        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
-        let system_program =
-            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
-        let log_wrapper =
-            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
-        let bad_mpl_core = new_test_account(
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        accounts[6] = new_test_account(
             Pubkey::new_unique(),
             false,
             false,
@@ -198,21 +457,579 @@ mod tests {
             Pubkey::new_unique(),
         );
 
-        let accounts = vec![
-            asset,
-            collection,
+        let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountOwner);
+    }
+
+    #[test]
+    fn test_update_nft_account_program_as_asset() {
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+
+        accounts[0] = new_test_account(mpl_core::ID, false, true, 1, 0, mpl_core::ID);
+        let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::ProgramAccountInWrongSlot.into()
+        );
+    }
+
+    #[cfg(feature = "ledger-state")]
+    fn new_ledger_state_accounts(log_wrapper: Pubkey) -> Vec<AccountInfo<'static>> {
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        accounts[0] = new_test_account(Pubkey::new_unique(), false, true, 1, 1, mpl_core::ID);
+        accounts[0].data.borrow_mut()[0] = mpl_core::types::Key::HashedAssetV1 as u8;
+        accounts[5] = new_test_account(log_wrapper, false, false, 1, 0, system_program::ID);
+        accounts
+    }
+
+    #[cfg(all(feature = "ledger-state", not(feature = "reject-compressed")))]
+    #[test]
+    fn test_update_nft_ledger_state_with_log_wrapper() {
+        let accounts = new_ledger_state_accounts(Pubkey::new_unique());
+        let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[cfg(feature = "reject-compressed")]
+    #[test]
+    fn test_update_nft_compressed_asset_rejected() {
+        let accounts = new_ledger_state_accounts(Pubkey::new_unique());
+        let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::AssetIsCompressed.into()
+        );
+    }
+
+    #[cfg(feature = "reject-compressed")]
+    #[test]
+    fn test_update_nft_uncompressed_asset_allowed() {
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        accounts[0] = new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]);
+
+        let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[cfg(all(feature = "ledger-state", not(feature = "reject-compressed")))]
+    #[test]
+    fn test_update_nft_ledger_state_missing_log_wrapper() {
+        let accounts = new_ledger_state_accounts(system_program::ID);
+        let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::MissingLogWrapper.into()
+        );
+    }
+
+    fn update_as_delegated_authority(
+        controller: Pubkey,
+        payer: AccountInfo<'static>,
+    ) -> ProgramResult {
+        let (pda, _) = derive_delegated_authority(&controller);
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        accounts[2] = new_test_account(pda, false, false, 1, 0, system_program::ID);
+        accounts[3] = payer;
+
+        UpdateNftV1::try_from((
+            accounts.as_slice(),
+            UpdateNftV1InstructionData {
+                new_name: Some("Renamed".to_string()),
+                new_uri: None,
+                uri_suffix: None,
+                delegated_authority: true,
+                approved_authorities: None,
+                new_collection: None,
+                expected_name: None,
+                expected_uri: None,
+            },
+        ))?
+        .process()
+    }
+
+    #[test]
+    fn test_update_nft_signed_by_delegated_authority() {
+        let controller = Pubkey::new_unique();
+        let payer = new_test_account(controller, true, true, 1, 0, system_program::ID);
+
+        take_recorded_cpis();
+        let res = update_as_delegated_authority(controller, payer);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(take_recorded_cpis(), vec!["UpdateV2"]);
+    }
+
+    #[test]
+    fn test_update_nft_delegated_authority_of_other_controller() {
+        let caller = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+
+        take_recorded_cpis();
+        let res = update_as_delegated_authority(Pubkey::new_unique(), caller);
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidSignerSeeds.into()
+        );
+        assert!(take_recorded_cpis().is_empty());
+    }
+
+    #[test]
+    fn test_update_nft_unsigned_authority_without_seeds() {
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        accounts[2] =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
+
+        let res = UpdateNftV1::try_from((
+            accounts.as_slice(),
+            UpdateNftV1InstructionData {
+                new_name: None,
+                new_uri: None,
+                uri_suffix: None,
+                delegated_authority: false,
+                approved_authorities: None,
+                new_collection: None,
+                expected_name: None,
+                expected_uri: None,
+            },
+        ));
+        assert_eq!(res.unwrap_err(), ProgramError::MissingRequiredSignature);
+    }
+
+    #[cfg(feature = "asset-state")]
+    fn update_name(asset: AccountInfo<'static>) -> Result<(), ProgramError> {
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        accounts[0] = asset;
+
+        UpdateNftV1::try_from((
+            accounts.as_slice(),
+            UpdateNftV1InstructionData {
+                new_name: Some("Renamed".to_string()),
+                new_uri: None,
+                uri_suffix: None,
+                delegated_authority: false,
+                approved_authorities: None,
+                new_collection: None,
+                expected_name: None,
+                expected_uri: None,
+            },
+        ))
+        .map(|_| ())
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_update_nft_immutable_metadata() {
+        use mpl_core::types::{Plugin, PluginAuthority, UpdateAuthority};
+
+        let asset = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::None,
+            &[(
+                Plugin::ImmutableMetadata(ImmutableMetadata {}),
+                PluginAuthority::UpdateAuthority,
+            )],
+        );
+
+        assert_eq!(
+            update_name(asset).unwrap_err(),
+            MplCoreWrapperError::MetadataImmutable.into()
+        );
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_update_nft_mutable_metadata() {
+        use mpl_core::types::{FreezeDelegate, Plugin, PluginAuthority, UpdateAuthority};
+
+        let asset = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::None,
+            &[(
+                Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                PluginAuthority::Owner,
+            )],
+        );
+
+        let res = update_name(asset);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_update_nft_event() {
+        use crate::events::{MplCoreWrapperEvent, UpdateNftEvent};
+
+        let accounts = update_nft_accounts(Pubkey::new_unique());
+        let instruction = UpdateNftV1::try_from((
+            accounts.as_slice(),
+            UpdateNftV1InstructionData {
+                new_name: None,
+                new_uri: Some("https://example.com/new.json".to_string()),
+                uri_suffix: None,
+                delegated_authority: false,
+                approved_authorities: None,
+                new_collection: None,
+                expected_name: None,
+                expected_uri: None,
+            },
+        ))
+        .unwrap();
+
+        let log = instruction.event().encode().unwrap();
+        assert_eq!(
+            MplCoreWrapperEvent::decode(&log),
+            Ok(MplCoreWrapperEvent::UpdateNft(UpdateNftEvent {
+                asset: *accounts[0].key,
+                new_name: None,
+                new_uri: Some("https://example.com/new.json".to_string()),
+            }))
+        );
+    }
+
+    fn update_uri(
+        new_uri: Option<String>,
+        uri_suffix: Option<String>,
+    ) -> Result<Option<String>, ProgramError> {
+        use mpl_core::types::UpdateAuthority;
+
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        accounts[0] = new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]);
+
+        let instruction = UpdateNftV1::try_from((
+            accounts.as_slice(),
+            UpdateNftV1InstructionData {
+                new_name: None,
+                new_uri,
+                uri_suffix,
+                delegated_authority: false,
+                approved_authorities: None,
+                new_collection: None,
+                expected_name: None,
+                expected_uri: None,
+            },
+        ))?;
+        assert!(instruction.instruction_data.uri_suffix.is_none());
+
+        Ok(instruction.instruction_data.new_uri)
+    }
+
+    #[test]
+    fn test_update_nft_uri_suffix() {
+        assert_eq!(
+            update_uri(None, Some("?v=2".to_string())),
+            Ok(Some("https://example.com/asset.json?v=2".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_update_nft_uri_suffix_too_long() {
+        assert_eq!(
+            update_uri(None, Some("x".repeat(MAX_URI_LEN))),
+            Err(MplCoreWrapperError::UriTooLong.into())
+        );
+    }
+
+    #[test]
+    fn test_update_nft_uri_suffix_conflict() {
+        assert_eq!(
+            update_uri(
+                Some("https://example.com/new.json".to_string()),
+                Some("?v=2".to_string()),
+            ),
+            Err(MplCoreWrapperError::ConflictingUriUpdate.into())
+        );
+    }
+
+    #[cfg(feature = "asset-state")]
+    fn update_with_expected(
+        expected_name: Option<&str>,
+        expected_uri: Option<&str>,
+    ) -> ProgramResult {
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        accounts[0] = new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]);
+
+        UpdateNftV1::try_from((
+            accounts.as_slice(),
+            UpdateNftV1InstructionData {
+                new_name: Some("Renamed".to_string()),
+                new_uri: None,
+                uri_suffix: None,
+                delegated_authority: false,
+                approved_authorities: None,
+                new_collection: None,
+                expected_name: expected_name.map(str::to_string),
+                expected_uri: expected_uri.map(str::to_string),
+            },
+        ))?
+        .process()
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_update_nft_expected_metadata_matches() {
+        assert_eq!(
+            update_with_expected(Some("Asset"), Some("https://example.com/asset.json")),
+            Ok(())
+        );
+        assert_eq!(update_with_expected(Some("Asset"), None), Ok(()));
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_update_nft_expected_metadata_mismatch() {
+        assert_eq!(
+            update_with_expected(Some("Stale"), None),
+            Err(MplCoreWrapperError::ConcurrentModification.into())
+        );
+        assert_eq!(
+            update_with_expected(Some("Asset"), Some("https://example.com/stale.json")),
+            Err(MplCoreWrapperError::ConcurrentModification.into())
+        );
+    }
+
+    #[cfg(feature = "reject-noop-update")]
+    fn update_asset(new_name: &str, new_uri: Option<&str>) -> ProgramResult {
+        let accounts = update_nft_accounts(Pubkey::new_unique());
+
+        UpdateNftV1::try_from((
+            accounts.as_slice(),
+            UpdateNftV1InstructionData {
+                new_name: Some(new_name.to_string()),
+                new_uri: new_uri.map(str::to_string),
+                uri_suffix: None,
+                delegated_authority: false,
+                approved_authorities: None,
+                new_collection: None,
+                expected_name: None,
+                expected_uri: None,
+            },
+        ))?
+        .process()
+    }
+
+    #[cfg(feature = "reject-noop-update")]
+    #[test]
+    fn test_update_nft_effective_change() {
+        assert_eq!(update_asset("Renamed", None), Ok(()));
+        assert_eq!(
+            update_asset("Renamed", Some("https://example.com/renamed.json")),
+            Ok(())
+        );
+    }
+
+    #[cfg(feature = "reject-noop-update")]
+    #[test]
+    fn test_update_nft_no_effective_change() {
+        assert_eq!(
+            update_asset("Asset", None),
+            Err(MplCoreWrapperError::NoEffectiveChange.into())
+        );
+        assert_eq!(
+            update_asset("Renamed", Some("https://example.com/asset.json")),
+            Err(MplCoreWrapperError::NoEffectiveChange.into())
+        );
+    }
+
+    fn update_with_approved(
+        accounts: &[AccountInfo<'static>],
+        approved: Vec<Pubkey>,
+    ) -> ProgramResult {
+        UpdateNftV1::try_from((
+            accounts,
+            UpdateNftV1InstructionData {
+                new_name: Some("Renamed".to_string()),
+                new_uri: None,
+                uri_suffix: None,
+                delegated_authority: false,
+                approved_authorities: Some(approved),
+                new_collection: None,
+                expected_name: None,
+                expected_uri: None,
+            },
+        ))
+        .map(|_| ())
+    }
+
+    #[test]
+    fn test_update_nft_approved_authority() {
+        let accounts = update_nft_accounts(Pubkey::new_unique());
+        let res = update_with_approved(&accounts, vec![Pubkey::new_unique(), *accounts[2].key]);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_update_nft_authority_not_approved() {
+        let accounts = update_nft_accounts(Pubkey::new_unique());
+        assert_eq!(
+            update_with_approved(&accounts, vec![Pubkey::new_unique()]),
+            Err(MplCoreWrapperError::AuthorityNotApproved.into())
+        );
+    }
+
+    /// Accounts moving the asset from a collection owned by `authority` to
+    /// `new_collection`.
+    fn new_move_accounts(
+        authority: Pubkey,
+        new_collection: AccountInfo<'static>,
+    ) -> Vec<AccountInfo<'static>> {
+        let mut accounts = update_nft_accounts(authority);
+        accounts[1] = new_collection_account(authority);
+        accounts[7] = new_collection;
+        accounts
+    }
+
+    fn move_collection(accounts: &[AccountInfo<'static>], new_collection: Pubkey) -> ProgramResult {
+        UpdateNftV1::try_from((
+            accounts,
+            UpdateNftV1InstructionData {
+                new_name: None,
+                new_uri: None,
+                uri_suffix: None,
+                delegated_authority: false,
+                approved_authorities: None,
+                new_collection: Some(new_collection),
+                expected_name: None,
+                expected_uri: None,
+            },
+        ))?
+        .process()
+    }
+
+    #[test]
+    fn test_update_nft_move_collection() {
+        let authority = Pubkey::new_unique();
+        let accounts = new_move_accounts(authority, new_collection_account(authority));
+        let res = move_collection(&accounts, *accounts[7].key);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+
+        let instructions = take_recorded_instructions();
+        let update = instructions.last().unwrap();
+        assert_eq!(update.program_id, mpl_core::ID);
+        assert_eq!(update.accounts[3].pubkey, authority);
+        assert!(update.accounts[3].is_signer);
+        assert_eq!(update.accounts[4].pubkey, *accounts[7].key);
+    }
+
+    #[test]
+    fn test_update_nft_move_collection_without_trailing_account() {
+        let accounts = update_nft_accounts(Pubkey::new_unique());
+        let res = UpdateNftV1::try_from((
+            &accounts[..7],
+            UpdateNftV1InstructionData {
+                new_name: Some("Renamed".to_string()),
+                new_uri: None,
+                uri_suffix: None,
+                delegated_authority: false,
+                approved_authorities: None,
+                new_collection: None,
+                expected_name: None,
+                expected_uri: None,
+            },
+        ));
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert!(res.unwrap().accounts.new_collection.is_none());
+    }
+
+    #[test]
+    fn test_update_nft_move_collection_inconsistent_accounts() {
+        let authority = Pubkey::new_unique();
+        let accounts = new_move_accounts(authority, new_collection_account(authority));
+        assert_eq!(
+            move_collection(&accounts, Pubkey::new_unique()),
+            Err(MplCoreWrapperError::InconsistentOptionalAccounts.into())
+        );
+
+        let accounts = update_nft_accounts(Pubkey::new_unique());
+        assert_eq!(
+            move_collection(&accounts, Pubkey::new_unique()),
+            Err(MplCoreWrapperError::InconsistentOptionalAccounts.into())
+        );
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_update_nft_move_collection_authority_mismatch() {
+        // The new collection belongs to someone else than the authority.
+        let accounts = new_move_accounts(
+            Pubkey::new_unique(),
+            new_collection_account(Pubkey::new_unique()),
+        );
+        assert_eq!(
+            move_collection(&accounts, *accounts[7].key),
+            Err(MplCoreWrapperError::AuthorityMismatch.into())
+        );
+
+        // The authority owns the new collection but not the current one.
+        let authority = Pubkey::new_unique();
+        let mut accounts = new_move_accounts(authority, new_collection_account(authority));
+        accounts[1] = new_collection_account(Pubkey::new_unique());
+        assert_eq!(
+            move_collection(&accounts, *accounts[7].key),
+            Err(MplCoreWrapperError::AuthorityMismatch.into())
+        );
+    }
+
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_update_nft_move_collection_update_delegate() {
+        use mpl_core::types::{Plugin, PluginAuthority, UpdateDelegate};
+
+        // The new collection has another update authority, which delegated
+        // updates to the authority.
+        let authority = Pubkey::new_unique();
+        let accounts = new_move_accounts(
             authority,
-            payer,
-            system_program,
-            log_wrapper,
-            bad_mpl_core,
-        ];
+            new_sized_collection_account(
+                Pubkey::new_unique(),
+                0,
+                &[(
+                    Plugin::UpdateDelegate(UpdateDelegate {
+                        additional_delegates: vec![],
+                    }),
+                    PluginAuthority::Address { address: authority },
+                )],
+            ),
+        );
+        let res = move_collection(&accounts, *accounts[7].key);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
 
+    #[cfg(feature = "asset-state")]
+    #[test]
+    fn test_update_nft_move_collection_immutable_metadata() {
+        use mpl_core::types::{Plugin, PluginAuthority, UpdateAuthority};
+
+        let authority = Pubkey::new_unique();
+        let mut accounts = new_move_accounts(authority, new_collection_account(authority));
+        accounts[0] = new_asset_account(
+            Pubkey::new_unique(),
+            UpdateAuthority::None,
+            &[(
+                Plugin::ImmutableMetadata(ImmutableMetadata {}),
+                PluginAuthority::UpdateAuthority,
+            )],
+        );
+
+        let res = move_collection(&accounts, *accounts[7].key);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_update_nft_logs_every_account_error() {
+        let mut accounts = update_nft_accounts(Pubkey::new_unique());
+        accounts[0].is_writable = false;
+        accounts[3].is_signer = false;
+        accounts[5].is_writable = true;
+
+        take_recorded_account_errors();
         let res = UpdateNftV1Accounts::try_from(accounts.as_slice());
-        assert!(
-            res.is_err(),
-            "expected failure because mpl_core was wrong, but got Ok: {:?}",
-            res
+        assert_eq!(res.unwrap_err(), ProgramError::InvalidAccountData);
+        assert_eq!(
+            take_recorded_account_errors(),
+            vec![
+                "account[0] must be writable",
+                "account[3] must sign",
+                "account[5] must not be writable",
+            ]
         );
     }
 
@@ -226,4 +1043,17 @@ mod tests {
             res
         );
     }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_update_nft_account_metas() {
+        let accounts = update_nft_accounts(Pubkey::new_unique());
+        let metas = UpdateNftV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[2], |accounts| {
+            UpdateNftV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
 }
@@ -0,0 +1,289 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    instructions::UpdatePluginV1CpiBuilder,
+    types::{Plugin, PluginAuthority},
+};
+#[cfg(feature = "client")]
+use solana_program::instruction::AccountMeta;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+#[cfg(feature = "asset-state")]
+use crate::utils::check_collection_account;
+#[cfg(feature = "client")]
+use crate::utils::{account_meta, optional_account_meta, ToAccountMetas};
+use crate::{
+    error::MplCoreWrapperError,
+    instructions::Instructions,
+    plugins::PluginValidation,
+    utils::{
+        invoke_cpi, AccountCheck, MplCoreAccount, NonDefaultKey, NonWritable, NotProgramAccount,
+        OptionalAccountCheck, ProcessInstruction, SignerAccount, SystemAccount, ToOptionalAccount,
+        WritableAccount, MAX_ASSET_DATA_LEN,
+    },
+};
+
+/// Most plugins updated by one instruction, keeping its CPI count within the
+/// compute budget.
+pub const MAX_UPDATED_PLUGINS: usize = 8;
+
+#[derive(Debug)]
+pub struct UpdatePluginsBatchV1Accounts<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub authority: Option<&'a AccountInfo<'info>>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub log_wrapper: Option<&'a AccountInfo<'info>>,
+    pub mpl_core: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for UpdatePluginsBatchV1Accounts<'a, 'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        let [asset, collection, payer, authority, system_program, log_wrapper, mpl_core] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        NonDefaultKey::check_at(0, asset)?;
+        NotProgramAccount::check_at(0, asset)?;
+        NonDefaultKey::check_at(2, payer)?;
+        NotProgramAccount::check_at(2, payer)?;
+        WritableAccount::check_at(0, asset)?;
+        WritableAccount::check_optional_at(1, collection.to_optional())?;
+        WritableAccount::check_at(2, payer)?;
+        SignerAccount::check_at(2, payer)?;
+        SignerAccount::check_optional_at(3, authority.to_optional())?;
+        SystemAccount::check_at(4, system_program)?;
+        NonWritable::check_optional_at(5, log_wrapper.to_optional())?;
+        MplCoreAccount::check_at(6, mpl_core)?;
+
+        Ok(Self {
+            asset,
+            collection: collection.to_optional(),
+            payer,
+            authority: authority.to_optional(),
+            system_program,
+            log_wrapper: log_wrapper.to_optional(),
+            mpl_core,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl ToAccountMetas for UpdatePluginsBatchV1Accounts<'_, '_> {
+    fn to_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            account_meta(self.asset, false, true),
+            optional_account_meta(self.collection, false, true),
+            account_meta(self.payer, true, true),
+            optional_account_meta(self.authority, true, false),
+            account_meta(self.system_program, false, false),
+            optional_account_meta(self.log_wrapper, false, false),
+            account_meta(self.mpl_core, false, false),
+        ]
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct UpdatePluginsBatchV1InstructionData {
+    /// New value of each plugin, applied in order.
+    pub plugins: Vec<Plugin>,
+}
+
+impl UpdatePluginsBatchV1InstructionData {
+    pub fn check_batch_size(&self, max_plugins: usize) -> ProgramResult {
+        if self.plugins.is_empty() || self.plugins.len() > max_plugins {
+            return Err(MplCoreWrapperError::InvalidBatchSize.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Updates set no authority, so only the plugins themselves are checked.
+impl PluginValidation for UpdatePluginsBatchV1InstructionData {
+    fn plugins(&self) -> Vec<(&Plugin, Option<&PluginAuthority>)> {
+        self.plugins.iter().map(|plugin| (plugin, None)).collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct UpdatePluginsBatchV1<'a, 'info> {
+    pub accounts: UpdatePluginsBatchV1Accounts<'a, 'info>,
+    pub instruction_data: UpdatePluginsBatchV1InstructionData,
+}
+
+impl<'a, 'info>
+    TryFrom<(
+        &'a [AccountInfo<'info>],
+        UpdatePluginsBatchV1InstructionData,
+    )> for UpdatePluginsBatchV1<'a, 'info>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (
+            &'a [AccountInfo<'info>],
+            UpdatePluginsBatchV1InstructionData,
+        ),
+    ) -> Result<Self, Self::Error> {
+        let accounts = UpdatePluginsBatchV1Accounts::try_from(accounts)?;
+        #[cfg(feature = "asset-state")]
+        check_collection_account(accounts.asset, accounts.collection)?;
+        instruction_data.check_batch_size(MAX_UPDATED_PLUGINS)?;
+        instruction_data.validate_plugins(MAX_UPDATED_PLUGINS, MAX_ASSET_DATA_LEN)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a, 'info> ProcessInstruction for UpdatePluginsBatchV1<'a, 'info> {
+    fn process(self) -> ProgramResult {
+        for plugin in self.instruction_data.plugins {
+            invoke_cpi("UpdatePluginV1", self.accounts.asset.key, || {
+                UpdatePluginV1CpiBuilder::new(self.accounts.mpl_core)
+                    .asset(self.accounts.asset)
+                    .collection(self.accounts.collection)
+                    .payer(self.accounts.payer)
+                    .authority(self.accounts.authority)
+                    .system_program(self.accounts.system_program)
+                    .log_wrapper(self.accounts.log_wrapper)
+                    .plugin(plugin)
+                    .invoke()
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn process_update_plugins_batch_v1(
+    accounts: &[AccountInfo],
+    instruction: Instructions,
+) -> ProgramResult {
+    let Instructions::UpdatePluginsBatchV1(instruction_data) = instruction else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    UpdatePluginsBatchV1::try_from((accounts, instruction_data))?.process()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::{Attribute, Attributes, FreezeDelegate, UpdateAuthority};
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts() -> Vec<AccountInfo<'static>> {
+        let asset = new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]);
+        let collection =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let payer = new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID);
+        let authority =
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        let system_program =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let log_wrapper =
+            new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        let mpl_core = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+
+        vec![
+            asset,
+            collection,
+            payer,
+            authority,
+            system_program,
+            log_wrapper,
+            mpl_core,
+        ]
+    }
+
+    fn freeze() -> Plugin {
+        Plugin::FreezeDelegate(FreezeDelegate { frozen: true })
+    }
+
+    #[test]
+    fn test_update_plugins_batch_two_plugins() {
+        let accounts = new_accounts();
+        let instruction = UpdatePluginsBatchV1::try_from((
+            accounts.as_slice(),
+            UpdatePluginsBatchV1InstructionData {
+                plugins: vec![
+                    freeze(),
+                    Plugin::Attributes(Attributes {
+                        attribute_list: vec![Attribute {
+                            key: "level".to_string(),
+                            value: "2".to_string(),
+                        }],
+                    }),
+                ],
+            },
+        ))
+        .unwrap();
+
+        take_recorded_cpis();
+        let res = instruction.process();
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(
+            take_recorded_cpis(),
+            vec!["UpdatePluginV1", "UpdatePluginV1"]
+        );
+    }
+
+    #[test]
+    fn test_update_plugins_batch_empty() {
+        let accounts = new_accounts();
+        let res = UpdatePluginsBatchV1::try_from((
+            accounts.as_slice(),
+            UpdatePluginsBatchV1InstructionData { plugins: vec![] },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidBatchSize.into()
+        );
+    }
+
+    #[test]
+    fn test_update_plugins_batch_too_many() {
+        let accounts = new_accounts();
+        let res = UpdatePluginsBatchV1::try_from((
+            accounts.as_slice(),
+            UpdatePluginsBatchV1InstructionData {
+                plugins: vec![freeze(); MAX_UPDATED_PLUGINS + 1],
+            },
+        ));
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::InvalidBatchSize.into()
+        );
+    }
+
+    #[test]
+    fn test_update_plugins_batch_account_not_enough_accounts() {
+        let accounts = vec![];
+        let res = UpdatePluginsBatchV1Accounts::try_from(accounts.as_slice());
+        assert_eq!(res.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_update_plugins_batch_account_metas() {
+        let accounts = new_accounts();
+        let metas = UpdatePluginsBatchV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_metas();
+
+        check_account_metas(&accounts, &metas, &[], |accounts| {
+            UpdatePluginsBatchV1Accounts::try_from(accounts).map(|_| ())
+        });
+    }
+}
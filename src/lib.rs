@@ -1,17 +1,25 @@
-mod instructions;
-mod utils;
+pub mod error;
+pub mod events;
+pub mod instructions;
+pub mod plugins;
+pub mod utils;
 
-use borsh::BorshDeserialize;
 use solana_program::{
     account_info::AccountInfo, declare_id, entrypoint, entrypoint::ProgramResult, pubkey::Pubkey,
 };
 
-use crate::{
-    instructions::{CreateNftV1, Instructions, TransferNftV1, UpdateNftV1},
-    utils::ProcessInstruction,
-};
+#[cfg(feature = "std")]
+use crate::error::log_error;
+use crate::{error::MplCoreWrapperError, instructions::dispatch};
+
+/// Program ID used unless `MPL_CORE_WRAPPER_PROGRAM_ID` is set at build time,
+/// e.g. to deploy a fork or a localnet build under its own keypair.
+pub const DEFAULT_PROGRAM_ID: &str = "3KRCmsnNYQvjp1TZaha1riRmx5GGVt67yv2sxDsFXbRG";
 
-declare_id!("3KRCmsnNYQvjp1TZaha1riRmx5GGVt67yv2sxDsFXbRG");
+declare_id!(match option_env!("MPL_CORE_WRAPPER_PROGRAM_ID") {
+    Some(program_id) => program_id,
+    None => DEFAULT_PROGRAM_ID,
+});
 
 entrypoint!(process_entrypoint);
 
@@ -20,11 +28,36 @@ fn process_entrypoint(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let instruction = Instructions::try_from_slice(instruction_data)?;
+    let result = if instruction_data.is_empty() {
+        Err(MplCoreWrapperError::EmptyInstructionData.into())
+    } else {
+        dispatch(accounts, instruction_data)
+    };
+
+    #[cfg(feature = "std")]
+    let result = result.inspect_err(log_error);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_instruction_data() {
+        assert_eq!(
+            process_entrypoint(&crate::ID, &[], &[]),
+            Err(MplCoreWrapperError::EmptyInstructionData.into())
+        );
+    }
 
-    match instruction {
-        Instructions::CreateNftV1(data) => CreateNftV1::try_from((accounts, data))?.process(),
-        Instructions::UpdateNftV1(data) => UpdateNftV1::try_from((accounts, data))?.process(),
-        Instructions::TransferNftV1 => TransferNftV1::try_from(accounts)?.process(),
+    #[test]
+    fn test_program_id_from_build_env() {
+        match option_env!("MPL_CORE_WRAPPER_PROGRAM_ID") {
+            Some(program_id) => assert_eq!(crate::ID.to_string(), program_id),
+            None => assert_eq!(crate::ID.to_string(), DEFAULT_PROGRAM_ID),
+        }
+        assert!(check_id(&crate::ID));
     }
 }
@@ -0,0 +1,395 @@
+use borsh::BorshSerialize;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    fetch_asset_plugin,
+    types::{
+        AddBlocker, Attribute, Attributes, Autograph, BurnDelegate, FreezeDelegate, FreezeExecute,
+        ImmutableMetadata, Key, MasterEdition, PermanentBurnDelegate, PermanentFreezeDelegate,
+        PermanentTransferDelegate, Plugin, PluginAuthority, PluginAuthorityPair, PluginType,
+        TransferDelegate, UpdateAuthority, UpdateDelegate, VerifiedCreators,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::MplCoreWrapperError,
+    utils::{assert_account_key, checked_add_size, MinDataLen, BASE_COLLECTION_MIN_LEN},
+};
+
+/// Upper bound on attributes kept on a single Attributes plugin.
+pub const MAX_ATTRIBUTES: usize = 32;
+
+/// Rejects plugin/authority combinations that mpl_core would refuse.
+///
+/// Permanent delegates can only be managed by the update authority.
+pub fn check_plugin_authority(
+    plugin: &Plugin,
+    authority: Option<&PluginAuthority>,
+) -> ProgramResult {
+    let is_permanent = matches!(
+        plugin,
+        Plugin::PermanentFreezeDelegate(_)
+            | Plugin::PermanentTransferDelegate(_)
+            | Plugin::PermanentBurnDelegate(_)
+    );
+
+    if is_permanent && matches!(authority, Some(PluginAuthority::Owner)) {
+        return Err(MplCoreWrapperError::IncompatiblePluginAuthority.into());
+    }
+
+    Ok(())
+}
+
+/// Plugin rules shared by the instructions that write plugins, so each of
+/// them rejects the same input with the same error.
+pub trait PluginValidation {
+    /// Every plugin the instruction writes, with the authority it sets.
+    fn plugins(&self) -> Vec<(&Plugin, Option<&PluginAuthority>)>;
+
+    /// Serialized size of what the instruction writes to the asset.
+    fn data_len(&self) -> Result<usize, ProgramError> {
+        self.plugins()
+            .into_iter()
+            .try_fold(0, |size, (plugin, authority)| {
+                let size = checked_add_size(size, plugin.try_to_vec()?.len())?;
+                checked_add_size(size, authority.try_to_vec()?.len())
+            })
+    }
+
+    fn check_plugin_count(&self, max_plugins: usize) -> ProgramResult {
+        if self.plugins().len() > max_plugins {
+            return Err(MplCoreWrapperError::TooManyPlugins.into());
+        }
+
+        Ok(())
+    }
+
+    /// mpl_core keeps at most one plugin of each type on an asset, so a
+    /// repeated type would only fail inside the CPI.
+    fn check_unique_plugin_types(&self) -> ProgramResult {
+        let mut seen = Vec::new();
+
+        for (plugin, _) in self.plugins() {
+            let plugin_type = PluginType::from(plugin);
+            if seen.contains(&plugin_type) {
+                return Err(MplCoreWrapperError::DuplicatePluginType.into());
+            }
+            seen.push(plugin_type);
+        }
+
+        Ok(())
+    }
+
+    fn check_plugin_authorities(&self) -> ProgramResult {
+        self.plugins()
+            .into_iter()
+            .try_for_each(|(plugin, authority)| check_plugin_authority(plugin, authority))
+    }
+
+    /// Rejects data that would not fit in an account of `max_len` bytes.
+    fn check_asset_size(&self, max_len: usize) -> ProgramResult {
+        if self.data_len()? > max_len {
+            return Err(MplCoreWrapperError::AssetTooLarge.into());
+        }
+
+        Ok(())
+    }
+
+    /// Runs every check above.
+    fn validate_plugins(&self, max_plugins: usize, max_len: usize) -> ProgramResult {
+        self.check_plugin_count(max_plugins)?;
+        self.check_unique_plugin_types()?;
+        self.check_asset_size(max_len)?;
+        self.check_plugin_authorities()
+    }
+}
+
+/// Permanent plugins stay on the asset for its lifetime, so mpl_core refuses
+/// to remove them. Neither can an AddBlocker be lifted to allow additions again.
+pub fn check_plugin_removable(plugin_type: &PluginType) -> ProgramResult {
+    if plugin_type == &PluginType::AddBlocker {
+        return Err(MplCoreWrapperError::PluginAdditionsBlocked.into());
+    }
+
+    if matches!(
+        plugin_type,
+        PluginType::PermanentFreezeDelegate
+            | PluginType::PermanentTransferDelegate
+            | PluginType::PermanentBurnDelegate
+    ) {
+        return Err(MplCoreWrapperError::CannotRemovePermanentPlugin.into());
+    }
+
+    Ok(())
+}
+
+/// Fails fast when the asset carries an AddBlocker, which makes mpl_core
+/// reject every later plugin addition.
+pub fn check_plugin_additions_allowed(asset: &AccountInfo) -> ProgramResult {
+    if fetch_asset_plugin::<AddBlocker>(asset, PluginType::AddBlocker).is_ok() {
+        return Err(MplCoreWrapperError::PluginAdditionsBlocked.into());
+    }
+
+    Ok(())
+}
+
+pub fn build_plugin_pair(
+    plugin: Plugin,
+    authority: Option<PluginAuthority>,
+) -> Result<PluginAuthorityPair, ProgramError> {
+    check_plugin_authority(&plugin, authority.as_ref())?;
+
+    Ok(PluginAuthorityPair { plugin, authority })
+}
+
+/// Empty plugin of `plugin_type`: no entries, no delegates and nothing frozen.
+///
+/// Royalties and Edition carry values with no neutral choice, and only
+/// Bubblegum may add BubblegumV2, so those have no default.
+pub fn default_plugin(plugin_type: PluginType) -> Result<Plugin, ProgramError> {
+    let plugin = match plugin_type {
+        PluginType::FreezeDelegate => Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+        PluginType::BurnDelegate => Plugin::BurnDelegate(BurnDelegate {}),
+        PluginType::TransferDelegate => Plugin::TransferDelegate(TransferDelegate {}),
+        PluginType::UpdateDelegate => Plugin::UpdateDelegate(UpdateDelegate {
+            additional_delegates: vec![],
+        }),
+        PluginType::PermanentFreezeDelegate => {
+            Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: false })
+        }
+        PluginType::Attributes => Plugin::Attributes(Attributes {
+            attribute_list: vec![],
+        }),
+        PluginType::PermanentTransferDelegate => {
+            Plugin::PermanentTransferDelegate(PermanentTransferDelegate {})
+        }
+        PluginType::PermanentBurnDelegate => {
+            Plugin::PermanentBurnDelegate(PermanentBurnDelegate {})
+        }
+        PluginType::MasterEdition => Plugin::MasterEdition(MasterEdition {
+            max_supply: None,
+            name: None,
+            uri: None,
+        }),
+        PluginType::AddBlocker => Plugin::AddBlocker(AddBlocker {}),
+        PluginType::ImmutableMetadata => Plugin::ImmutableMetadata(ImmutableMetadata {}),
+        PluginType::VerifiedCreators => {
+            Plugin::VerifiedCreators(VerifiedCreators { signatures: vec![] })
+        }
+        PluginType::Autograph => Plugin::Autograph(Autograph { signatures: vec![] }),
+        PluginType::FreezeExecute => Plugin::FreezeExecute(FreezeExecute { frozen: false }),
+        PluginType::Royalties | PluginType::Edition | PluginType::BubblegumV2 => {
+            return Err(MplCoreWrapperError::UnsupportedPluginDefault.into());
+        }
+    };
+
+    Ok(plugin)
+}
+
+/// The collection `asset` belongs to, read from the `collection` account, or
+/// `None` for an asset outside any collection.
+pub fn load_asset_collection(
+    asset: &BaseAssetV1,
+    collection: Option<&AccountInfo>,
+) -> Result<Option<BaseCollectionV1>, ProgramError> {
+    let UpdateAuthority::Collection(address) = asset.update_authority else {
+        return Ok(None);
+    };
+
+    let Some(collection) = collection.filter(|collection| collection.key == &address) else {
+        return Err(MplCoreWrapperError::CollectionAccountRequired.into());
+    };
+    MinDataLen::check(collection, BASE_COLLECTION_MIN_LEN)?;
+    assert_account_key(collection, Key::CollectionV1)?;
+
+    BaseCollectionV1::try_from(collection)
+        .map(Some)
+        .map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Whether `key` holds `authority` over a plugin on `asset`, or on
+/// `collection`, the asset's collection from [`load_asset_collection`].
+///
+/// The update authority of an asset in a collection is the collection's.
+pub fn is_plugin_authority(
+    authority: &PluginAuthority,
+    key: &Pubkey,
+    asset: &BaseAssetV1,
+    collection: Option<&BaseCollectionV1>,
+) -> bool {
+    match authority {
+        PluginAuthority::None => false,
+        PluginAuthority::Owner => key == &asset.owner,
+        PluginAuthority::UpdateAuthority => match asset.update_authority {
+            UpdateAuthority::Address(address) => &address == key,
+            UpdateAuthority::Collection(_) => {
+                collection.is_some_and(|collection| &collection.update_authority == key)
+            }
+            UpdateAuthority::None => false,
+        },
+        PluginAuthority::Address { address } => key == address,
+    }
+}
+
+/// Applies `updates` on top of `existing`, keyed by attribute key.
+///
+/// Later values win, including duplicates within `updates`, and keys keep the
+/// position they were first seen at.
+pub fn merge_attributes(
+    existing: Vec<Attribute>,
+    updates: Vec<Attribute>,
+    max: usize,
+) -> Result<Vec<Attribute>, ProgramError> {
+    let mut merged: Vec<Attribute> = Vec::with_capacity(existing.len() + updates.len());
+
+    for attribute in existing.into_iter().chain(updates) {
+        match merged.iter_mut().find(|a| a.key == attribute.key) {
+            Some(current) => current.value = attribute.value,
+            None => merged.push(attribute),
+        }
+    }
+
+    if merged.len() > max {
+        return Err(MplCoreWrapperError::TooManyAttributes.into());
+    }
+
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Plugins(Vec<PluginAuthorityPair>);
+
+    impl PluginValidation for Plugins {
+        fn plugins(&self) -> Vec<(&Plugin, Option<&PluginAuthority>)> {
+            self.0
+                .iter()
+                .map(|pair| (&pair.plugin, pair.authority.as_ref()))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_plugin_validation_data_len_matches_borsh() {
+        let plugins = vec![
+            PluginAuthorityPair {
+                plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                authority: None,
+            },
+            PluginAuthorityPair {
+                plugin: Plugin::BurnDelegate(BurnDelegate {}),
+                authority: Some(PluginAuthority::Address {
+                    address: Pubkey::new_unique(),
+                }),
+            },
+        ];
+        let expected = plugins
+            .iter()
+            .map(|pair| pair.try_to_vec().unwrap().len())
+            .sum::<usize>();
+
+        let plugins = Plugins(plugins);
+        assert_eq!(plugins.data_len(), Ok(expected));
+        assert_eq!(
+            plugins.check_asset_size(expected - 1),
+            Err(MplCoreWrapperError::AssetTooLarge.into())
+        );
+    }
+
+    #[test]
+    fn test_build_plugin_pair_success() {
+        let res = build_plugin_pair(
+            Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+            Some(PluginAuthority::Owner),
+        );
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+
+        let res = build_plugin_pair(
+            Plugin::PermanentTransferDelegate(PermanentTransferDelegate {}),
+            Some(PluginAuthority::UpdateAuthority),
+        );
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+
+    #[test]
+    fn test_build_plugin_pair_permanent_with_owner() {
+        let res = build_plugin_pair(
+            Plugin::PermanentTransferDelegate(PermanentTransferDelegate {}),
+            Some(PluginAuthority::Owner),
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::IncompatiblePluginAuthority.into()
+        );
+    }
+
+    #[test]
+    fn test_default_plugin() {
+        assert_eq!(
+            default_plugin(PluginType::FreezeDelegate).unwrap(),
+            Plugin::FreezeDelegate(FreezeDelegate { frozen: false })
+        );
+        assert_eq!(
+            default_plugin(PluginType::Attributes).unwrap(),
+            Plugin::Attributes(Attributes {
+                attribute_list: vec![]
+            })
+        );
+    }
+
+    #[test]
+    fn test_default_plugin_unsupported() {
+        assert_eq!(
+            default_plugin(PluginType::Royalties).unwrap_err(),
+            MplCoreWrapperError::UnsupportedPluginDefault.into()
+        );
+    }
+
+    fn attribute(key: &str, value: &str) -> Attribute {
+        Attribute {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_merge_attributes_dedup_last_write_wins() {
+        let merged = merge_attributes(
+            vec![attribute("color", "red"), attribute("size", "m")],
+            vec![
+                attribute("color", "blue"),
+                attribute("rarity", "common"),
+                attribute("rarity", "rare"),
+            ],
+            MAX_ATTRIBUTES,
+        )
+        .unwrap();
+
+        assert_eq!(
+            merged,
+            vec![
+                attribute("color", "blue"),
+                attribute("size", "m"),
+                attribute("rarity", "rare"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_attributes_too_many() {
+        let updates = (0..=MAX_ATTRIBUTES)
+            .map(|i| attribute(&i.to_string(), "v"))
+            .collect();
+
+        let res = merge_attributes(vec![], updates, MAX_ATTRIBUTES);
+        assert_eq!(
+            res.unwrap_err(),
+            MplCoreWrapperError::TooManyAttributes.into()
+        );
+    }
+}
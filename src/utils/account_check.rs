@@ -1,19 +1,117 @@
+use mpl_core::types::Key;
+#[cfg(feature = "std")]
+use solana_program::msg;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
 };
 use solana_sdk_ids::system_program;
 
+use crate::error::MplCoreWrapperError;
+
+/// `account[<index>] <requirement>`, naming the slot a check failed on so
+/// clients can find the broken entry of their account list.
+pub fn account_error_message(index: usize, requirement: &str) -> String {
+    format!("account[{}] {}", index, requirement)
+}
+
+#[cfg_attr(
+    not(any(test, feature = "std", feature = "diagnostics")),
+    allow(unused_variables)
+)]
+fn log_account_error(index: usize, requirement: &str) {
+    #[cfg(feature = "std")]
+    msg!("{}", account_error_message(index, requirement));
+
+    #[cfg(feature = "diagnostics")]
+    crate::utils::record_first_account_error(account_error_message(index, requirement));
+
+    #[cfg(test)]
+    crate::utils::test_utils::record_account_error(account_error_message(index, requirement));
+}
+
 pub trait AccountCheck {
+    /// What the check requires of the account, as logged on failure.
+    const REQUIREMENT: &'static str;
+
     fn check<'info>(account: &AccountInfo<'info>) -> ProgramResult;
+
+    /// Runs [`check`](Self::check) on the account at slot `index` of the
+    /// instruction, logging the slot when it fails.
+    fn check_at<'info>(index: usize, account: &AccountInfo<'info>) -> ProgramResult {
+        Self::check(account).inspect_err(|_| log_account_error(index, Self::REQUIREMENT))
+    }
 }
 
-pub trait OptionalAccountCheck {
+pub trait OptionalAccountCheck: AccountCheck {
     fn check_optional<'info>(account: Option<&AccountInfo<'info>>) -> ProgramResult;
+
+    /// Runs [`check_optional`](Self::check_optional) on the account at slot
+    /// `index` of the instruction, logging the slot when it fails.
+    fn check_optional_at<'info>(
+        index: usize,
+        account: Option<&AccountInfo<'info>>,
+    ) -> ProgramResult {
+        Self::check_optional(account).inspect_err(|_| log_account_error(index, Self::REQUIREMENT))
+    }
+}
+
+/// Runs account checks without stopping at the first failure, so a
+/// misconfigured transaction logs every broken slot in one go.
+#[derive(Debug, Default)]
+pub struct AccountCheckBatch {
+    errors: Vec<ProgramError>,
+}
+
+impl AccountCheckBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn check_at<'info, C: AccountCheck>(
+        &mut self,
+        index: usize,
+        account: &AccountInfo<'info>,
+    ) -> &mut Self {
+        if let Err(error) = C::check_at(index, account) {
+            self.errors.push(error);
+        }
+
+        self
+    }
+
+    pub fn check_optional_at<'info, C: OptionalAccountCheck>(
+        &mut self,
+        index: usize,
+        account: Option<&AccountInfo<'info>>,
+    ) -> &mut Self {
+        if let Err(error) = C::check_optional_at(index, account) {
+            self.errors.push(error);
+        }
+
+        self
+    }
+
+    /// Every failure so far, in the order the checks ran.
+    pub fn errors(&self) -> &[ProgramError] {
+        &self.errors
+    }
+
+    /// Fails with the first error, the same code short-circuiting checks
+    /// would have returned.
+    pub fn finish(self) -> ProgramResult {
+        match self.errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
 }
 
 pub struct WritableAccount;
 
 impl AccountCheck for WritableAccount {
+    const REQUIREMENT: &'static str = "must be writable";
+
     fn check<'info>(account: &AccountInfo<'info>) -> ProgramResult {
         if !account.is_writable {
             return Err(ProgramError::InvalidAccountData);
@@ -35,9 +133,37 @@ impl OptionalAccountCheck for WritableAccount {
     }
 }
 
+/// For accounts only ever read, like the log wrapper, where a writable
+/// flag means the caller swapped accounts around.
+pub struct NonWritable;
+
+impl AccountCheck for NonWritable {
+    const REQUIREMENT: &'static str = "must not be writable";
+
+    fn check<'info>(account: &AccountInfo<'info>) -> ProgramResult {
+        if account.is_writable {
+            return Err(MplCoreWrapperError::UnexpectedWritableAccount.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl OptionalAccountCheck for NonWritable {
+    fn check_optional<'info>(account: Option<&AccountInfo<'info>>) -> ProgramResult {
+        if let Some(account) = account {
+            Self::check(account)?;
+        }
+
+        Ok(())
+    }
+}
+
 pub struct SignerAccount;
 
 impl AccountCheck for SignerAccount {
+    const REQUIREMENT: &'static str = "must sign";
+
     fn check<'info>(account: &AccountInfo<'info>) -> ProgramResult {
         if !account.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
@@ -59,9 +185,23 @@ impl OptionalAccountCheck for SignerAccount {
     }
 }
 
+impl SignerAccount {
+    /// Passes when at least one of the given accounts signed; absent accounts
+    /// never count.
+    pub fn check_any<'info>(accounts: &[Option<&AccountInfo<'info>>]) -> ProgramResult {
+        if !accounts.iter().flatten().any(|account| account.is_signer) {
+            return Err(MplCoreWrapperError::NoAuthorizedSigner.into());
+        }
+
+        Ok(())
+    }
+}
+
 pub struct SystemAccount;
 
 impl AccountCheck for SystemAccount {
+    const REQUIREMENT: &'static str = "must be owned by the system program";
+
     fn check<'info>(account: &AccountInfo<'info>) -> ProgramResult {
         if account.owner != &system_program::ID {
             return Err(ProgramError::InvalidAccountOwner);
@@ -86,6 +226,8 @@ impl OptionalAccountCheck for SystemAccount {
 pub struct MplCoreAccount;
 
 impl AccountCheck for MplCoreAccount {
+    const REQUIREMENT: &'static str = "must be owned by mpl_core";
+
     fn check<'info>(account: &AccountInfo<'info>) -> ProgramResult {
         if account.owner != &mpl_core::ID {
             return Err(ProgramError::InvalidAccountOwner);
@@ -95,11 +237,146 @@ impl AccountCheck for MplCoreAccount {
     }
 }
 
+pub struct NonDefaultKey;
+
+impl AccountCheck for NonDefaultKey {
+    const REQUIREMENT: &'static str = "must not be the default pubkey";
+
+    fn check<'info>(account: &AccountInfo<'info>) -> ProgramResult {
+        if account.key == &Pubkey::default() {
+            return Err(MplCoreWrapperError::InvalidAccountAddress.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Rejects a program id in a role account slot, a common copy-paste mistake
+/// in client account lists.
+pub struct NotProgramAccount;
+
+impl AccountCheck for NotProgramAccount {
+    const REQUIREMENT: &'static str = "must not be a program id";
+
+    fn check<'info>(account: &AccountInfo<'info>) -> ProgramResult {
+        if account.key == &mpl_core::ID || account.key == &system_program::ID {
+            return Err(MplCoreWrapperError::ProgramAccountInWrongSlot.into());
+        }
+
+        Ok(())
+    }
+}
+
+pub struct MinDataLen;
+
+impl MinDataLen {
+    /// Checks the account holds at least `min` bytes, before its data is
+    /// deserialized by hand.
+    pub fn check<'info>(account: &AccountInfo<'info>, min: usize) -> ProgramResult {
+        if account.data_len() < min {
+            return Err(MplCoreWrapperError::AccountDataTooSmall.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks the account's leading discriminator byte is `expected`, before its
+/// data is read as that mpl_core account.
+pub fn assert_account_key(account: &AccountInfo, expected: Key) -> Result<(), ProgramError> {
+    if account.try_borrow_data()?.first() != Some(&(expected as u8)) {
+        return Err(MplCoreWrapperError::InvalidAccountKey.into());
+    }
+
+    Ok(())
+}
+
+/// Checks no key fills two of `signers`, for signer roles an instruction
+/// needs held by different parties.
+pub fn check_distinct_signers(signers: &[&AccountInfo]) -> ProgramResult {
+    for (i, signer) in signers.iter().enumerate() {
+        if signers[..i].iter().any(|other| other.key == signer.key) {
+            return Err(MplCoreWrapperError::DuplicateSigner.into());
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utils::test_utils::*;
-    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn test_account_check_at_logs_index() {
+        let acc = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            10,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        take_recorded_account_errors();
+        assert!(WritableAccount::check_at(4, &acc).is_err());
+        assert!(SignerAccount::check_optional_at(2, Some(&acc)).is_err());
+        assert!(SignerAccount::check_optional_at(3, None).is_ok());
+        assert_eq!(
+            take_recorded_account_errors(),
+            vec!["account[4] must be writable", "account[2] must sign"]
+        );
+    }
+
+    #[test]
+    fn test_account_check_batch_collects_all_failures() {
+        let acc = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            10,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        take_recorded_account_errors();
+        let mut batch = AccountCheckBatch::new();
+        batch
+            .check_at::<WritableAccount>(0, &acc)
+            .check_at::<NonDefaultKey>(0, &acc)
+            .check_optional_at::<SignerAccount>(1, Some(&acc))
+            .check_optional_at::<SystemAccount>(2, Some(&acc));
+        assert_eq!(
+            batch.errors(),
+            [
+                ProgramError::InvalidAccountData,
+                ProgramError::MissingRequiredSignature,
+                ProgramError::InvalidAccountOwner,
+            ]
+        );
+        assert_eq!(batch.finish(), Err(ProgramError::InvalidAccountData));
+        assert_eq!(
+            take_recorded_account_errors(),
+            vec![
+                "account[0] must be writable",
+                "account[1] must sign",
+                "account[2] must be owned by the system program",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_account_check_batch_success() {
+        let acc = new_test_account(Pubkey::new_unique(), true, true, 10, 0, system_program::ID);
+
+        let mut batch = AccountCheckBatch::new();
+        batch
+            .check_at::<WritableAccount>(0, &acc)
+            .check_at::<SignerAccount>(0, &acc);
+        assert!(batch.errors().is_empty());
+        assert_eq!(batch.finish(), Ok(()));
+    }
 
     #[test]
     fn test_writable_account_check_success() {
@@ -158,6 +435,43 @@ mod tests {
         assert!(WritableAccount::check_optional(None).is_ok());
     }
 
+    #[test]
+    fn test_non_writable_check_success() {
+        let acc = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            10,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        assert!(NonWritable::check(&acc).is_ok());
+        assert!(NonWritable::check_optional(Some(&acc)).is_ok());
+        assert!(NonWritable::check_optional(None).is_ok());
+    }
+
+    #[test]
+    fn test_non_writable_check_failed() {
+        let acc = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            true,
+            10,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        assert_eq!(
+            NonWritable::check(&acc),
+            Err(MplCoreWrapperError::UnexpectedWritableAccount.into())
+        );
+        assert_eq!(
+            NonWritable::check_optional(Some(&acc)),
+            Err(MplCoreWrapperError::UnexpectedWritableAccount.into())
+        );
+    }
+
     #[test]
     fn test_signer_account_check_success() {
         let acc = new_test_account(
@@ -215,6 +529,58 @@ mod tests {
         assert!(SignerAccount::check_optional(None).is_ok());
     }
 
+    #[test]
+    fn test_signer_account_check_any() {
+        let signer = new_test_account(
+            Pubkey::new_unique(),
+            true,
+            false,
+            10,
+            0,
+            Pubkey::new_unique(),
+        );
+        let other_signer = new_test_account(
+            Pubkey::new_unique(),
+            true,
+            false,
+            10,
+            0,
+            Pubkey::new_unique(),
+        );
+        let non_signer = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            10,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        assert!(SignerAccount::check_any(&[Some(&non_signer), Some(&signer), None]).is_ok());
+        assert!(SignerAccount::check_any(&[Some(&signer), Some(&other_signer)]).is_ok());
+    }
+
+    #[test]
+    fn test_signer_account_check_any_failed() {
+        let non_signer = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            10,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        assert_eq!(
+            SignerAccount::check_any(&[Some(&non_signer), None]),
+            Err(MplCoreWrapperError::NoAuthorizedSigner.into())
+        );
+        assert_eq!(
+            SignerAccount::check_any(&[]),
+            Err(MplCoreWrapperError::NoAuthorizedSigner.into())
+        );
+    }
+
     #[test]
     fn test_system_account_check_success() {
         let acc = new_test_account(
@@ -290,4 +656,114 @@ mod tests {
         );
         assert!(MplCoreAccount::check(&acc).is_err());
     }
+
+    #[test]
+    fn test_non_default_key_check_success() {
+        let acc = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            10,
+            0,
+            Pubkey::new_unique(),
+        );
+        assert!(NonDefaultKey::check(&acc).is_ok());
+    }
+
+    #[test]
+    fn test_non_default_key_check_failed() {
+        let acc = new_test_account(Pubkey::default(), false, false, 10, 0, Pubkey::new_unique());
+        assert!(NonDefaultKey::check(&acc).is_err());
+    }
+
+    #[test]
+    fn test_not_program_account_check_success() {
+        let acc = new_test_account(Pubkey::new_unique(), false, false, 1, 0, mpl_core::ID);
+        assert!(NotProgramAccount::check(&acc).is_ok());
+    }
+
+    #[test]
+    fn test_not_program_account_check_failed() {
+        for key in [mpl_core::ID, system_program::ID] {
+            let acc = new_test_account(key, false, false, 1, 0, key);
+            assert_eq!(
+                NotProgramAccount::check(&acc),
+                Err(MplCoreWrapperError::ProgramAccountInWrongSlot.into())
+            );
+        }
+    }
+
+    #[test]
+    fn test_min_data_len_check_success() {
+        let acc = new_test_account(Pubkey::new_unique(), false, false, 1, 10, mpl_core::ID);
+        assert!(MinDataLen::check(&acc, 10).is_ok());
+        assert!(MinDataLen::check(&acc, 1).is_ok());
+    }
+
+    #[test]
+    fn test_min_data_len_check_failed() {
+        let acc = new_test_account(Pubkey::new_unique(), false, false, 1, 10, mpl_core::ID);
+        assert_eq!(
+            MinDataLen::check(&acc, 11),
+            Err(MplCoreWrapperError::AccountDataTooSmall.into())
+        );
+    }
+
+    #[test]
+    fn test_min_data_len_check_fixtures() {
+        let asset = new_asset_account(
+            Pubkey::new_unique(),
+            mpl_core::types::UpdateAuthority::None,
+            &[],
+        );
+        let collection = new_collection_account(Pubkey::new_unique());
+        assert!(MinDataLen::check(&asset, crate::utils::BASE_ASSET_MIN_LEN).is_ok());
+        assert!(MinDataLen::check(&collection, crate::utils::BASE_COLLECTION_MIN_LEN).is_ok());
+    }
+
+    #[test]
+    fn test_assert_account_key_asset() {
+        let asset = new_asset_account(
+            Pubkey::new_unique(),
+            mpl_core::types::UpdateAuthority::None,
+            &[],
+        );
+        assert!(assert_account_key(&asset, Key::AssetV1).is_ok());
+        assert_eq!(
+            assert_account_key(&asset, Key::CollectionV1),
+            Err(MplCoreWrapperError::InvalidAccountKey.into())
+        );
+    }
+
+    #[test]
+    fn test_assert_account_key_collection() {
+        let collection = new_collection_account(Pubkey::new_unique());
+        assert!(assert_account_key(&collection, Key::CollectionV1).is_ok());
+        assert_eq!(
+            assert_account_key(&collection, Key::AssetV1),
+            Err(MplCoreWrapperError::InvalidAccountKey.into())
+        );
+    }
+
+    #[test]
+    fn test_assert_account_key_empty_data() {
+        let acc = new_test_account(Pubkey::new_unique(), false, false, 1, 0, mpl_core::ID);
+        assert_eq!(
+            assert_account_key(&acc, Key::AssetV1),
+            Err(MplCoreWrapperError::InvalidAccountKey.into())
+        );
+    }
+
+    #[test]
+    fn test_check_distinct_signers() {
+        let a = new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        let b = new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        assert_eq!(check_distinct_signers(&[&a, &b]), Ok(()));
+
+        let a_again = new_test_account(*a.key, true, false, 1, 0, system_program::ID);
+        assert_eq!(
+            check_distinct_signers(&[&a, &b, &a_again]),
+            Err(MplCoreWrapperError::DuplicateSigner.into())
+        );
+    }
 }
@@ -0,0 +1,85 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::error::MplCoreWrapperError;
+
+/// Carves `accounts` into consecutive windows of the lengths in `layout`,
+/// one per sub-instruction of a composite instruction.
+///
+/// The windows must cover the slice exactly; a short or overlong slice fails
+/// with `AccountLayoutMismatch` rather than shifting every later window.
+pub fn split_accounts<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    layout: &[usize],
+) -> Result<Vec<&'a [AccountInfo<'info>]>, ProgramError> {
+    let mut rest = accounts;
+    let mut windows = Vec::with_capacity(layout.len());
+
+    for len in layout {
+        if rest.len() < *len {
+            return Err(MplCoreWrapperError::AccountLayoutMismatch.into());
+        }
+
+        let (window, tail) = rest.split_at(*len);
+        windows.push(window);
+        rest = tail;
+    }
+
+    if !rest.is_empty() {
+        return Err(MplCoreWrapperError::AccountLayoutMismatch.into());
+    }
+
+    Ok(windows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts(len: usize) -> Vec<AccountInfo<'static>> {
+        (0..len)
+            .map(|_| new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_accounts() {
+        let accounts = new_accounts(6);
+
+        let windows = split_accounts(&accounts, &[3, 0, 2, 1]).unwrap();
+        assert_eq!(
+            windows
+                .iter()
+                .map(|window| window.iter().map(|a| *a.key).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![
+                vec![*accounts[0].key, *accounts[1].key, *accounts[2].key],
+                vec![],
+                vec![*accounts[3].key, *accounts[4].key],
+                vec![*accounts[5].key],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_accounts_too_short() {
+        let accounts = new_accounts(4);
+
+        assert_eq!(
+            split_accounts(&accounts, &[3, 2]).unwrap_err(),
+            MplCoreWrapperError::AccountLayoutMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_split_accounts_leftover_accounts() {
+        let accounts = new_accounts(4);
+
+        assert_eq!(
+            split_accounts(&accounts, &[3]).unwrap_err(),
+            MplCoreWrapperError::AccountLayoutMismatch.into()
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use solana_program::{account_info::AccountInfo, instruction::AccountMeta};
+use solana_sdk_ids::system_program;
+
+/// Client-side view of an accounts struct as the `AccountMeta`s its
+/// instruction expects, in the order `try_from` reads them.
+pub trait ToAccountMetas {
+    fn to_metas(&self) -> Vec<AccountMeta>;
+}
+
+pub fn account_meta(account: &AccountInfo, is_signer: bool, is_writable: bool) -> AccountMeta {
+    AccountMeta {
+        pubkey: *account.key,
+        is_signer,
+        is_writable,
+    }
+}
+
+/// An absent optional account is passed as the system program, which
+/// `to_optional` reads back as `None`.
+pub fn optional_account_meta(
+    account: Option<&AccountInfo>,
+    is_signer: bool,
+    is_writable: bool,
+) -> AccountMeta {
+    match account {
+        Some(account) => account_meta(account, is_signer, is_writable),
+        None => AccountMeta::new_readonly(system_program::ID, false),
+    }
+}
+
+/// An absent system program is passed as the mpl_core program, which
+/// `optional_system_program` reads back as `None`.
+pub fn optional_system_program_meta(account: Option<&AccountInfo>) -> AccountMeta {
+    match account {
+        Some(account) => account_meta(account, false, false),
+        None => AccountMeta::new_readonly(mpl_core::ID, false),
+    }
+}
@@ -0,0 +1,67 @@
+use borsh::BorshSerialize;
+use solana_program::{instruction::Instruction, program_error::ProgramError};
+
+use crate::{instructions::Instructions, utils::ToAccountMetas};
+
+/// Builds the [`Instruction`] Anchor programs hand to `invoke` or
+/// `invoke_signed` (or wrap in a `CpiContext`) to call into this program,
+/// with the accounts in the order `try_from` reads them.
+pub trait ToInstruction: ToAccountMetas {
+    fn to_instruction(&self, instruction: &Instructions) -> Result<Instruction, ProgramError> {
+        Ok(Instruction {
+            program_id: crate::ID,
+            accounts: self.to_metas(),
+            data: instruction.try_to_vec()?,
+        })
+    }
+}
+
+impl<T: ToAccountMetas> ToInstruction for T {}
+
+#[cfg(all(test, feature = "transfer"))]
+mod tests {
+    use super::*;
+    use crate::{instructions::*, process_entrypoint, utils::test_utils::*};
+    use borsh::BorshDeserialize;
+    use mpl_core::types::UpdateAuthority;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    #[test]
+    fn test_to_instruction_matches_entrypoint_layout() {
+        let owner = Pubkey::new_unique();
+        let accounts = transfer_nft_accounts(
+            new_asset_account(owner, UpdateAuthority::None, &[]),
+            new_test_account(owner, true, false, 1, 0, system_program::ID),
+        );
+        let instruction = TransferNftV1Accounts::try_from(accounts.as_slice())
+            .unwrap()
+            .to_instruction(&Instructions::TransferNftV1(TransferNftV1InstructionData {
+                delegated_authority: false,
+                recipient_allow_list: None,
+                require_owner_signature: None,
+            }))
+            .unwrap();
+
+        assert_eq!(instruction.program_id, crate::ID);
+        assert_eq!(instruction.data[0], discriminant::TRANSFER_NFT_V1);
+        assert!(matches!(
+            Instructions::try_from_slice(&instruction.data),
+            Ok(Instructions::TransferNftV1(_))
+        ));
+        assert_eq!(
+            instruction
+                .accounts
+                .iter()
+                .map(|meta| meta.pubkey)
+                .collect::<Vec<_>>(),
+            accounts
+                .iter()
+                .map(|account| *account.key)
+                .collect::<Vec<_>>()
+        );
+
+        let res = process_entrypoint(&instruction.program_id, &accounts, &instruction.data);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+    }
+}
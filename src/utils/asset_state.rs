@@ -0,0 +1,85 @@
+use mpl_core::{
+    accounts::BaseCollectionV1,
+    fetch_collection_plugin,
+    types::{Key, PluginAuthority, PluginType, UpdateDelegate},
+};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::utils::{assert_account_key, MinDataLen, BASE_COLLECTION_MIN_LEN};
+
+/// Compressed (ledger-state) assets keep only a `HashedAssetV1` header on chain.
+pub fn is_ledger_state<'info>(asset: &AccountInfo<'info>) -> bool {
+    assert_account_key(asset, Key::HashedAssetV1).is_ok()
+}
+
+/// Whether `authority` is the collection's update authority or an update
+/// delegate of it, the keys mpl_core lets change the collection's assets.
+pub fn is_collection_authority(
+    collection: &AccountInfo,
+    authority: &Pubkey,
+) -> Result<bool, ProgramError> {
+    MinDataLen::check(collection, BASE_COLLECTION_MIN_LEN)?;
+    assert_account_key(collection, Key::CollectionV1)?;
+    let update_authority = BaseCollectionV1::try_from(collection)
+        .map_err(|_| ProgramError::InvalidAccountData)?
+        .update_authority;
+    if authority == &update_authority {
+        return Ok(true);
+    }
+
+    Ok(
+        match fetch_collection_plugin::<UpdateDelegate>(collection, PluginType::UpdateDelegate) {
+            Ok((PluginAuthority::Address { address }, _, _)) if &address == authority => true,
+            Ok((_, delegate, _)) => delegate.additional_delegates.contains(authority),
+            Err(_) => false,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn test_is_ledger_state_hashed_asset() {
+        let acc = new_test_account(Pubkey::new_unique(), false, true, 1, 1, mpl_core::ID);
+        acc.data.borrow_mut()[0] = Key::HashedAssetV1 as u8;
+        assert!(is_ledger_state(&acc));
+    }
+
+    #[test]
+    fn test_is_collection_authority() {
+        use mpl_core::types::Plugin;
+
+        let update_authority = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let additional_delegate = Pubkey::new_unique();
+        let collection = new_sized_collection_account(
+            update_authority,
+            0,
+            &[(
+                Plugin::UpdateDelegate(UpdateDelegate {
+                    additional_delegates: vec![additional_delegate],
+                }),
+                PluginAuthority::Address { address: delegate },
+            )],
+        );
+
+        for authority in [update_authority, delegate, additional_delegate] {
+            assert_eq!(is_collection_authority(&collection, &authority), Ok(true));
+        }
+        assert_eq!(
+            is_collection_authority(&collection, &Pubkey::new_unique()),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_is_ledger_state_account_state_asset() {
+        let acc = new_test_account(Pubkey::new_unique(), false, true, 1, 1, mpl_core::ID);
+        acc.data.borrow_mut()[0] = Key::AssetV1 as u8;
+        assert!(!is_ledger_state(&acc));
+    }
+}
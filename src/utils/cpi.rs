@@ -0,0 +1,126 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::get_stack_height,
+    pubkey::Pubkey,
+};
+use solana_sdk_ids::system_program;
+
+#[cfg(not(test))]
+use solana_program::program::{get_return_data, set_return_data};
+
+use crate::error::MplCoreWrapperError;
+#[cfg(test)]
+use crate::utils::test_utils::{get_return_data, set_return_data};
+
+/// Highest invoke stack height this program CPIs from. The runtime caps the
+/// stack at 5 and mpl_core may CPI once more itself, e.g. into the system
+/// program.
+pub const MAX_CPI_STACK_HEIGHT: usize = 3;
+
+/// Fails when a CPI made at `stack_height` could push the CPIs below it past
+/// the runtime's invoke stack limit.
+pub fn check_stack_height(stack_height: usize) -> ProgramResult {
+    if stack_height > MAX_CPI_STACK_HEIGHT {
+        return Err(MplCoreWrapperError::CpiDepthExceeded.into());
+    }
+
+    Ok(())
+}
+
+/// Issues one CPI acting on `target`, failing up front when the invoke stack
+/// is already too deep for it to succeed.
+///
+/// Off-chain `invoke` is a no-op, so tests record `name` and `target` instead
+/// to check which CPIs a handler issues and in what order.
+#[cfg_attr(not(test), allow(unused_variables))]
+pub fn invoke_cpi(
+    name: &'static str,
+    target: &Pubkey,
+    invoke: impl FnOnce() -> ProgramResult,
+) -> ProgramResult {
+    check_stack_height(get_stack_height())?;
+
+    #[cfg(test)]
+    crate::utils::test_utils::record_cpi(name, *target);
+
+    invoke()
+}
+
+/// Re-sets the return data left by a CPI as this program's own, so callers
+/// of this program can read what the wrapped instruction returned.
+pub fn propagate_return_data() {
+    if let Some((_, data)) = get_return_data() {
+        set_return_data(&data);
+    }
+}
+
+/// Checks the only program accounts a multi-CPI handler was given are the
+/// validated `mpl_core` account every CPI targets, the system program and the
+/// log wrapper, so no second program account can stand in for `mpl_core`.
+pub fn check_cpi_programs(
+    accounts: &[AccountInfo],
+    mpl_core: &AccountInfo,
+    log_wrapper: Option<&AccountInfo>,
+) -> ProgramResult {
+    let is_expected = |key: &Pubkey| {
+        key == mpl_core.key
+            || key == &system_program::ID
+            || log_wrapper.is_some_and(|log_wrapper| log_wrapper.key == key)
+    };
+
+    if accounts
+        .iter()
+        .any(|account| account.executable && !is_expected(account.key))
+    {
+        return Err(MplCoreWrapperError::MplCoreProgramMismatch.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+
+    #[test]
+    fn test_check_stack_height() {
+        assert_eq!(check_stack_height(1), Ok(()));
+        assert_eq!(check_stack_height(MAX_CPI_STACK_HEIGHT), Ok(()));
+        assert_eq!(
+            check_stack_height(MAX_CPI_STACK_HEIGHT + 1),
+            Err(MplCoreWrapperError::CpiDepthExceeded.into())
+        );
+    }
+
+    #[test]
+    fn test_check_cpi_programs() {
+        let mpl_core = new_program_account(mpl_core::ID);
+        let log_wrapper = new_program_account(Pubkey::new_unique());
+        let accounts = vec![
+            new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+            new_program_account(system_program::ID),
+            log_wrapper.clone(),
+            mpl_core.clone(),
+        ];
+        assert_eq!(
+            check_cpi_programs(&accounts, &mpl_core, Some(&log_wrapper)),
+            Ok(())
+        );
+
+        let mut accounts = accounts;
+        accounts.push(new_program_account(Pubkey::new_unique()));
+        assert_eq!(
+            check_cpi_programs(&accounts, &mpl_core, Some(&log_wrapper)),
+            Err(MplCoreWrapperError::MplCoreProgramMismatch.into())
+        );
+    }
+
+    #[test]
+    fn test_invoke_cpi_records_target() {
+        let target = Pubkey::new_unique();
+
+        take_recorded_cpis();
+        assert_eq!(invoke_cpi("TransferV1", &target, || Ok(())), Ok(()));
+        assert_eq!(take_recorded_cpi_targets(), vec![("TransferV1", target)]);
+    }
+}
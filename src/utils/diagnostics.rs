@@ -0,0 +1,192 @@
+use std::cell::RefCell;
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::instructions::*;
+
+thread_local! {
+    static FIRST_ACCOUNT_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Keeps the first account check failure since the last diagnosis.
+pub fn record_first_account_error(message: String) {
+    FIRST_ACCOUNT_ERROR.with(|first| {
+        first.borrow_mut().get_or_insert(message);
+    });
+}
+
+/// Parses `accounts` the way the instruction with `discriminant` does and
+/// describes the first problem found, e.g. `account[3] must sign`, or `None`
+/// when they parse.
+///
+/// Only account layout and flags are checked; asset state and instruction
+/// data are left to the instruction itself. Composite instructions are
+/// checked against the accounts of the instruction they wrap.
+pub fn diagnose_accounts(accounts: &[AccountInfo], discriminant: u8) -> Option<String> {
+    FIRST_ACCOUNT_ERROR.with(RefCell::take);
+    let error = parse_accounts(accounts, discriminant)?.err()?;
+
+    Some(match (FIRST_ACCOUNT_ERROR.with(RefCell::take), error) {
+        (Some(message), _) => message,
+        (None, ProgramError::NotEnoughAccountKeys) => {
+            format!("wrong account count: got {}", accounts.len())
+        }
+        (None, error) => format!("accounts rejected: {}", error),
+    })
+}
+
+/// `None` for a discriminant without accounts to check in this build.
+fn parse_accounts(accounts: &[AccountInfo], discriminant: u8) -> Option<ProgramResult> {
+    let result = match discriminant {
+        #[cfg(feature = "create")]
+        discriminant::CREATE_NFT_V1
+        | discriminant::CREATE_NFT_V2
+        | discriminant::CREATE_NFT_WITH_PLUGIN_V1 => {
+            CreateNftV1Accounts::try_from(accounts).map(|_| ())
+        }
+        #[cfg(feature = "create")]
+        discriminant::CREATE_COLLECTION_V2 => {
+            CreateCollectionV2Accounts::try_from(accounts).map(|_| ())
+        }
+        #[cfg(feature = "update")]
+        discriminant::UPDATE_NFT_V1 => UpdateNftV1Accounts::try_from(accounts).map(|_| ()),
+        #[cfg(feature = "update")]
+        discriminant::ADD_TO_COLLECTION_V1 => {
+            AddToCollectionV1Accounts::try_from(accounts).map(|_| ())
+        }
+        #[cfg(feature = "update")]
+        discriminant::REMOVE_FROM_COLLECTION_V1 => {
+            RemoveFromCollectionV1Accounts::try_from(accounts).map(|_| ())
+        }
+        #[cfg(feature = "update")]
+        discriminant::UPDATE_NFT_BATCH_V1 => {
+            UpdateNftBatchV1Accounts::try_from(accounts).map(|_| ())
+        }
+        #[cfg(feature = "transfer")]
+        discriminant::TRANSFER_NFT_V1
+        | discriminant::TRANSFER_WITH_PROVENANCE_V1
+        | discriminant::TRANSFER_AND_FREEZE_V1 => {
+            TransferNftV1Accounts::try_from(accounts).map(|_| ())
+        }
+        #[cfg(feature = "transfer")]
+        discriminant::THAW_AND_TRANSFER_V1 => match accounts.split_last() {
+            Some((_, transfer_accounts)) => {
+                TransferNftV1Accounts::try_from(transfer_accounts).map(|_| ())
+            }
+            None => Err(ProgramError::NotEnoughAccountKeys),
+        },
+        #[cfg(feature = "transfer")]
+        discriminant::TRANSFER_NFT_BATCH_V1 => {
+            TransferNftBatchV1Accounts::try_from(accounts).map(|_| ())
+        }
+        discriminant::EXECUTE_NFT_V1 => ExecuteNftV1Accounts::try_from(accounts).map(|_| ()),
+        discriminant::GET_ASSET_INFO_V1 => GetAssetInfoV1Accounts::try_from(accounts).map(|_| ()),
+        discriminant::TRANSFER_COLLECTION_AUTHORITY_V1 => {
+            TransferCollectionAuthorityV1Accounts::try_from(accounts).map(|_| ())
+        }
+        discriminant::ADD_AUTOGRAPH_V1 => AddAutographV1Accounts::try_from(accounts).map(|_| ()),
+        discriminant::UPDATE_ATTRIBUTES_V1 => {
+            UpdateAttributesV1Accounts::try_from(accounts).map(|_| ())
+        }
+        discriminant::REMOVE_PLUGIN_V1 => RemovePluginV1Accounts::try_from(accounts).map(|_| ()),
+        discriminant::UPDATE_ROYALTY_RULESET_V1 => {
+            UpdateRoyaltyRulesetV1Accounts::try_from(accounts).map(|_| ())
+        }
+        discriminant::ADD_PLUGIN_V1 => AddPluginV1Accounts::try_from(accounts).map(|_| ()),
+        discriminant::BURN_AND_REFUND_V1 => BurnAndRefundV1Accounts::try_from(accounts).map(|_| ()),
+        discriminant::REVOKE_ALL_PLUGIN_AUTHORITIES_V1 => {
+            RevokeAllPluginAuthoritiesV1Accounts::try_from(accounts).map(|_| ())
+        }
+        discriminant::UPDATE_EXTERNAL_PLUGIN_ADAPTER_V1 => {
+            UpdateExternalPluginAdapterV1Accounts::try_from(accounts).map(|_| ())
+        }
+        discriminant::UPDATE_PLUGINS_BATCH_V1 => {
+            UpdatePluginsBatchV1Accounts::try_from(accounts).map(|_| ())
+        }
+        discriminant::SET_PERMANENT_BURN_DELEGATE_V1 => {
+            SetPermanentBurnDelegateV1Accounts::try_from(accounts).map(|_| ())
+        }
+        discriminant::DELEGATE_BURN_V1 => DelegateBurnV1Accounts::try_from(accounts).map(|_| ()),
+        _ => return None,
+    };
+
+    Some(result)
+}
+
+#[cfg(all(test, feature = "transfer"))]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk_ids::system_program;
+
+    fn new_accounts() -> Vec<AccountInfo<'static>> {
+        transfer_nft_accounts(
+            new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        )
+    }
+
+    fn diagnose(accounts: &[AccountInfo]) -> Option<String> {
+        diagnose_accounts(accounts, discriminant::TRANSFER_NFT_V1)
+    }
+
+    #[test]
+    fn test_diagnose_valid_accounts() {
+        assert_eq!(diagnose(&new_accounts()), None);
+    }
+
+    #[test]
+    fn test_diagnose_wrong_count() {
+        let accounts = new_accounts();
+        assert_eq!(
+            diagnose(&accounts[..5]),
+            Some("wrong account count: got 5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_diagnose_wrong_program() {
+        let mut accounts = new_accounts();
+        accounts[7] = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            1,
+            0,
+            Pubkey::new_unique(),
+        );
+        assert_eq!(
+            diagnose(&accounts),
+            Some("account[7] must be owned by mpl_core".to_string())
+        );
+    }
+
+    #[test]
+    fn test_diagnose_missing_signer() {
+        let mut accounts = new_accounts();
+        accounts[4] = new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID);
+        assert_eq!(
+            diagnose(&accounts),
+            Some("account[4] must sign".to_string())
+        );
+    }
+
+    #[test]
+    fn test_diagnose_not_writable() {
+        let mut accounts = new_accounts();
+        accounts[0] =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID);
+        assert_eq!(
+            diagnose(&accounts),
+            Some("account[0] must be writable".to_string())
+        );
+    }
+
+    #[test]
+    fn test_diagnose_unknown_instruction() {
+        assert_eq!(diagnose_accounts(&new_accounts(), u8::MAX), None);
+    }
+}
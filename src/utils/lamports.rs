@@ -0,0 +1,142 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError, rent::Rent,
+};
+
+use crate::error::MplCoreWrapperError;
+
+/// Moves `amount` lamports directly between two accounts.
+///
+/// Only valid when `from` is owned by this program; system-owned payers still
+/// have to go through a system transfer.
+pub fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> ProgramResult {
+    let mut from_lamports = from.try_borrow_mut_lamports()?;
+    let mut to_lamports = to.try_borrow_mut_lamports()?;
+
+    let new_from = from_lamports
+        .checked_sub(amount)
+        .ok_or(MplCoreWrapperError::LamportOverflow)?;
+    let new_to = to_lamports
+        .checked_add(amount)
+        .ok_or(MplCoreWrapperError::LamportOverflow)?;
+
+    **from_lamports = new_from;
+    **to_lamports = new_to;
+
+    Ok(())
+}
+
+/// Lamports `account` gained while `f` ran, e.g. what a CPI paid out to it.
+pub fn lamports_received(
+    account: &AccountInfo,
+    f: impl FnOnce() -> ProgramResult,
+) -> Result<u64, ProgramError> {
+    let before = account.lamports();
+    f()?;
+
+    Ok(account.lamports().saturating_sub(before))
+}
+
+/// Checks `account` holds enough lamports to be rent exempt at its current
+/// size, catching a CPI that succeeded but left it underfunded.
+pub fn check_rent_exempt(account: &AccountInfo, rent: &Rent) -> ProgramResult {
+    if !rent.is_exempt(account.lamports(), account.data_len()) {
+        return Err(MplCoreWrapperError::AssetNotRentExempt.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use solana_program::pubkey::Pubkey;
+
+    fn new_account(lamports: u64) -> AccountInfo<'static> {
+        new_test_account(Pubkey::new_unique(), false, true, lamports, 0, crate::ID)
+    }
+
+    #[test]
+    fn test_transfer_lamports() {
+        let from = new_account(10);
+        let to = new_account(5);
+
+        let res = transfer_lamports(&from, &to, 4);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(from.lamports(), 6);
+        assert_eq!(to.lamports(), 9);
+    }
+
+    #[test]
+    fn test_lamports_received() {
+        let asset = new_account(500);
+        let receiver = new_account(5);
+
+        // Like a burn that leaves tombstone lamports on the asset.
+        let received = lamports_received(&receiver, || transfer_lamports(&asset, &receiver, 400));
+        assert_eq!(received, Ok(400));
+        assert_eq!(asset.lamports(), 100);
+
+        let received = lamports_received(&receiver, || transfer_lamports(&receiver, &asset, 5));
+        assert_eq!(received, Ok(0));
+    }
+
+    #[test]
+    fn test_check_rent_exempt_funded() {
+        let rent = Rent::default();
+        let asset = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            true,
+            rent.minimum_balance(100),
+            100,
+            mpl_core::ID,
+        );
+
+        assert_eq!(check_rent_exempt(&asset, &rent), Ok(()));
+    }
+
+    #[test]
+    fn test_check_rent_exempt_underfunded() {
+        let rent = Rent::default();
+        let asset = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            true,
+            rent.minimum_balance(100) - 1,
+            100,
+            mpl_core::ID,
+        );
+
+        assert_eq!(
+            check_rent_exempt(&asset, &rent),
+            Err(MplCoreWrapperError::AssetNotRentExempt.into())
+        );
+    }
+
+    #[test]
+    fn test_transfer_lamports_underflow() {
+        let from = new_account(3);
+        let to = new_account(5);
+
+        assert_eq!(
+            transfer_lamports(&from, &to, 4),
+            Err(MplCoreWrapperError::LamportOverflow.into())
+        );
+        assert_eq!(from.lamports(), 3);
+        assert_eq!(to.lamports(), 5);
+    }
+
+    #[test]
+    fn test_transfer_lamports_overflow() {
+        let from = new_account(3);
+        let to = new_account(u64::MAX);
+
+        assert_eq!(
+            transfer_lamports(&from, &to, 1),
+            Err(MplCoreWrapperError::LamportOverflow.into())
+        );
+        assert_eq!(from.lamports(), 3);
+        assert_eq!(to.lamports(), u64::MAX);
+    }
+}
@@ -0,0 +1,60 @@
+use solana_program::entrypoint::ProgramResult;
+
+#[cfg(feature = "strict-metadata")]
+use crate::error::MplCoreWrapperError;
+
+/// Rejects a name or uri containing ASCII control characters, NUL included,
+/// which off-chain tooling may mis-render or interpret.
+///
+/// Only enforced with the `strict-metadata` feature; otherwise any string
+/// mpl_core accepts passes.
+#[cfg_attr(not(feature = "strict-metadata"), allow(unused_variables))]
+pub fn check_metadata_text(value: &str) -> ProgramResult {
+    #[cfg(feature = "strict-metadata")]
+    if value.chars().any(|c| c.is_ascii_control()) {
+        return Err(MplCoreWrapperError::InvalidMetadataCharacters.into());
+    }
+
+    Ok(())
+}
+
+/// Runs [`check_metadata_text`] on an asset or collection name and uri.
+pub fn check_metadata(name: &str, uri: &str) -> ProgramResult {
+    check_metadata_text(name)?;
+    check_metadata_text(uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_metadata_clean() {
+        assert_eq!(
+            check_metadata("Asset #1", "https://example.com/asset.json"),
+            Ok(())
+        );
+    }
+
+    #[cfg(feature = "strict-metadata")]
+    #[test]
+    fn test_check_metadata_control_characters() {
+        assert_eq!(
+            check_metadata("Asset\0", "https://example.com/asset.json"),
+            Err(MplCoreWrapperError::InvalidMetadataCharacters.into())
+        );
+        assert_eq!(
+            check_metadata("Asset", "https://example.com/\nasset.json"),
+            Err(MplCoreWrapperError::InvalidMetadataCharacters.into())
+        );
+    }
+
+    #[cfg(not(feature = "strict-metadata"))]
+    #[test]
+    fn test_check_metadata_control_characters_not_strict() {
+        assert_eq!(
+            check_metadata("Asset\0", "https://example.com/asset.json"),
+            Ok(())
+        );
+    }
+}
@@ -1,10 +1,42 @@
 pub mod account_check;
+pub mod account_layout;
+#[cfg(feature = "client")]
+pub mod account_meta;
+#[cfg(feature = "anchor")]
+pub mod anchor;
+#[cfg(feature = "ledger-state")]
+pub mod asset_state;
+pub mod cpi;
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+pub mod lamports;
+pub mod metadata;
 pub mod optional_account;
+pub mod pda;
 pub mod process;
+pub mod processed_flag;
+pub mod realloc;
+pub mod size;
 
 #[cfg(test)]
 pub mod test_utils;
 
 pub use account_check::*;
+pub use account_layout::*;
+#[cfg(feature = "client")]
+pub use account_meta::*;
+#[cfg(feature = "anchor")]
+pub use anchor::*;
+#[cfg(feature = "ledger-state")]
+pub use asset_state::*;
+pub use cpi::*;
+#[cfg(feature = "diagnostics")]
+pub use diagnostics::*;
+pub use lamports::*;
+pub use metadata::*;
 pub use optional_account::*;
+pub use pda::*;
 pub use process::*;
+pub use processed_flag::*;
+pub use realloc::*;
+pub use size::*;
@@ -1,6 +1,17 @@
-use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+#[cfg(feature = "asset-state")]
+use mpl_core::{
+    accounts::BaseAssetV1,
+    types::{Key, UpdateAuthority},
+};
+#[cfg(feature = "asset-state")]
+use solana_program::program_error::ProgramError;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 use solana_sdk_ids::system_program;
 
+use crate::error::MplCoreWrapperError;
+#[cfg(feature = "asset-state")]
+use crate::utils::{assert_account_key, MinDataLen, BASE_ASSET_MIN_LEN};
+
 pub trait ToOptionalAccount<'a, 'info> {
     fn to_optional(self) -> Option<&'a AccountInfo<'info>>;
 }
@@ -15,6 +26,66 @@ impl<'a, 'info> ToOptionalAccount<'a, 'info> for &'a AccountInfo<'info> {
     }
 }
 
+/// Trailing accounts of an instruction whose last account is optional: the
+/// client may leave it off entirely or pass a placeholder.
+impl<'a, 'info> ToOptionalAccount<'a, 'info> for &'a [AccountInfo<'info>] {
+    fn to_optional(self) -> Option<&'a AccountInfo<'info>> {
+        self.first().and_then(ToOptionalAccount::to_optional)
+    }
+}
+
+/// The system program can't stand in for its own absence, so an omitted
+/// system program slot holds the mpl_core program, as mpl_core's own clients
+/// fill their omitted accounts.
+pub fn optional_system_program<'a, 'info>(
+    account: &'a AccountInfo<'info>,
+) -> Option<&'a AccountInfo<'info>> {
+    (account.key != &mpl_core::ID).then_some(account)
+}
+
+/// Optional accounts that only make sense as a pair must be both present or both absent.
+pub fn require_together<'info>(
+    a: Option<&AccountInfo<'info>>,
+    b: Option<&AccountInfo<'info>>,
+) -> ProgramResult {
+    if a.is_some() != b.is_some() {
+        return Err(MplCoreWrapperError::InconsistentOptionalAccounts.into());
+    }
+
+    Ok(())
+}
+
+/// An optional account filled in although the instruction data never uses
+/// it usually means the client shifted its accounts.
+#[cfg(feature = "strict-accounts")]
+pub fn reject_unused_account(account: Option<&AccountInfo>, used: bool) -> ProgramResult {
+    if account.is_some() && !used {
+        return Err(MplCoreWrapperError::UnexpectedAccount.into());
+    }
+
+    Ok(())
+}
+
+/// mpl_core needs the collection of an asset that belongs to one for every
+/// plugin operation, so an asset in a collection requires that account.
+#[cfg(feature = "asset-state")]
+pub fn check_collection_account(
+    asset: &AccountInfo,
+    collection: Option<&AccountInfo>,
+) -> ProgramResult {
+    MinDataLen::check(asset, BASE_ASSET_MIN_LEN)?;
+    assert_account_key(asset, Key::AssetV1)?;
+    let asset = BaseAssetV1::try_from(asset).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    if let UpdateAuthority::Collection(expected) = asset.update_authority {
+        if collection.map(|collection| collection.key) != Some(&expected) {
+            return Err(MplCoreWrapperError::CollectionAccountRequired.into());
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,4 +116,44 @@ mod tests {
         );
         assert!(acc.to_optional().is_none());
     }
+
+    #[test]
+    fn test_optional_system_program() {
+        let acc = new_test_account(system_program::ID, false, false, 1, 0, system_program::ID);
+        assert!(optional_system_program(&acc).is_some());
+
+        let acc = new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID);
+        assert!(optional_system_program(&acc).is_none());
+    }
+
+    #[test]
+    fn test_require_together() {
+        let a = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            10u64,
+            0,
+            Pubkey::new_unique(),
+        );
+        let b = new_test_account(
+            Pubkey::new_unique(),
+            false,
+            false,
+            10u64,
+            0,
+            Pubkey::new_unique(),
+        );
+
+        assert!(require_together(Some(&a), Some(&b)).is_ok());
+        assert!(require_together(None, None).is_ok());
+        assert_eq!(
+            require_together(Some(&a), None),
+            Err(MplCoreWrapperError::InconsistentOptionalAccounts.into())
+        );
+        assert_eq!(
+            require_together(None, Some(&b)),
+            Err(MplCoreWrapperError::InconsistentOptionalAccounts.into())
+        );
+    }
 }
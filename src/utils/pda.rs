@@ -0,0 +1,213 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::MplCoreWrapperError,
+    utils::{AccountCheck, OptionalAccountCheck, SignerAccount},
+};
+
+pub const ASSET_SIGNER_SEED: &[u8] = b"mpl-core-execute";
+
+pub const ASSET_SEED: &[u8] = b"asset";
+
+pub const PROGRAM_AUTHORITY_SEED: &[u8] = b"authority";
+
+pub fn derive_asset_signer(asset: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ASSET_SIGNER_SEED, asset.as_ref()], &mpl_core::ID)
+}
+
+pub fn check_asset_signer<'info>(
+    asset: &AccountInfo<'info>,
+    asset_signer: &AccountInfo<'info>,
+) -> ProgramResult {
+    let (expected, _) = derive_asset_signer(asset.key);
+
+    if asset_signer.key != &expected {
+        return Err(MplCoreWrapperError::InvalidAssetSigner.into());
+    }
+
+    Ok(())
+}
+
+/// Asset PDA of this program for a client-chosen `nonce`, so retries of the
+/// same create land on the same address.
+pub fn derive_asset_address(payer: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ASSET_SEED, payer.as_ref(), &nonce.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Authority PDA this program signs as on behalf of `controller`. Only
+/// instructions `controller` signed can use it, so whatever it holds or
+/// updates stays under `controller`'s control.
+pub fn derive_delegated_authority(controller: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_AUTHORITY_SEED, controller.as_ref()], &crate::ID)
+}
+
+/// Seeds, bump included, of [`derive_delegated_authority`].
+pub fn delegated_authority_seeds(controller: &Pubkey, bump: u8) -> Vec<Vec<u8>> {
+    vec![
+        PROGRAM_AUTHORITY_SEED.to_vec(),
+        controller.to_bytes().to_vec(),
+        vec![bump],
+    ]
+}
+
+/// Checks `authority` either signed the transaction or, when `delegated`,
+/// is the delegated authority of `controller`, which must have signed.
+///
+/// Returns the seeds the program signs for `authority` with; they are
+/// derived here rather than taken from the client, so no caller can sign as
+/// another controller's authority.
+pub fn resolve_authority<'info>(
+    authority: Option<&AccountInfo<'info>>,
+    controller: &AccountInfo<'info>,
+    delegated: bool,
+) -> Result<Option<Vec<Vec<u8>>>, ProgramError> {
+    if !delegated {
+        SignerAccount::check_optional(authority)?;
+        return Ok(None);
+    }
+
+    let Some(authority) = authority else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    SignerAccount::check(controller)?;
+    let (expected, bump) = derive_delegated_authority(controller.key);
+
+    if authority.key != &expected {
+        return Err(MplCoreWrapperError::InvalidSignerSeeds.into());
+    }
+
+    Ok(Some(delegated_authority_seeds(controller.key, bump)))
+}
+
+/// Checks `authority` is one of the `approved` keys, for callers that gate an
+/// instruction on a set of authorities rather than a single one.
+pub fn check_approved_authority(
+    authority: Option<&AccountInfo>,
+    approved: &[Pubkey],
+) -> ProgramResult {
+    match authority {
+        Some(authority) if approved.contains(authority.key) => Ok(()),
+        _ => Err(MplCoreWrapperError::AuthorityNotApproved.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+
+    #[test]
+    fn test_derive_asset_signer() {
+        let asset = Pubkey::new_unique();
+        let (asset_signer, bump) = derive_asset_signer(&asset);
+
+        let expected = Pubkey::create_program_address(
+            &[ASSET_SIGNER_SEED, asset.as_ref(), &[bump]],
+            &mpl_core::ID,
+        );
+        assert_eq!(expected, Ok(asset_signer));
+    }
+
+    #[test]
+    fn test_check_asset_signer_success() {
+        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, mpl_core::ID);
+        let (asset_signer, _) = derive_asset_signer(asset.key);
+        let asset_signer = new_test_account(asset_signer, false, false, 1, 0, Pubkey::default());
+
+        assert!(check_asset_signer(&asset, &asset_signer).is_ok());
+    }
+
+    #[test]
+    fn test_check_asset_signer_failed() {
+        let asset = new_test_account(Pubkey::new_unique(), false, true, 1, 0, mpl_core::ID);
+        let asset_signer =
+            new_test_account(Pubkey::new_unique(), false, false, 1, 0, Pubkey::default());
+
+        assert_eq!(
+            check_asset_signer(&asset, &asset_signer),
+            Err(MplCoreWrapperError::InvalidAssetSigner.into()),
+        );
+    }
+
+    #[test]
+    fn test_resolve_authority_delegated() {
+        let controller =
+            new_test_account(Pubkey::new_unique(), true, true, 1, 0, Pubkey::default());
+        let (pda, bump) = derive_delegated_authority(controller.key);
+        let authority = new_test_account(pda, false, false, 1, 0, Pubkey::default());
+
+        assert_eq!(
+            resolve_authority(Some(&authority), &controller, true),
+            Ok(Some(delegated_authority_seeds(controller.key, bump)))
+        );
+        assert_eq!(
+            resolve_authority(None, &controller, true),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+        // Unsigned, the PDA isn't the caller's to use.
+        assert_eq!(
+            resolve_authority(Some(&authority), &controller, false),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+    }
+
+    #[test]
+    fn test_resolve_authority_other_controller() {
+        let victim = Pubkey::new_unique();
+        let (pda, _) = derive_delegated_authority(&victim);
+        let authority = new_test_account(pda, false, false, 1, 0, Pubkey::default());
+        let caller = new_test_account(Pubkey::new_unique(), true, true, 1, 0, Pubkey::default());
+
+        assert_eq!(
+            resolve_authority(Some(&authority), &caller, true),
+            Err(MplCoreWrapperError::InvalidSignerSeeds.into())
+        );
+
+        let victim = new_test_account(victim, false, true, 1, 0, Pubkey::default());
+        assert_eq!(
+            resolve_authority(Some(&authority), &victim, true),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+    }
+
+    #[test]
+    fn test_derive_asset_address_nonce() {
+        let payer = Pubkey::new_unique();
+
+        assert_eq!(
+            derive_asset_address(&payer, 7),
+            derive_asset_address(&payer, 7)
+        );
+        assert_ne!(
+            derive_asset_address(&payer, 7).0,
+            derive_asset_address(&payer, 8).0
+        );
+        assert_ne!(
+            derive_asset_address(&payer, 7).0,
+            derive_asset_address(&Pubkey::new_unique(), 7).0
+        );
+    }
+
+    #[test]
+    fn test_check_approved_authority() {
+        let authority =
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, Pubkey::default());
+        let approved = [Pubkey::new_unique(), *authority.key];
+
+        assert!(check_approved_authority(Some(&authority), &approved).is_ok());
+        assert_eq!(
+            check_approved_authority(Some(&authority), &approved[..1]),
+            Err(MplCoreWrapperError::AuthorityNotApproved.into())
+        );
+        assert_eq!(
+            check_approved_authority(None, &approved),
+            Err(MplCoreWrapperError::AuthorityNotApproved.into())
+        );
+    }
+}
@@ -1,5 +1,151 @@
 use solana_program::entrypoint::ProgramResult;
+#[cfg(feature = "std")]
+use solana_program::msg;
 
 pub trait ProcessInstruction {
     fn process(self) -> ProgramResult;
 }
+
+/// Cross-cutting behaviour run around an instruction by [`Middleware`].
+pub trait MiddlewareHooks {
+    /// Runs before the instruction; an error skips it.
+    fn before(&mut self) -> ProgramResult {
+        Ok(())
+    }
+
+    /// Runs after the instruction with its result and returns the result the
+    /// wrapper reports.
+    fn after(&mut self, result: ProgramResult) -> ProgramResult {
+        result
+    }
+}
+
+/// Wraps an instruction so `hooks` run around its `process`, without the
+/// handler itself knowing about them.
+#[derive(Debug)]
+pub struct Middleware<H, I> {
+    pub hooks: H,
+    pub inner: I,
+}
+
+impl<H: MiddlewareHooks, I: ProcessInstruction> Middleware<H, I> {
+    pub fn new(hooks: H, inner: I) -> Self {
+        Self { hooks, inner }
+    }
+}
+
+impl<H: MiddlewareHooks, I: ProcessInstruction> ProcessInstruction for Middleware<H, I> {
+    fn process(mut self) -> ProgramResult {
+        self.hooks.before()?;
+        let result = self.inner.process();
+        self.hooks.after(result)
+    }
+}
+
+/// Logs when the wrapped instruction starts and how it finished.
+#[derive(Debug)]
+pub struct LogMiddleware {
+    pub name: &'static str,
+}
+
+impl MiddlewareHooks for LogMiddleware {
+    fn before(&mut self) -> ProgramResult {
+        #[cfg(feature = "std")]
+        msg!("{}: start", self.name);
+
+        Ok(())
+    }
+
+    fn after(&mut self, result: ProgramResult) -> ProgramResult {
+        #[cfg(feature = "std")]
+        match &result {
+            Ok(()) => msg!("{}: ok", self.name),
+            Err(err) => msg!("{}: failed with {}", self.name, err),
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::program_error::ProgramError;
+    use std::{cell::RefCell, rc::Rc};
+
+    type Calls = Rc<RefCell<Vec<&'static str>>>;
+
+    struct Recorder {
+        calls: Calls,
+        fail_before: bool,
+    }
+
+    impl MiddlewareHooks for Recorder {
+        fn before(&mut self) -> ProgramResult {
+            self.calls.borrow_mut().push("before");
+            if self.fail_before {
+                return Err(ProgramError::Custom(1));
+            }
+
+            Ok(())
+        }
+
+        fn after(&mut self, result: ProgramResult) -> ProgramResult {
+            self.calls.borrow_mut().push("after");
+            result
+        }
+    }
+
+    struct Instruction {
+        calls: Calls,
+        result: ProgramResult,
+    }
+
+    impl ProcessInstruction for Instruction {
+        fn process(self) -> ProgramResult {
+            self.calls.borrow_mut().push("process");
+            self.result
+        }
+    }
+
+    fn wrapped(calls: &Calls, fail_before: bool, result: ProgramResult) -> ProgramResult {
+        Middleware::new(
+            Recorder {
+                calls: calls.clone(),
+                fail_before,
+            },
+            Instruction {
+                calls: calls.clone(),
+                result,
+            },
+        )
+        .process()
+    }
+
+    #[test]
+    fn test_middleware_hooks_around_process() {
+        let calls = Calls::default();
+
+        assert_eq!(wrapped(&calls, false, Ok(())), Ok(()));
+        assert_eq!(*calls.borrow(), vec!["before", "process", "after"]);
+    }
+
+    #[test]
+    fn test_middleware_after_sees_error() {
+        let calls = Calls::default();
+
+        assert_eq!(
+            wrapped(&calls, false, Err(ProgramError::InvalidArgument)),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(*calls.borrow(), vec!["before", "process", "after"]);
+    }
+
+    #[test]
+    fn test_middleware_before_error_skips_process() {
+        let calls = Calls::default();
+
+        assert_eq!(wrapped(&calls, true, Ok(())), Err(ProgramError::Custom(1)));
+        assert_eq!(*calls.borrow(), vec!["before"]);
+    }
+}
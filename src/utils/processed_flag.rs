@@ -0,0 +1,89 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed,
+    program_error::ProgramError, pubkey::Pubkey, rent::Rent,
+};
+use solana_system_interface::instruction as system_instruction;
+
+use crate::{error::MplCoreWrapperError, utils::invoke_cpi};
+
+pub const PROCESSED_SEED: &[u8] = b"processed";
+
+/// Flag PDA of this program marking the client-chosen `id` of `payer` as
+/// processed.
+pub fn derive_processed_flag(payer: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROCESSED_SEED, payer.as_ref(), &id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Checks `flag` is the PDA for `id` and no earlier call set it, returning
+/// its bump.
+///
+/// Only this program can assign the PDA to itself, so its owner is the flag.
+pub fn check_processed_flag(
+    flag: &AccountInfo,
+    payer: &Pubkey,
+    id: u64,
+) -> Result<u8, ProgramError> {
+    let (expected, bump) = derive_processed_flag(payer, id);
+
+    if flag.key != &expected {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if flag.owner == &crate::ID {
+        return Err(MplCoreWrapperError::AlreadyProcessed.into());
+    }
+
+    Ok(bump)
+}
+
+/// Creates `flag` as an empty account of this program, so a replay fails
+/// [`check_processed_flag`].
+pub fn set_processed_flag<'info>(
+    flag: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    id: u64,
+    bump: u8,
+    rent: &Rent,
+) -> ProgramResult {
+    let create = system_instruction::create_account(
+        payer.key,
+        flag.key,
+        rent.minimum_balance(0),
+        0,
+        &crate::ID,
+    );
+
+    invoke_cpi("CreateProcessedFlag", flag.key, || {
+        invoke_signed(
+            &create,
+            &[payer.clone(), flag.clone(), system_program.clone()],
+            &[&[
+                PROCESSED_SEED,
+                payer.key.as_ref(),
+                &id.to_le_bytes(),
+                &[bump],
+            ]],
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use solana_sdk_ids::system_program;
+
+    #[test]
+    fn test_check_processed_flag_wrong_address() {
+        let flag = new_test_account(Pubkey::new_unique(), false, true, 0, 0, system_program::ID);
+
+        assert_eq!(
+            check_processed_flag(&flag, &Pubkey::new_unique(), 0),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+}
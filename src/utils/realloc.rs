@@ -0,0 +1,142 @@
+use borsh::BorshSerialize;
+use mpl_core::{
+    accounts::{BaseAssetV1, PluginHeaderV1, PluginRegistryV1},
+    types::{Key, Plugin, PluginAuthority, PluginType, RegistryRecord},
+    DataBlob,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke,
+    program_error::ProgramError, rent::Rent,
+};
+use solana_system_interface::instruction as system_instruction;
+
+use crate::utils::{checked_add_size, invoke_cpi};
+
+/// Asset account size once mpl_core has appended `plugin` and its registry
+/// record, including the header and registry if the asset has no plugins yet.
+pub fn plugin_realloc_size(
+    asset_data: &[u8],
+    plugin: &Plugin,
+    authority: &PluginAuthority,
+) -> Result<usize, ProgramError> {
+    let asset =
+        BaseAssetV1::from_bytes(asset_data).map_err(|_| ProgramError::InvalidAccountData)?;
+    let mut size = asset_data.len();
+
+    if asset_data.len() <= asset.len() {
+        let registry = PluginRegistryV1 {
+            key: Key::PluginRegistryV1,
+            registry: vec![],
+            external_registry: vec![],
+        };
+
+        size = checked_add_size(size, PluginHeaderV1::LEN)?;
+        size = checked_add_size(size, registry.try_to_vec()?.len())?;
+    }
+
+    let record = RegistryRecord {
+        plugin_type: PluginType::from(plugin),
+        authority: authority.clone(),
+        offset: 0,
+    };
+
+    size = checked_add_size(size, plugin.try_to_vec()?.len())?;
+    checked_add_size(size, record.try_to_vec()?.len())
+}
+
+/// Lamports the account needs on top of `lamports` to stay rent exempt at `new_len`.
+pub fn realloc_top_up(rent: &Rent, lamports: u64, new_len: usize) -> u64 {
+    rent.minimum_balance(new_len).saturating_sub(lamports)
+}
+
+/// Tops `account` up from `payer` so it is rent exempt at `new_len`.
+///
+/// mpl_core owns the asset and does the realloc itself during the CPI; this
+/// only makes sure the lamports for the larger size are already there.
+pub fn fund_realloc<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+    rent: &Rent,
+) -> ProgramResult {
+    let top_up = realloc_top_up(rent, account.lamports(), new_len);
+
+    if top_up == 0 {
+        return Ok(());
+    }
+
+    invoke_cpi("FundRealloc", account.key, || {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, top_up),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use mpl_core::types::{FreezeDelegate, TransferDelegate, UpdateAuthority};
+    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn test_plugin_realloc_size_first_plugin() {
+        let plugin = Plugin::FreezeDelegate(FreezeDelegate { frozen: false });
+        let authority = PluginAuthority::Owner;
+        let owner = Pubkey::new_unique();
+
+        let asset = new_asset_account(owner, UpdateAuthority::None, &[]);
+        let expected = new_asset_account(
+            owner,
+            UpdateAuthority::None,
+            &[(plugin.clone(), authority.clone())],
+        );
+
+        assert_eq!(
+            plugin_realloc_size(&asset.data.borrow(), &plugin, &authority),
+            Ok(expected.data_len())
+        );
+    }
+
+    #[test]
+    fn test_plugin_realloc_size_existing_plugins() {
+        let existing = (
+            Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+            PluginAuthority::Owner,
+        );
+        let plugin = Plugin::TransferDelegate(TransferDelegate {});
+        let authority = PluginAuthority::Address {
+            address: Pubkey::new_unique(),
+        };
+        let owner = Pubkey::new_unique();
+
+        let asset = new_asset_account(
+            owner,
+            UpdateAuthority::None,
+            std::slice::from_ref(&existing),
+        );
+        let expected = new_asset_account(
+            owner,
+            UpdateAuthority::None,
+            &[existing, (plugin.clone(), authority.clone())],
+        );
+
+        assert_eq!(
+            plugin_realloc_size(&asset.data.borrow(), &plugin, &authority),
+            Ok(expected.data_len())
+        );
+    }
+
+    #[test]
+    fn test_realloc_top_up() {
+        let rent = Rent::default();
+
+        assert_eq!(
+            realloc_top_up(&rent, rent.minimum_balance(100), 150),
+            rent.minimum_balance(150) - rent.minimum_balance(100)
+        );
+        assert_eq!(realloc_top_up(&rent, rent.minimum_balance(200), 150), 0);
+    }
+}
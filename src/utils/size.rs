@@ -0,0 +1,213 @@
+use borsh::BorshSerialize;
+use mpl_core::types::{Key, PluginAuthorityPair, UpdateAuthority};
+use solana_program::{program_error::ProgramError, rent::Rent};
+
+/// Borsh prefixes strings and vecs with a `u32` length.
+pub const BORSH_LEN_PREFIX: usize = 4;
+
+/// Solana's hard cap on account data, 10 MiB.
+pub const MAX_ASSET_DATA_LEN: usize = 10 * 1024 * 1024;
+
+/// Smallest serialized `BaseAssetV1`: key, owner, a `None` update authority,
+/// empty name and uri, and no seq.
+pub const BASE_ASSET_MIN_LEN: usize =
+    1 + 32 + update_authority_len(&UpdateAuthority::None) + 1 + BORSH_LEN_PREFIX + BORSH_LEN_PREFIX;
+
+/// Smallest serialized `BaseCollectionV1`: key, update authority, empty name
+/// and uri, `num_minted` and `current_size`.
+pub const BASE_COLLECTION_MIN_LEN: usize =
+    base_len(Key::CollectionV1) + BORSH_LEN_PREFIX + BORSH_LEN_PREFIX;
+
+/// Fixed bytes of a `key` account outside what [`checked_asset_size`]
+/// counts, i.e. everything but the name, uri and plugins.
+///
+/// An asset holds an owner, an update authority and a seq option, while a
+/// collection holds an update authority and two `u32` counters. Other
+/// accounts only contribute their key byte.
+///
+/// mpl_core creates every asset with an `Address` or `Collection` update
+/// authority, so an asset counts one with its key.
+pub const fn base_len(key: Key) -> usize {
+    match key {
+        Key::AssetV1 => 1 + 32 + (1 + 32) + 1,
+        Key::CollectionV1 => 1 + 32 + 4 + 4,
+        _ => 1,
+    }
+}
+
+/// Serialized size of `update_authority`: its discriminator, plus the key
+/// of an `Address` or `Collection` authority.
+pub const fn update_authority_len(update_authority: &UpdateAuthority) -> usize {
+    match update_authority {
+        UpdateAuthority::None => 1,
+        UpdateAuthority::Address(_) | UpdateAuthority::Collection(_) => 1 + 32,
+    }
+}
+
+/// Lamports a `key` account holding `data_len` bytes of name, uri and
+/// plugins needs to be rent exempt.
+pub fn rent_exempt_lamports(rent: &Rent, key: Key, data_len: usize) -> Result<u64, ProgramError> {
+    Ok(rent.minimum_balance(checked_add_size(base_len(key), data_len)?))
+}
+
+/// `size + additional`, or `ArithmeticOverflow` instead of wrapping.
+pub fn checked_add_size(size: usize, additional: usize) -> Result<usize, ProgramError> {
+    size.checked_add(additional)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Serialized size of an asset's name, uri and plugins.
+///
+/// Lengths come from instruction data, so every step is checked rather than
+/// trusted not to overflow.
+pub fn checked_asset_size(
+    name_len: usize,
+    uri_len: usize,
+    plugins: &[PluginAuthorityPair],
+) -> Result<usize, ProgramError> {
+    let mut size = checked_add_size(BORSH_LEN_PREFIX, name_len)?;
+    size = checked_add_size(size, BORSH_LEN_PREFIX)?;
+    size = checked_add_size(size, uri_len)?;
+
+    for plugin in plugins {
+        size = checked_add_size(size, plugin.try_to_vec()?.len())?;
+    }
+
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mpl_core::types::{FreezeDelegate, Plugin};
+
+    #[test]
+    fn test_checked_asset_size_matches_borsh() {
+        let plugins = vec![PluginAuthorityPair {
+            plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+            authority: None,
+        }];
+        let expected = "Asset".to_string().try_to_vec().unwrap().len()
+            + "https://example.com"
+                .to_string()
+                .try_to_vec()
+                .unwrap()
+                .len()
+            + plugins[0].try_to_vec().unwrap().len();
+
+        assert_eq!(
+            checked_asset_size("Asset".len(), "https://example.com".len(), &plugins),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn test_checked_asset_size_overflow() {
+        assert_eq!(
+            checked_asset_size(usize::MAX, 0, &[]),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            checked_asset_size(usize::MAX / 2, usize::MAX / 2, &[]),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_base_len_asset_and_collection() {
+        assert_ne!(base_len(Key::AssetV1), base_len(Key::CollectionV1));
+        assert_eq!(base_len(Key::AssetV1), 67);
+        assert_eq!(base_len(Key::CollectionV1), 41);
+    }
+
+    #[test]
+    fn test_base_len_matches_borsh() {
+        use mpl_core::accounts::{BaseAssetV1, BaseCollectionV1};
+        use solana_program::pubkey::Pubkey;
+
+        let asset = BaseAssetV1 {
+            key: Key::AssetV1,
+            owner: Pubkey::new_unique(),
+            update_authority: UpdateAuthority::None,
+            name: String::new(),
+            uri: String::new(),
+            seq: None,
+        };
+        let collection = BaseCollectionV1 {
+            key: Key::CollectionV1,
+            update_authority: Pubkey::new_unique(),
+            name: String::new(),
+            uri: String::new(),
+            num_minted: 0,
+            current_size: 0,
+        };
+
+        assert_eq!(asset.try_to_vec().unwrap().len(), BASE_ASSET_MIN_LEN);
+        assert_eq!(
+            update_authority_len(&asset.update_authority),
+            UpdateAuthority::None.try_to_vec().unwrap().len()
+        );
+        assert_eq!(
+            collection.try_to_vec().unwrap().len(),
+            BASE_COLLECTION_MIN_LEN
+        );
+    }
+
+    #[test]
+    fn test_base_len_matches_borsh_address_authority() {
+        use mpl_core::accounts::BaseAssetV1;
+        use solana_program::pubkey::Pubkey;
+
+        for update_authority in [
+            UpdateAuthority::Address(Pubkey::new_unique()),
+            UpdateAuthority::Collection(Pubkey::new_unique()),
+        ] {
+            let asset = BaseAssetV1 {
+                key: Key::AssetV1,
+                owner: Pubkey::new_unique(),
+                update_authority,
+                name: "Asset".to_string(),
+                uri: "https://example.com".to_string(),
+                seq: None,
+            };
+            let data_len = checked_asset_size(asset.name.len(), asset.uri.len(), &[]).unwrap();
+
+            assert_eq!(
+                update_authority_len(&asset.update_authority),
+                asset.update_authority.try_to_vec().unwrap().len()
+            );
+            assert_eq!(
+                asset.try_to_vec().unwrap().len(),
+                base_len(Key::AssetV1) + data_len
+            );
+        }
+    }
+
+    #[test]
+    fn test_rent_exempt_lamports_by_key() {
+        let rent = Rent::default();
+        let data_len = checked_asset_size("Asset".len(), "https://example.com".len(), &[]).unwrap();
+
+        assert_eq!(
+            rent_exempt_lamports(&rent, Key::AssetV1, data_len),
+            Ok(rent.minimum_balance(base_len(Key::AssetV1) + data_len))
+        );
+        assert_ne!(
+            rent_exempt_lamports(&rent, Key::CollectionV1, data_len),
+            rent_exempt_lamports(&rent, Key::AssetV1, data_len)
+        );
+        assert_eq!(
+            rent_exempt_lamports(&rent, Key::CollectionV1, usize::MAX),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_checked_add_size_overflow() {
+        assert_eq!(
+            checked_add_size(usize::MAX - 1, 2),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(checked_add_size(usize::MAX - 1, 1), Ok(usize::MAX));
+    }
+}
@@ -1,4 +1,93 @@
-use solana_program::{account_info::AccountInfo, clock::Epoch, pubkey::Pubkey};
+use borsh::BorshSerialize;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1, PluginHeaderV1, PluginRegistryV1},
+    types::{Key, Plugin, PluginAuthority, PluginType, RegistryRecord, UpdateAuthority},
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Epoch,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+};
+use solana_sdk_ids::{bpf_loader_upgradeable, system_program};
+use std::{cell::RefCell, sync::Once};
+
+thread_local! {
+    static RECORDED_CPIS: RefCell<Vec<(&'static str, Pubkey)>> = const { RefCell::new(Vec::new()) };
+    static RECORDED_ACCOUNT_ERRORS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = const { RefCell::new(None) };
+    static RECORDED_INSTRUCTIONS: RefCell<Vec<Instruction>> = const { RefCell::new(Vec::new()) };
+}
+
+static RECORDING_STUBS: Once = Once::new();
+
+/// Keeps the instruction of every off-chain CPI, which the default stub drops.
+struct RecordingStubs;
+
+impl SyscallStubs for RecordingStubs {
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        _account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        RECORDED_INSTRUCTIONS
+            .with(|instructions| instructions.borrow_mut().push(instruction.clone()));
+        Ok(())
+    }
+}
+
+pub fn record_cpi(name: &'static str, target: Pubkey) {
+    RECORDING_STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(RecordingStubs));
+    });
+    RECORDED_CPIS.with(|cpis| cpis.borrow_mut().push((name, target)));
+}
+
+/// Instructions of the CPIs made on this test's thread since the last call.
+pub fn take_recorded_instructions() -> Vec<Instruction> {
+    RECORDED_INSTRUCTIONS.with(|instructions| instructions.take())
+}
+
+/// Names of the CPIs made through `invoke_cpi` on this test's thread since
+/// the last call.
+pub fn take_recorded_cpis() -> Vec<&'static str> {
+    take_recorded_cpi_targets()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Like [`take_recorded_cpis`], with the account each CPI acted on.
+pub fn take_recorded_cpi_targets() -> Vec<(&'static str, Pubkey)> {
+    RECORDED_CPIS.with(|cpis| cpis.take())
+}
+
+pub fn record_account_error(message: String) {
+    RECORDED_ACCOUNT_ERRORS.with(|errors| errors.borrow_mut().push(message));
+}
+
+/// Account check failures logged on this test's thread since the last call.
+pub fn take_recorded_account_errors() -> Vec<String> {
+    RECORDED_ACCOUNT_ERRORS.with(|errors| errors.take())
+}
+
+/// Stands in for the runtime's return data: `program_id` is the program a
+/// mocked CPI left `data` behind for.
+pub fn mock_return_data(program_id: Pubkey, data: &[u8]) {
+    RETURN_DATA.with(|return_data| *return_data.borrow_mut() = Some((program_id, data.to_vec())));
+}
+
+pub fn get_return_data() -> Option<(Pubkey, Vec<u8>)> {
+    RETURN_DATA.with(|return_data| return_data.borrow().clone())
+}
+
+/// Like the runtime, attributes the data to this program.
+pub fn set_return_data(data: &[u8]) {
+    mock_return_data(crate::ID, data);
+}
 
 pub fn new_test_account(
     key: Pubkey,
@@ -23,3 +112,299 @@ pub fn new_test_account(
         Epoch::default(),
     )
 }
+
+/// Executable account standing in for a deployed program.
+pub fn new_program_account(key: Pubkey) -> AccountInfo<'static> {
+    let mut account = new_test_account(key, false, false, 1, 0, bpf_loader_upgradeable::ID);
+    account.executable = true;
+    account
+}
+
+/// Builds an mpl_core owned asset account laid out the way mpl_core stores it:
+/// the base asset, then a plugin header, the plugins and the plugin registry.
+pub fn new_asset_account(
+    owner: Pubkey,
+    update_authority: UpdateAuthority,
+    plugins: &[(Plugin, PluginAuthority)],
+) -> AccountInfo<'static> {
+    let mut data = BaseAssetV1 {
+        key: Key::AssetV1,
+        owner,
+        update_authority,
+        name: "Asset".to_string(),
+        uri: "https://example.com/asset.json".to_string(),
+        seq: None,
+    }
+    .try_to_vec()
+    .unwrap();
+    append_plugins(&mut data, plugins);
+
+    let asset = new_test_account(
+        Pubkey::new_unique(),
+        false,
+        true,
+        1,
+        data.len(),
+        mpl_core::ID,
+    );
+    asset.data.borrow_mut().copy_from_slice(&data);
+    asset
+}
+
+/// Appends the plugin header, the plugins and the plugin registry to the
+/// base account in `data`.
+fn append_plugins(data: &mut Vec<u8>, plugins: &[(Plugin, PluginAuthority)]) {
+    if !plugins.is_empty() {
+        let header_offset = data.len();
+        data.resize(header_offset + PluginHeaderV1::LEN, 0);
+
+        let mut registry = Vec::with_capacity(plugins.len());
+        for (plugin, authority) in plugins {
+            registry.push(RegistryRecord {
+                plugin_type: PluginType::from(plugin),
+                authority: authority.clone(),
+                offset: data.len() as u64,
+            });
+            data.extend(plugin.try_to_vec().unwrap());
+        }
+
+        let header = PluginHeaderV1 {
+            key: Key::PluginHeaderV1,
+            plugin_registry_offset: data.len() as u64,
+        }
+        .try_to_vec()
+        .unwrap();
+        data[header_offset..header_offset + PluginHeaderV1::LEN].copy_from_slice(&header);
+
+        data.extend(
+            PluginRegistryV1 {
+                key: Key::PluginRegistryV1,
+                registry,
+                external_registry: vec![],
+            }
+            .try_to_vec()
+            .unwrap(),
+        );
+    }
+}
+
+/// Builds a plugin-less, empty mpl_core collection account.
+pub fn new_collection_account(update_authority: Pubkey) -> AccountInfo<'static> {
+    new_sized_collection_account(update_authority, 0, &[])
+}
+
+/// Builds an mpl_core collection account holding `current_size` assets.
+pub fn new_sized_collection_account(
+    update_authority: Pubkey,
+    current_size: u32,
+    plugins: &[(Plugin, PluginAuthority)],
+) -> AccountInfo<'static> {
+    let mut data = BaseCollectionV1 {
+        key: Key::CollectionV1,
+        update_authority,
+        name: "Collection".to_string(),
+        uri: "https://example.com/collection.json".to_string(),
+        num_minted: current_size,
+        current_size,
+    }
+    .try_to_vec()
+    .unwrap();
+    append_plugins(&mut data, plugins);
+
+    let collection = new_test_account(
+        Pubkey::new_unique(),
+        false,
+        true,
+        1,
+        data.len(),
+        mpl_core::ID,
+    );
+    collection.data.borrow_mut().copy_from_slice(&data);
+    collection
+}
+
+/// Stands in for an omitted optional account.
+pub fn absent_account() -> AccountInfo<'static> {
+    new_test_account(system_program::ID, false, false, 1, 0, system_program::ID)
+}
+
+/// `CreateNftV1` accounts in instruction order, every optional one present
+/// and `owner` signing. `authority` is the collection's update authority.
+pub fn create_nft_accounts(owner: Pubkey) -> Vec<AccountInfo<'static>> {
+    let authority = Pubkey::new_unique();
+
+    vec![
+        new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+        new_collection_account(authority),
+        new_test_account(authority, true, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+        new_test_account(owner, true, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID),
+        new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID),
+        new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+        absent_account(),
+    ]
+}
+
+/// `UpdateNftV1` accounts in instruction order with `authority` signing and
+/// no collection move, around a plugin-less asset.
+pub fn update_nft_accounts(authority: Pubkey) -> Vec<AccountInfo<'static>> {
+    vec![
+        new_asset_account(Pubkey::new_unique(), UpdateAuthority::None, &[]),
+        new_test_account(Pubkey::new_unique(), false, true, 1, 0, system_program::ID),
+        new_test_account(authority, true, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+        new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID),
+        new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+        absent_account(),
+    ]
+}
+
+/// `TransferNftV1` accounts in instruction order around `asset` and
+/// `authority`, without collection or log wrapper.
+pub fn transfer_nft_accounts(
+    asset: AccountInfo<'static>,
+    authority: AccountInfo<'static>,
+) -> Vec<AccountInfo<'static>> {
+    vec![
+        asset,
+        absent_account(),
+        authority,
+        new_test_account(Pubkey::new_unique(), false, false, 1, 0, system_program::ID),
+        new_test_account(Pubkey::new_unique(), true, true, 1, 0, system_program::ID),
+        new_test_account(system_program::ID, false, false, 1, 0, system_program::ID),
+        absent_account(),
+        new_test_account(mpl_core::ID, false, false, 1, 0, mpl_core::ID),
+    ]
+}
+
+/// Checks `metas` line up with `accounts` and carry exactly the flags `parse`
+/// validates: the accounts parse with the metas' flags, and clearing any flag
+/// makes them fail, except on the `unchecked` accounts whose flags only the
+/// CPI relies on.
+#[cfg(feature = "client")]
+pub fn check_account_metas(
+    accounts: &[AccountInfo<'static>],
+    metas: &[solana_program::instruction::AccountMeta],
+    unchecked: &[usize],
+    parse: impl Fn(&[AccountInfo<'static>]) -> solana_program::entrypoint::ProgramResult,
+) {
+    assert_eq!(
+        metas.iter().map(|meta| meta.pubkey).collect::<Vec<_>>(),
+        accounts
+            .iter()
+            .map(|account| *account.key)
+            .collect::<Vec<_>>()
+    );
+
+    let with_flags = |flags: &dyn Fn(usize) -> (bool, bool)| {
+        accounts
+            .iter()
+            .enumerate()
+            .map(|(i, account)| {
+                let mut account = account.clone();
+                (account.is_signer, account.is_writable) = flags(i);
+                account
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let res = parse(&with_flags(&|i| (metas[i].is_signer, metas[i].is_writable)));
+    assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+
+    for (i, meta) in metas.iter().enumerate() {
+        if unchecked.contains(&i) {
+            continue;
+        }
+
+        if meta.is_signer {
+            let accounts = with_flags(&|j| (metas[j].is_signer && j != i, metas[j].is_writable));
+            assert!(
+                parse(&accounts).is_err(),
+                "signer flag of account {i} is not checked"
+            );
+        }
+
+        if meta.is_writable {
+            let accounts = with_flags(&|j| (metas[j].is_signer, metas[j].is_writable && j != i));
+            assert!(
+                parse(&accounts).is_err(),
+                "writable flag of account {i} is not checked"
+            );
+        }
+    }
+}
+
+#[cfg(any(feature = "create", feature = "update", feature = "transfer"))]
+mod tests {
+    use super::*;
+    use solana_program::program_error::ProgramError;
+
+    /// Dropping or adding an account must break parsing, so the helpers hold
+    /// exactly as many accounts as each `try_from` destructures.
+    #[cfg(feature = "transfer")]
+    fn check_exact_len(
+        accounts: Vec<AccountInfo<'static>>,
+        parse: impl Fn(&[AccountInfo<'static>]) -> Result<(), ProgramError>,
+    ) {
+        let res = parse(&accounts);
+        assert!(res.is_ok(), "expected Ok, but got Err: {:?}", res);
+        assert_eq!(
+            parse(&accounts[..accounts.len() - 1]).unwrap_err(),
+            ProgramError::NotEnoughAccountKeys
+        );
+
+        let mut extra = accounts.clone();
+        extra.push(absent_account());
+        assert_eq!(
+            parse(&extra).unwrap_err(),
+            ProgramError::NotEnoughAccountKeys
+        );
+    }
+
+    #[cfg(feature = "create")]
+    #[test]
+    fn test_create_nft_accounts_len() {
+        use crate::instructions::CreateNftV1Accounts;
+
+        // The trailing processed flag may be left off, but no account before it.
+        let accounts = create_nft_accounts(Pubkey::new_unique());
+        assert!(CreateNftV1Accounts::try_from(accounts.as_slice()).is_ok());
+        assert!(CreateNftV1Accounts::try_from(&accounts[..accounts.len() - 1]).is_ok());
+        assert_eq!(
+            CreateNftV1Accounts::try_from(&accounts[..accounts.len() - 2]).unwrap_err(),
+            ProgramError::NotEnoughAccountKeys
+        );
+    }
+
+    #[cfg(feature = "update")]
+    #[test]
+    fn test_update_nft_accounts_len() {
+        use crate::instructions::UpdateNftV1Accounts;
+
+        // The trailing new collection may be left off, but no account before it.
+        let accounts = update_nft_accounts(Pubkey::new_unique());
+        assert!(UpdateNftV1Accounts::try_from(accounts.as_slice()).is_ok());
+        assert!(UpdateNftV1Accounts::try_from(&accounts[..accounts.len() - 1]).is_ok());
+        assert_eq!(
+            UpdateNftV1Accounts::try_from(&accounts[..accounts.len() - 2]).unwrap_err(),
+            ProgramError::NotEnoughAccountKeys
+        );
+    }
+
+    #[cfg(feature = "transfer")]
+    #[test]
+    fn test_transfer_nft_accounts_len() {
+        let authority =
+            new_test_account(Pubkey::new_unique(), true, false, 1, 0, system_program::ID);
+        check_exact_len(
+            transfer_nft_accounts(
+                new_asset_account(*authority.key, UpdateAuthority::None, &[]),
+                authority,
+            ),
+            |accounts| crate::instructions::TransferNftV1Accounts::try_from(accounts).map(|_| ()),
+        );
+    }
+}